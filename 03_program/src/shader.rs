@@ -9,7 +9,7 @@ impl Shader {
         let shader;
 
         // Load shader file
-        let text = std::fs::read(file_path.as_ref())?;
+        let text = std::fs::read(framework::asset::find(file_path.as_ref()))?;
         let text_len = text.len() as i32;
 
         // Create and compile shader
@@ -1,6 +1,5 @@
 pub enum Error {
-    InitError(glfw::InitError),
-    CreateWindowError,
+    FrameworkError(framework::Error),
     ReadFileError(std::io::Error),
     CompileShaderError(String),
     LinkProgramError(String),
@@ -12,11 +11,8 @@ impl std::error::Error for Error {}
 impl std::fmt::Debug for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Error::InitError(description) => {
-                write!(f, "Failed to initialize glfw\n{}", description)
-            }
-            Error::CreateWindowError => {
-                write!(f, "Failed to create GLFW window")
+            Error::FrameworkError(description) => {
+                write!(f, "{:?}", description)
             }
             Error::ReadFileError(description) => {
                 write!(f, "Failed to read file\n{}", description)
@@ -40,9 +36,9 @@ impl std::fmt::Display for Error {
     }
 }
 
-impl From<glfw::InitError> for Error {
-    fn from(description: glfw::InitError) -> Self {
-        Error::InitError(description)
+impl From<framework::Error> for Error {
+    fn from(description: framework::Error) -> Self {
+        Error::FrameworkError(description)
     }
 }
 
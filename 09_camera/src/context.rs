@@ -1,4 +1,5 @@
 use super::{errors, shader, program, vertex_array, buffer, texture, image};
+use framework::camera;
 use nalgebra_glm as glm;
 
 pub struct Context {
@@ -160,30 +161,16 @@ impl Context {
         let angle = glm::pi::<f32>() * time * 0.1;
         let camera_position = glm::vec3(f32::sin(angle) * 10.0, 5.0, f32::cos(angle) * 10.0); // 카메라 위치
         let camera_target = glm::vec3(0.0, 0.0, 0.0); // 카메라가 바라보는 대상
-        let up = glm::vec3(0.0, 1.0, 0.0); // 화면 상의 위쪽 방향
-
-        // 대상에 대한 카메라 백터 정보 계산
-        let camera_direction = glm::normalize(&(camera_position - camera_target));
-        let camera_right = glm::normalize(&glm::cross(&up, &camera_direction));
-        let camera_up = glm::cross(&camera_direction, &camera_right);
-        let camera_position_mat = glm::mat4(
-            1.0, 0.0, 0.0, camera_position.x,
-            0.0, 1.0, 0.0, camera_position.y,
-            0.0, 0.0, 1.0, camera_position.z,
-            0.0, 0.0, 0.0, 1.0
-        ); // 전역 좌표 공간 기준으로 카메라의 위치를 이동하는 행렬
-        let camera_mat = glm::mat4(
-            camera_right.x, camera_up.x, camera_direction.x, 0.0,
-            camera_right.y, camera_up.y, camera_direction.y, 0.0,
-            camera_right.z, camera_up.z, camera_direction.z, 0.0,
-            0.0, 0.0, 0.0, 1.0
-        ); // 카메라 로컬 백터 공간으로 변환하는 행렬
+
+        // 카메라가 대상을 바라보도록 pitch, yaw를 계산한다
+        let mut camera = camera::Camera::new(camera_position);
+        camera.look_at(&camera_target).set_perspective(45.0).set_clip_planes(0.01, 20.0);
 
         unsafe {
             gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT); // State-using function
             self.program.use_(); // 사용할 프로그램을 지정
-            let view = glm::inverse(&(camera_position_mat * camera_mat)); // let view = glm::look_at(&camera_position, &camera_target, &up);
-            let projection = glm::perspective(super::WINDOW_WIDTH as f32/super::WINDOW_HEIGHT as f32, glm::pi::<f32>() / 180.0 * 45.0, 0.01, 20.0);
+            let view = camera.get_view_matrix(); // 카메라의 위치와 방향으로 만든 행렬의 역행렬
+            let projection = camera.get_projection_matrix(super::WINDOW_WIDTH as f32 / super::WINDOW_HEIGHT as f32);
 
             for (index, cube_position) in cube_positions.iter().enumerate() {
                 let position = cube_position;
@@ -1,4 +1,5 @@
 use super::{errors, shader, program, vertex_array, buffer, texture, image};
+use framework::camera;
 use nalgebra_glm as glm;
 
 pub struct Context {
@@ -12,11 +13,9 @@ pub struct Context {
     tbo2: texture::Texture,
     mat4: glm::TMat4<f32>,
     cube_positions: Vec<glm::TVec3<f32>>,
-    camera_position: glm::TVec3<f32>,
-    camera_front: glm::TVec3<f32>,
-    up: glm::TVec3<f32>,
-    camera_pitch: f32,
-    camera_yaw: f32,
+    camera: camera::Camera,
+    camera_controller: camera::Controller,
+    camera_input: camera::Input,
     previous_mouse_position: glm::TVec2<f32>,
     mouse_position: glm::TVec2<f32>,
     camera_control: bool,
@@ -159,13 +158,12 @@ impl Context {
         let transform = mat4 * projection * view * model * scale;
         program.set_uniform_matrix4fv("transform\0", &transform); // 프로그램의 전역 변수 `transform`에 4차원 형렬의 주소값을 할당
 
-        // 카메라 위치 정보
-        let camera_position = glm::vec3(0.0, 0.0, 3.0); // 카메라 위치
-        let camera_front = glm::vec3(0.0, 0.0, -1.0); // 카메라가 바라보는 방향
-        let up = glm::vec3(0.0, 1.0, 0.0); // 화면 상의 위쪽 방향
-
-        let camera_pitch = 0f32;
-        let camera_yaw = 0f32;
+        // 카메라 정보
+        let mut camera = camera::Camera::new(glm::vec3(0.0, 0.0, 3.0)); // 카메라 위치
+        camera.set_perspective(45.0).set_clip_planes(0.01, 20.0);
+        let mut camera_controller = camera::Controller::new(camera::ControllerMode::FreeFly);
+        camera_controller.sensitivity = 0.1;
+        let camera_input = camera::Input::new();
 
         // 마우스 위치 정보
         let previous_mouse_position = glm::vec2(0.0, 0.0);
@@ -173,15 +171,15 @@ impl Context {
 
         let camera_control = false;
 
-        Ok(Context { width, height, program, vao, vbo, ebo, tbo1, tbo2, mat4, cube_positions, camera_position, camera_front, up, camera_pitch, camera_yaw, previous_mouse_position, mouse_position, camera_control })
+        Ok(Context { width, height, program, vao, vbo, ebo, tbo1, tbo2, mat4, cube_positions, camera, camera_controller, camera_input, previous_mouse_position, mouse_position, camera_control })
     }
 
     pub fn render(&mut self, time: f32) {
         unsafe {
             gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT); // State-using function
             self.program.use_(); // 사용할 프로그램을 지정
-            let view = glm::look_at(&self.camera_position, &(&self.camera_position + &self.camera_front), &self.up);
-            let projection = glm::perspective(self.width as f32 / self.height as f32, glm::pi::<f32>() / 180.0 * 45.0, 0.01, 20.0);
+            let view = self.camera.get_view_matrix();
+            let projection = self.camera.get_projection_matrix(self.width as f32 / self.height as f32);
 
             for (index, cube_position) in self.cube_positions.iter().enumerate() {
                 let position = cube_position;
@@ -195,29 +193,16 @@ impl Context {
     }
 
     pub fn process_input(&mut self, window: &glfw::PWindow, delta_time: f32) {
-        let camera_speed = delta_time * 2.5;
-        // let camera_direction = glm::normalize(&(self.camera_position - self.camera_target));
-        if window.get_key(glfw::Key::W) == glfw::Action::Press {
-            self.camera_position += camera_speed * &self.camera_front;
-        }
-        if window.get_key(glfw::Key::S) == glfw::Action::Press {
-            self.camera_position -= camera_speed * &self.camera_front;
-        }
-
-        let camera_right = glm::normalize(&glm::cross(&self.up, &-self.camera_front));
-        if window.get_key(glfw::Key::D) == glfw::Action::Press {
-            self.camera_position += camera_speed * &camera_right;
-        }
-        if window.get_key(glfw::Key::A) == glfw::Action::Press {
-            self.camera_position -= camera_speed * &camera_right;
-        }
-
-        if window.get_key(glfw::Key::Space) == glfw::Action::Press {
-            self.camera_position += camera_speed * &self.up;
-        }
-        if window.get_key(glfw::Key::LeftShift) == glfw::Action::Press {
-            self.camera_position -= camera_speed * &self.up;
-        }
+        let axis = |positive: glfw::Key, negative: glfw::Key| {
+            (window.get_key(positive) == glfw::Action::Press) as i32 as f32 - (window.get_key(negative) == glfw::Action::Press) as i32 as f32
+        };
+        self.camera_input.movement = glm::vec3(
+            axis(glfw::Key::D, glfw::Key::A),
+            axis(glfw::Key::Space, glfw::Key::LeftShift),
+            axis(glfw::Key::W, glfw::Key::S),
+        );
+        self.camera_controller.update(&mut self.camera, &self.camera_input, delta_time);
+        self.camera_input = camera::Input::new();
     }
 
     pub fn reshape(&mut self, width: u32, height: u32) {
@@ -232,25 +217,8 @@ impl Context {
             return;
         }
 
-        let delta_position = self.mouse_position - self.previous_mouse_position;
-
-        let sensitivity: f32 = 0.1;
-        self.camera_yaw -= delta_position.x * sensitivity;
-        self.camera_pitch -= delta_position.y * sensitivity;
-        if self.camera_yaw < 0.0 {
-            self.camera_yaw += 360.0;
-        }
-        if self.camera_yaw > 360.0 {
-            self.camera_yaw -= 360.0;
-        }
-        if self.camera_pitch < -89.0 {
-            self.camera_pitch = -89.0;
-        }
-        if self.camera_pitch > 89.0 {
-            self.camera_pitch = 89.0;
-        }
-
-        self.camera_front = (glm::rotate(&self.mat4, glm::pi::<f32>() / 180.0 * self.camera_yaw, &glm::vec3(0.0, 1.0, 0.0)) * glm::rotate(&self.mat4, glm::pi::<f32>() / 180.0 * self.camera_pitch, &glm::vec3(1.0, 0.0, 0.0)) * glm::vec4(0.0, 0.0, -1.0, 0.0)).xyz();
+        // 다음 프레임에 카메라 컨트롤러가 한꺼번에 처리한다
+        self.camera_input.rotation += self.mouse_position - self.previous_mouse_position;
 
         self.previous_mouse_position = self.mouse_position;
     }
@@ -11,7 +11,7 @@ pub struct Image {
 impl Image {
     pub fn load<S>(file_path: S) -> Result<Image, errors::Error> where S: AsRef<str> {
         // 이미지 파일과 OpenGL은 이미지의 시작점이 상하 대칭 관계에 있기 때문에 불러온 이미지 파일을 상하 반전시켜야 한다
-        let image = image::open(file_path.as_ref())?.flipv();
+        let image = image::open(framework::asset::find(file_path.as_ref()))?.flipv();
         let width = image.width();
         let height = image.height();
         let channel_count = image.color().channel_count();
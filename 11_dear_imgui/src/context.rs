@@ -1,4 +1,5 @@
 use super::{errors, shader, program, vertex_array, buffer, texture, image};
+use framework::camera;
 use nalgebra_glm as glm;
 
 pub struct Context {
@@ -12,12 +13,9 @@ pub struct Context {
     tbo1: texture::Texture,
     tbo2: texture::Texture,
     cube_positions: Vec<glm::Vec3>,
-    camera_position: glm::Vec3,
-    camera_front: glm::Vec3,
-    camera_right: glm::Vec3,
-    camera_pitch: f32,
-    camera_yaw: f32,
-    camera_fov: i32,
+    camera: camera::Camera,
+    camera_controller: camera::Controller,
+    camera_input: camera::Input,
     previous_mouse_position: glm::Vec2,
     mouse_position: glm::Vec2,
     camera_control: bool,
@@ -146,8 +144,7 @@ impl Context {
         // let vec4 = glm::vec4(1.0, 0.0, 0.0, 1.0); // 위치 (1, 0, 0)의 `점` 동차 좌표
         let model = glm::rotate(&glm::Mat4::identity(), 30.0f32.to_radians(), &glm::vec3(1.0, 0.0, 0.0)); // 단위 행렬 기준 x축으로 30도만큼 회전하는 행렬
         let view = glm::translate(&glm::Mat4::identity(), &glm::vec3(0.0, 0.0, -3.0)); // 단위 행렬 기준 (0.0, 0.0, -3)만큼 평행 이동 하는 행렬
-        let camera_fov = 45;
-        let projection = glm::perspective(width as f32 / height as f32, (camera_fov as f32).to_radians(), 0.01, 10.0); // 종횡비 16:9, 세로 화각 45도의 원근 투영
+        let projection = glm::perspective(width as f32 / height as f32, 45.0f32.to_radians(), 0.01, 10.0); // 종횡비 16:9, 세로 화각 45도의 원근 투영
         let scale = glm::scale(&glm::Mat4::identity(), &glm::vec3(0.5, 0.5, 0.5)); // 단위 행렬 기준 모든 축에 대해 0.5배 확대하는 행렬
         // let result = translate * rotate * scale * vec4; // 확대, 회전, 평행 이동 순으로 점에 선형 변환 적용
         // spdlog::info!("Transformated vec4: [{}, {}, {}]", result.x, result.y, result.z);
@@ -155,12 +152,10 @@ impl Context {
         program.set_uniform_matrix4fv("transform\0", &transform); // 프로그램의 전역 변수 `transform`에 4차원 형렬의 주소값을 할당
 
         // 카메라 정보
-        let camera_position = glm::vec3(0.0, 0.0, 4.0); // 카메라 위치
-        // let camera_direction = glm::normalize(&(self.camera_position - self.camera_target));
-        let camera_front = glm::vec3(0.0, 0.0, -1.0); // 카메라가 바라보는 방향
-        let camera_right = glm::normalize(&glm::cross(&glm::vec3(0.0, 1.0, 0.0), &-camera_front));
-        let camera_pitch = 0f32; // 끄덕끄덕
-        let camera_yaw = 0f32; // 도리도리
+        let mut camera = camera::Camera::new(glm::vec3(0.0, 0.0, 4.0)); // 카메라 위치
+        camera.set_perspective(45.0).set_clip_planes(0.01, 20.0);
+        let camera_controller = camera::Controller::new(camera::ControllerMode::FreeFly);
+        let camera_input = camera::Input::new();
 
         // 마우스 위치 정보
         let previous_mouse_position = glm::vec2(0.0, 0.0);
@@ -168,7 +163,7 @@ impl Context {
 
         let camera_control = false;
 
-        Ok(Context { width, height, clear_color, program, vao, vbo, ebo, tbo1, tbo2, cube_positions, camera_position, camera_front, camera_right, camera_pitch, camera_yaw, camera_fov, previous_mouse_position, mouse_position, camera_control })
+        Ok(Context { width, height, clear_color, program, vao, vbo, ebo, tbo1, tbo2, cube_positions, camera, camera_controller, camera_input, previous_mouse_position, mouse_position, camera_control })
     }
 
     pub fn render(&mut self, time: f32, ui: &mut imgui::Ui) {
@@ -179,32 +174,28 @@ impl Context {
                 }
             }
             ui.separator();
-            let mut camera_position = mint::Vector3::from_slice(self.camera_position.as_slice());
+            let mut camera_position = mint::Vector3::from_slice(self.camera.get_position().as_slice());
             if ui.input_float3("camera position", &mut camera_position).build() {
-                self.camera_position.x = camera_position.x;
-                self.camera_position.y = camera_position.y;
-                self.camera_position.z = camera_position.z;
+                self.camera.set_position(glm::vec3(camera_position.x, camera_position.y, camera_position.z));
             };
-            ui.slider("camera pitch", -89f32, 89f32, &mut self.camera_pitch);
-            ui.slider("camera yaw", -360f32, 360f32, &mut self.camera_yaw);
-            if ui.input_int("camera fov", &mut self.camera_fov).build() {}
+            let mut camera_pitch = self.camera.get_pitch();
+            let mut camera_yaw = self.camera.get_yaw();
+            if ui.slider("camera pitch", -89f32, 89f32, &mut camera_pitch) | ui.slider("camera yaw", 0f32, 360f32, &mut camera_yaw) {
+                self.camera.set_rotation(camera_pitch, camera_yaw);
+            }
+            if let camera::Projection::Perspective { mut fov } = self.camera.get_projection() && ui.slider("camera fov", 1f32, 120f32, &mut fov) {
+                self.camera.set_perspective(fov);
+            }
             if ui.button("reset camera") {
-                self.camera_position.x = 0.0;
-                self.camera_position.y = 0.0;
-                self.camera_position.z = 4.0;
-                self.camera_pitch = 0.0;
-                self.camera_yaw = 0.0;
-                self.camera_fov = 45;
+                self.camera.set_position(glm::vec3(0.0, 0.0, 4.0)).set_rotation(0.0, 0.0).set_perspective(45.0);
             }
         });
 
         unsafe {
             gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT); // State-using function
             self.program.use_(); // 사용할 프로그램을 지정
-            self.camera_front = (glm::rotate(&glm::Mat4::identity(), self.camera_yaw.to_radians(), &glm::vec3(0.0, 1.0, 0.0)) * glm::rotate(&glm::Mat4::identity(), self.camera_pitch.to_radians(), &glm::vec3(1.0, 0.0, 0.0)) * glm::vec4(0.0, 0.0, -1.0, 0.0)).xyz();
-            self.camera_right = glm::normalize(&glm::cross(&glm::vec3(0.0, 1.0, 0.0), &-self.camera_front));
-            let view = glm::look_at(&self.camera_position, &(&self.camera_position + &self.camera_front), &glm::vec3(0.0, 1.0, 0.0));
-            let projection = glm::perspective(self.width as f32 / self.height as f32, (self.camera_fov as f32).to_radians(), 0.01, 20.0);
+            let view = self.camera.get_view_matrix();
+            let projection = self.camera.get_projection_matrix(self.width as f32 / self.height as f32);
 
             for (index, cube_position) in self.cube_positions.iter().enumerate() {
                 let position = cube_position;
@@ -218,27 +209,16 @@ impl Context {
     }
 
     pub fn process_input(&mut self, window: &glfw::PWindow, delta_time: f32) {
-        let camera_speed = delta_time * 2.5;
-        if window.get_key(glfw::Key::W) == glfw::Action::Press {
-            self.camera_position += camera_speed * &self.camera_front;
-        }
-        if window.get_key(glfw::Key::S) == glfw::Action::Press {
-            self.camera_position -= camera_speed * &self.camera_front;
-        }
-
-        if window.get_key(glfw::Key::D) == glfw::Action::Press {
-            self.camera_position += camera_speed * &self.camera_right;
-        }
-        if window.get_key(glfw::Key::A) == glfw::Action::Press {
-            self.camera_position -= camera_speed * &self.camera_right;
-        }
-
-        if window.get_key(glfw::Key::Space) == glfw::Action::Press {
-            self.camera_position += camera_speed * &glm::vec3(0.0, 1.0, 0.0);
-        }
-        if window.get_key(glfw::Key::LeftShift) == glfw::Action::Press {
-            self.camera_position -= camera_speed * &glm::vec3(0.0, 1.0, 0.0);
-        }
+        let axis = |positive: glfw::Key, negative: glfw::Key| {
+            (window.get_key(positive) == glfw::Action::Press) as i32 as f32 - (window.get_key(negative) == glfw::Action::Press) as i32 as f32
+        };
+        self.camera_input.movement = glm::vec3(
+            axis(glfw::Key::D, glfw::Key::A),
+            axis(glfw::Key::Space, glfw::Key::LeftShift),
+            axis(glfw::Key::W, glfw::Key::S),
+        );
+        self.camera_controller.update(&mut self.camera, &self.camera_input, delta_time);
+        self.camera_input = camera::Input::new();
     }
 
     pub fn reshape(&mut self, width: u32, height: u32) {
@@ -253,23 +233,8 @@ impl Context {
             return;
         }
 
-        let delta_position = self.mouse_position - self.previous_mouse_position;
-
-        let sensitivity = 0.15f32;
-        self.camera_yaw -= delta_position.x * sensitivity;
-        self.camera_pitch -= delta_position.y * sensitivity;
-        if self.camera_yaw < 0.0 {
-            self.camera_yaw += 360.0;
-        }
-        if self.camera_yaw > 360.0 {
-            self.camera_yaw -= 360.0;
-        }
-        if self.camera_pitch < -89.0 {
-            self.camera_pitch = -89.0;
-        }
-        if self.camera_pitch > 89.0 {
-            self.camera_pitch = 89.0;
-        }
+        // 다음 프레임에 카메라 컨트롤러가 한꺼번에 처리한다
+        self.camera_input.rotation += self.mouse_position - self.previous_mouse_position;
 
         self.previous_mouse_position = self.mouse_position;
     }
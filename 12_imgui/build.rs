@@ -0,0 +1,49 @@
+// embed_assets 기능을 켜면 워크스페이스의 shader/와 resources/ 안의 파일을 실행 파일에 넣는 목록(assets.rs)을 만든다
+use std::{fs, io, path::{Path, PathBuf}};
+
+const ASSET_DIRECTORIES: [&str; 2] = ["shader", "resources"];
+
+fn main() -> io::Result<()> {
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    let manifest_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let root = manifest_dir.parent().unwrap().to_path_buf();
+
+    println!("cargo:rerun-if-changed=build.rs");
+    if std::env::var_os("CARGO_FEATURE_EMBED_ASSETS").is_none() {
+        return Ok(());
+    }
+
+    let mut files = Vec::new();
+    for directory in ASSET_DIRECTORIES {
+        let path = root.join(directory);
+        println!("cargo:rerun-if-changed={}", path.display());
+        collect_files(&path, &mut files)?;
+    }
+    files.sort();
+
+    let mut source = String::from("pub static ASSETS: &[(&str, &[u8])] = &[\n");
+    for file in &files {
+        println!("cargo:rerun-if-changed={}", file.display());
+        // 키는 워크스페이스 루트 기준의 '/'로 구분한 경로
+        let key = file.strip_prefix(&root).unwrap().components().map(|component| component.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/");
+        source += &format!("    ({:?}, include_bytes!({:?})),\n", key, file.display().to_string());
+    }
+    source += "];\n";
+    fs::write(out_dir.join("assets.rs"), source)
+}
+
+fn collect_files(directory: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    if !directory.is_dir() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            println!("cargo:rerun-if-changed={}", path.display());
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
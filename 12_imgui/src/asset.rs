@@ -0,0 +1,133 @@
+use super::{errors, pack::{self, Pack}};
+use std::{borrow::Cow, path::{Path, PathBuf}, sync::RwLock};
+
+// 쉐이더, 이미지, 모델, 설정 파일은 모두 이 함수들로 읽는다
+// 상대 경로는 마운트한 순서대로 찾아서 처음 찾은 것을 사용하고, 절대 경로는 디스크에서 바로 읽는다
+// 디렉토리 마운트는 항상 디스크에서 읽으므로 파일을 고치면 다음에 불러올 때 바로 반영된다
+
+const DEFAULT_PACK_NAME: &str = "assets.pak";
+
+#[cfg(feature = "embed_assets")]
+mod embedded {
+    include!(concat!(env!("OUT_DIR"), "/assets.rs"));
+}
+
+pub enum Mount {
+    Directory(PathBuf),
+    Pack(Pack),
+    Embedded, // embed_assets 기능으로 실행 파일에 넣어 둔 shader/와 resources/
+}
+
+impl Mount {
+    fn read(&self, path: &str) -> std::io::Result<Option<Cow<'static, [u8]>>> {
+        match self {
+            Mount::Directory(directory) => match std::fs::read(directory.join(path)) {
+                Ok(data) => Ok(Some(Cow::Owned(data))),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(err) => Err(err),
+            },
+            Mount::Pack(pack) => Ok(pack.read(path)?.map(Cow::Owned)),
+            Mount::Embedded => Ok(find_embedded(path).map(Cow::Borrowed)),
+        }
+    }
+
+    fn describe(&self, path: &str) -> String {
+        match self {
+            Mount::Directory(directory) => directory.join(path).display().to_string(),
+            Mount::Pack(pack) => format!("{}:{}", pack.get_file_path().display(), pack::normalize(path)),
+            Mount::Embedded => format!("embedded:{}", pack::normalize(path)),
+        }
+    }
+}
+
+// 로딩 스레드에서도 읽을 수 있도록 thread_local이 아닌 전역 잠금을 사용한다
+static MOUNTS: RwLock<Vec<Mount>> = RwLock::new(Vec::new());
+
+#[cfg(feature = "embed_assets")]
+fn find_embedded(path: &str) -> Option<&'static [u8]> {
+    let path = pack::normalize(path);
+    embedded::ASSETS.iter().find(|(key, _)| *key == path).map(|(_, data)| *data)
+}
+
+#[cfg(not(feature = "embed_assets"))]
+fn find_embedded(_: &str) -> Option<&'static [u8]> {
+    None
+}
+
+// 먼저 마운트한 것이 우선한다
+pub fn mount(mount: Mount) {
+    spdlog::info!("Mounted {}", mount.describe(""));
+    MOUNTS.write().unwrap().push(mount);
+}
+
+pub fn mount_directory<P>(directory: P) where P: AsRef<Path> {
+    mount(Mount::Directory(directory.as_ref().to_path_buf()));
+}
+
+pub fn mount_pack<P>(file_path: P) -> Result<(), errors::Error> where P: AsRef<Path> {
+    let pack = Pack::open(file_path)?;
+    spdlog::info!("Opened pack \"{}\" ({} files)", pack.get_file_path().display(), pack.get_paths().len());
+    mount(Mount::Pack(pack));
+    Ok(())
+}
+
+// 우선순위: framework::asset의 디렉토리 > 실행 파일 옆의 assets.pak > 실행 파일에 넣어 둔 파일
+pub fn mount_defaults() {
+    for directory in framework::asset::get_directories() {
+        mount_directory(directory);
+    }
+    if let Some(directory) = std::env::current_exe().ok().and_then(|path| path.parent().map(Path::to_path_buf)) {
+        let pack_path = directory.join(DEFAULT_PACK_NAME);
+        if pack_path.is_file() && let Err(err) = mount_pack(&pack_path) {
+            spdlog::warn!("{:?}", err);
+        }
+    }
+    if cfg!(feature = "embed_assets") {
+        mount(Mount::Embedded);
+    }
+}
+
+pub fn read(path: &str) -> Result<Cow<'static, [u8]>, errors::Error> {
+    if Path::new(path).is_absolute() {
+        return Ok(Cow::Owned(std::fs::read(path)?));
+    }
+    let mounts = MOUNTS.read().unwrap();
+    // 아무것도 마운트하지 않았으면 현재 디렉토리에서 찾는다
+    if mounts.is_empty() {
+        return Ok(Cow::Owned(std::fs::read(path)?));
+    }
+    for mount in mounts.iter() {
+        if let Some(data) = mount.read(path)? {
+            return Ok(data);
+        }
+    }
+    Err(errors::Error::AssetNotFoundError(path.to_owned(), mounts.iter().map(|mount| mount.describe(path)).collect()))
+}
+
+pub fn read_to_string(path: &str) -> Result<String, errors::Error> {
+    let data = read(path)?;
+    String::from_utf8(data.into_owned()).map_err(|err| errors::Error::ReadFileError(std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{}: {}", path, err))))
+}
+
+// 디스크에 있는 파일이면 실제 경로를 돌려준다, 다른 파일을 상대 경로로 참조하는 파일(.gltf 등)에 사용한다
+pub fn resolve(path: &str) -> Option<PathBuf> {
+    if Path::new(path).is_absolute() {
+        return Some(PathBuf::from(path));
+    }
+    let mounts = MOUNTS.read().unwrap();
+    if mounts.is_empty() {
+        return Some(PathBuf::from(path));
+    }
+    for mount in mounts.iter() {
+        match mount {
+            Mount::Directory(directory) if directory.join(path).is_file() => return Some(directory.join(path)),
+            Mount::Directory(_) => {}
+            // 팩이나 실행 파일 안에서 먼저 찾으면 디스크 경로가 없다
+            Mount::Pack(pack) if pack.contains(path) => return None,
+            Mount::Pack(_) => {}
+            Mount::Embedded if find_embedded(path).is_some() => return None,
+            Mount::Embedded => {}
+        }
+    }
+    None
+}
@@ -0,0 +1,43 @@
+// 파일과 디렉토리를 하나의 팩 파일로 묶는다
+// cargo run -p imgui --bin packer -- assets.pak shader resources
+// 팩 안의 경로는 실행한 디렉토리 기준의 상대 경로이므로 워크스페이스 루트에서 실행한다
+#[allow(dead_code)] // contains처럼 packer에서는 쓰지 않는 함수가 있다
+#[path = "../pack.rs"]
+mod pack;
+
+use std::path::{Path, PathBuf};
+
+fn main() -> std::io::Result<()> {
+    let arguments: Vec<String> = std::env::args().skip(1).collect();
+    let Some((output, inputs)) = arguments.split_first().filter(|(_, inputs)| !inputs.is_empty()) else {
+        eprintln!("usage: packer <output.pak> <file or directory>...");
+        std::process::exit(1);
+    };
+
+    let mut files = Vec::new();
+    for input in inputs {
+        collect_files(Path::new(input), &mut files)?;
+    }
+    files.sort();
+    let files: Vec<(String, PathBuf)> = files.into_iter().map(|path| (pack::normalize(&path.to_string_lossy()), path)).collect();
+    pack::write(output, &files)?;
+
+    // 다시 열어서 목록이 올바르게 쓰였는지 확인한다
+    let pack = pack::Pack::open(output)?;
+    for path in pack.get_paths() {
+        println!("{}", path);
+    }
+    println!("Packed {} files into \"{}\"", files.len(), output);
+    Ok(())
+}
+
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    if path.is_dir() {
+        for entry in std::fs::read_dir(path)? {
+            collect_files(&entry?.path(), files)?;
+        }
+    } else {
+        files.push(path.to_path_buf());
+    }
+    Ok(())
+}
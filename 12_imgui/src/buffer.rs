@@ -1,39 +1,44 @@
+use super::{gl_check, gl_debug, gl_state};
+use std::cell::Cell;
+
 pub struct Buffer {
     buffer: u32,
-    type_: u32,
-    usage: u32,
+    size: Cell<isize>, // 마지막으로 할당한 바이트 수
 }
 
 impl Buffer {
+    #[cfg_attr(any(debug_assertions, feature = "gl_check"), track_caller)]
     pub fn create(type_: u32, data_size: isize, data: *const std::ffi::c_void, usage: u32) -> Buffer {
         let mut buffer = 0;
 
         unsafe {
             gl::GenBuffers(1, &mut buffer);
             spdlog::info!("Created buffer({})", buffer);
-            gl::BindBuffer(type_, buffer);
+            gl_state::bind_buffer(type_, buffer);
             gl::BufferData(type_, data_size, data, usage);
         }
+        gl_check::check("Buffer::create");
         
-        Buffer { buffer, type_, usage }
+        Buffer { buffer, size: Cell::new(data_size) }
     }
 
+    #[cfg_attr(any(debug_assertions, feature = "gl_check"), track_caller)]
     pub fn set(&self, type_: u32, data_size: isize, data: *const std::ffi::c_void, usage: u32) {
         unsafe {
-            gl::BindBuffer(type_, self.buffer);
+            gl_state::bind_buffer(type_, self.buffer);
             gl::BufferData(type_, data_size, data, usage);
         }
+        gl_check::check("Buffer::set");
+        self.size.set(data_size);
         
     }
 
-    pub fn get(&self) -> u32 {
-        self.buffer
+    pub fn get_size(&self) -> isize {
+        self.size.get()
     }
 
-    pub fn bind(&self) {
-        unsafe {
-            gl::BindBuffer(self.type_, self.buffer);
-        }
+    pub fn set_label(&self, label: &str) {
+        gl_debug::set_label(gl::BUFFER, self.buffer, label);
     }
 }
 
@@ -42,6 +47,7 @@ impl Drop for Buffer {
         unsafe {
             gl::DeleteBuffers(1, &mut self.buffer);
         }
+        gl_state::on_buffer_deleted(self.buffer);
         spdlog::info!("Dropped buffer({})", self.buffer);
     }
 }
\ No newline at end of file
@@ -1,122 +1,80 @@
-use super::{errors, shader, program, vertex_array, buffer, texture, image};
+use super::{errors, program, material, primitive, scene, light, ibl, shadow, camera, frustum, picking, gizmo, debug_draw, render_queue, profiler, resource, loader, gl_state, gl_check, input, gamepad};
 use nalgebra_glm as glm;
+use std::{cell::{OnceCell, RefCell}, rc::Rc, time::Duration};
+
+const LOADER_BUDGET: Duration = Duration::from_millis(4); // 한 프레임에 GL 객체를 만드는 데 쓰는 시간
+
+const FOCUS_DISTANCE: f32 = 4.0; // 궤도의 중심과 직교 투영의 크기를 정하는 카메라 앞의 거리
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ShadingModel {
+    BlinnPhong,
+    Pbr,
+}
 
 pub struct Context {
     width: u32,
     height: u32,
     clear_color: glm::Vec4,
-    program: program::Program,
-    vao: vertex_array::VertexArray,
-    vbo: buffer::Buffer,
-    ebo: buffer::Buffer,
-    tbo1: texture::Texture,
-    tbo2: texture::Texture,
-    cube_positions: Vec<glm::Vec3>,
-    camera_position: glm::Vec3,
-    camera_front: glm::Vec3,
-    camera_right: glm::Vec3,
-    camera_pitch: f32,
-    camera_yaw: f32,
-    camera_fov: i32,
+    program: Rc<program::Program>,
+    pbr_program: Rc<program::Program>,
+    shading_model: ShadingModel,
+    environment: ibl::Environment,
+    shadow_map: shadow::ShadowMap,
+    show_shadow_map: bool,
+    outline_program: Rc<OnceCell<program::Program>>, // 선택한 물체가 생길 때 쓰므로 따로 불러온다
+    max_light_count: usize,
+    default_material: Rc<material::Material>,
+    scene: scene::Scene,
+    cubes: Vec<Rc<RefCell<scene::Node>>>,
+    cull_stats: frustum::CullStats,
+    selection: Option<picking::Hit>,
+    gizmo: gizmo::Gizmo,
+    debug_renderer: debug_draw::Renderer,
+    show_bounds: bool,
+    show_profiler: bool,
+    screenshot_path: Option<String>, // update에서 요청하고 render의 마지막에 저장한다
+    render_queue: render_queue::RenderQueue,
+    camera: camera::Camera,
+    camera_controller: camera::Controller,
+    camera_input: camera::Input,
     previous_mouse_position: glm::Vec2,
     mouse_position: glm::Vec2,
-    camera_control: bool,
-    key_down: [bool; 6],
+    input: input::InputMap,
+    loader: loader::Loader,
 }
 
 impl Context {
     pub fn create() -> Result<Context, errors::Error> {
-        let vertex_shader = shader::Shader::create("shader/camera.vert", gl::VERTEX_SHADER)?;
-        let fragment_shader = shader::Shader::create("shader/camera.frag", gl::FRAGMENT_SHADER)?;
-        spdlog::info!("Created vertex shader({})", vertex_shader.get());
-        spdlog::info!("Created fragment shader({})", fragment_shader.get());
-
-        let program = program::Program::create(vec![&vertex_shader, &fragment_shader])?;
-        spdlog::info!("Created program({})", program.get());
+        let max_light_count = light::DEFAULT_MAX_LIGHT_COUNT;
+        let defines = [("MAX_LIGHT_COUNT", max_light_count.to_string()), ("MAX_SHADOW_COUNT", shadow::MAX_SHADOW_COUNT.to_string())];
+        let program = resource::load_program("shader/lighting.vert", "shader/lighting.frag", &defines)?;
         program.use_();  // 사용할 프로그램을 지정
 
-        let vertices: [f32; 192] = [
-            0.5, 0.5, -0.5, 0.0, 0.0, 1.0, 1.0, 1.0,
-            0.5, -0.5, -0.5, 0.0, 0.0, 1.0, 1.0, 0.0,
-            -0.5, -0.5, -0.5, 0.0, 0.0, 1.0, 0.0, 0.0,
-            -0.5, 0.5, -0.5, 0.0, 0.0, 1.0, 0.0, 1.0,
-
-            -0.5, 0.5, 0.5, 1.0, 0.0, 0.0, 1.0, 1.0,
-            -0.5, -0.5, 0.5, 1.0, 0.0, 0.0, 1.0, 0.0,
-            0.5, -0.5, 0.5, 1.0, 0.0, 0.0, 0.0, 0.0,
-            0.5, 0.5, 0.5, 1.0, 0.0, 0.0, 0.0, 1.0,
-
-            -0.5, 0.5, -0.5, 1.0, 1.0, 0.0, 1.0, 1.0,
-            -0.5, -0.5, -0.5, 1.0, 1.0, 0.0, 1.0, 0.0,
-            -0.5, -0.5, 0.5, 1.0, 1.0, 0.0, 0.0, 0.0,
-            -0.5, 0.5, 0.5, 1.0, 1.0, 0.0, 0.0, 1.0,
-
-            0.5, 0.5, 0.5, 0.0, 1.0, 0.0, 1.0, 1.0,
-            0.5, -0.5, 0.5, 0.0, 1.0, 0.0, 1.0, 0.0,
-            0.5, -0.5, -0.5, 0.0, 1.0, 0.0, 0.0, 0.0,
-            0.5, 0.5, -0.5, 0.0, 1.0, 0.0, 0.0, 1.0,
-
-            0.5, 0.5, -0.5, 1.0, 0.0, 1.0, 1.0, 1.0,
-            0.5, 0.5, 0.5, 1.0, 0.0, 1.0, 1.0, 0.0,
-            -0.5, 0.5, 0.5, 1.0, 0.0, 1.0, 0.0, 0.0,
-            -0.5, 0.5, -0.5, 1.0, 0.0, 1.0, 0.0, 1.0,
-
-            0.5, -0.5, -0.5, 0.0, 1.0, 1.0, 1.0, 1.0,
-            0.5, -0.5, 0.5, 0.0, 1.0, 1.0, 1.0, 0.0,
-            -0.5, -0.5, 0.5, 0.0, 1.0, 1.0, 0.0, 0.0,
-            -0.5, -0.5, -0.5, 0.0, 1.0, 1.0, 0.0, 1.0
-        ];
-        let indices: [u32; 36] = [
-            0, 1, 2,
-            2, 3, 0,
-
-            4, 5, 6,
-            6, 7, 4,
-
-            8, 9, 10,
-            10, 11, 8,
-
-            12, 13, 14,
-            14, 15, 12,
-
-            16, 17, 18,
-            18, 19, 16,
-
-            20, 21, 22,
-            22, 23, 20,
-        ];
+        let pbr_program = resource::load_program("shader/pbr.vert", "shader/pbr.frag", &defines)?;
+        let shading_model = ShadingModel::Pbr;
+        let environment = ibl::Environment::create(None)?;
+        let shadow_map = shadow::ShadowMap::create(shadow::ShadowSettings::new())?;
+        let show_shadow_map = false;
+        let mut loader = loader::Loader::create();
+        let outline_program = loader.load_program("shader/outline.vert", "shader/outline.frag", &[]);
 
         let clear_color = glm::vec4(0.2, 0.2, 0.2, 1.0);
         unsafe {
             gl::ClearColor(clear_color.x, clear_color.y, clear_color.z, clear_color.w); // State-setting function
         }
 
-        // 사용할 vao를 먼저 바인딩 해줘야 나머지 오르젝트들이 vao에 저장된다
-        let vao = vertex_array::VertexArray::create(); // 새로운 vao를 생성
-        vao.bind(); // 사용할 vao를 지정
-        let vbo = buffer::Buffer::create(gl::ARRAY_BUFFER, size_of_val(&vertices).cast_signed(), vertices.as_ptr().cast(), gl::STATIC_DRAW); // 새로운 vbo를 생성
-        let ebo = buffer::Buffer::create(gl::ELEMENT_ARRAY_BUFFER, size_of_val(&indices).cast_signed(), indices.as_ptr().cast(), gl::STATIC_DRAW); // 새로운 ebo를 생성
-
-        // 초기에 `VertexAttribPointer`의 포인터는 각 버택스 버퍼에 바인딩된 배열의 시작 주소를 가리켰지만, vao가 등장하면서 vao가 가리키는 배열의 오프셋을 의미하게 되었다
-        // 속성 0번: position
-        vao.set(0, 3, gl::FLOAT, gl::FALSE, (size_of::<f32>() * 8) as i32, (size_of::<f32>() * 0) as *const _); // vao의 0번 속성을 활성화하고, 해당하는 vbo 데이터를 전달
-        // 속성 1번: color
-        vao.set(1, 3, gl::FLOAT, gl::FALSE, (size_of::<f32>() * 8) as i32, (size_of::<f32>() * 3) as *const _); // vao의 1번 속성을 활성화하고, 해당하는 vbo 데이터를 전달
-        // 속성 2번: texture coordinate
-        vao.set(2, 2, gl::FLOAT, gl::FALSE, (size_of::<f32>() * 8) as i32, (size_of::<f32>() * 6) as *const _); // vao의 2번 속성을 활성화하고, 해당하는 vbo 데이터를 전달
-
-        let awesomeface = image::Image::load("resources/images/awesomeface.png")?;
-        spdlog::info!("Loaded image file \"resources/images/awesomeface.png\" ({} x {}, {} channels)", awesomeface.get_width(), awesomeface.get_height(), awesomeface.get_channel_count());
-        let container = image::Image::load("resources/images/container.jpg")?;
-        spdlog::info!("Loaded image file \"resources/images/container.jpg\" ({} x {}, {} channels)", container.get_width(), container.get_height(), container.get_channel_count());
-
-        let tbo1 = texture::Texture::create();
-        tbo1.set_texture(&awesomeface);
-        let tbo2 = texture::Texture::create();
-        tbo2.set_texture(&container);
-
-        program.set_uniform1i("texture0\0", 0); // 프로그램의 전역 변수 `texture0`에 0을 할당
-        program.set_uniform1i("texture1\0", 1); // 프로그램의 전역 변수 `texture1`에 1을 할당
+        let cube = resource::get_mesh("primitive/cube", || primitive::Primitive::cube(1.0, 1).create_mesh());
+        let tbo = loader.load_texture("resources/images/container.jpg", gl::CLAMP_TO_EDGE);
+
+        // 재질이 없는 메시에 사용하는 재질
+        let mut default_material = material::Material::new("default");
+        default_material.diffuse = glm::vec3(1.0, 1.0, 1.0);
+        default_material.specular = glm::vec3(0.5, 0.5, 0.5);
+        default_material.roughness = 0.6;
+        default_material.diffuse_texture = Some(tbo);
+        default_material.base_color_texture = default_material.diffuse_texture.clone();
+        let default_material = Rc::new(default_material);
 
         let cube_positions = vec![
             glm::vec3::<f32>(-2.0, 0.0, -2.0),
@@ -130,131 +88,470 @@ impl Context {
             glm::vec3::<f32>(2.0, 0.0, 2.0),
         ];
 
+        // 장면 그래프: root -> cubes -> cube 0 ~ 8, root -> shapes -> 도형들
+        let scene = scene::Scene::create();
+        let cube_group = scene.add_node("cubes");
+        let mut cubes = Vec::new();
+        for (index, cube_position) in cube_positions.iter().enumerate() {
+            let node = scene::Node::add_child(&cube_group, scene::Node::create(&format!("cube {}", index)));
+            node.borrow_mut().set_translation(*cube_position).set_mesh(cube.clone());
+            cubes.push(node);
+        }
+
+        // 큐브 뒤에 나머지 도형들을 한 줄로 늘어놓는다
+        let shapes = [
+            ("uv sphere", glm::vec3(-3.0, 0.0, -4.5), primitive::Primitive::uv_sphere(0.5, 32, 16)),
+            ("icosphere", glm::vec3(-1.5, 0.0, -4.5), primitive::Primitive::icosphere(0.5, 2)),
+            ("cylinder", glm::vec3(0.0, 0.0, -4.5), primitive::Primitive::cylinder(0.5, 1.0, 32, 1)),
+            ("cone", glm::vec3(1.5, 0.0, -4.5), primitive::Primitive::cone(0.5, 1.0, 32, 1)),
+            ("torus", glm::vec3(3.0, 0.0, -4.5), primitive::Primitive::torus(0.4, 0.15, 32, 16)),
+            ("capsule", glm::vec3(4.5, 0.0, -4.5), primitive::Primitive::capsule(0.3, 0.4, 32, 8)),
+        ];
+        let shape_group = scene.add_node("shapes");
+        for (name, position, shape) in shapes {
+            let node = scene::Node::add_child(&shape_group, scene::Node::create(name));
+            node.borrow_mut().set_translation(position).set_mesh(Rc::new(shape.create_mesh()));
+        }
+
+        // 그림자를 받을 바닥
+        let ground = scene.add_node("ground");
+        ground.borrow_mut().set_translation(glm::vec3(0.0, -1.0, 0.0)).set_mesh(resource::get_mesh("primitive/plane", || primitive::Primitive::plane(20.0, 1).create_mesh()));
+
+        // 광원도 장면 그래프의 노드에 붙는다, 빛은 노드의 -z 방향으로 나아간다
+        let sun = scene.add_node("sun");
+        sun.borrow_mut().set_rotation(glm::quat_angle_axis(30.0f32.to_radians(), &glm::vec3(0.0, 1.0, 0.0)) * glm::quat_angle_axis(-45.0f32.to_radians(), &glm::vec3(1.0, 0.0, 0.0)));
+        sun.borrow_mut().set_light(*light::Light::new(light::LightType::Directional).set_color(glm::vec3(1.0, 0.95, 0.9), 0.6).set_cast_shadow(true));
+        let lamp = scene.add_node("lamp");
+        lamp.borrow_mut().set_translation(glm::vec3(0.0, 1.5, 0.0));
+        lamp.borrow_mut().set_light(*light::Light::new(light::LightType::Point).set_color(glm::vec3(1.0, 0.6, 0.3), 1.0));
+        let spot = scene.add_node("spot");
+        spot.borrow_mut().set_translation(glm::vec3(0.0, 4.0, 0.0)).set_rotation(glm::quat_angle_axis(-90.0f32.to_radians(), &glm::vec3(1.0, 0.0, 0.0)));
+        spot.borrow_mut().set_light(*light::Light::new(light::LightType::Spot).set_cone(15.0, 25.0).set_cast_shadow(true));
+
         let width = super::WINDOW_WIDTH;
         let height = super::WINDOW_HEIGHT;
 
-        // let vec4 = glm::vec4(1.0, 0.0, 0.0, 1.0); // 위치 (1, 0, 0)의 `점` 동차 좌표
-        let model = glm::rotate(&glm::Mat4::identity(), 30.0f32.to_radians(), &glm::vec3(1.0, 0.0, 0.0)); // 단위 행렬 기준 x축으로 30도만큼 회전하는 행렬
-        let view = glm::translate(&glm::Mat4::identity(), &glm::vec3(0.0, 0.0, -3.0)); // 단위 행렬 기준 (0.0, 0.0, -3)만큼 평행 이동 하는 행렬
-        let camera_fov = 45;
-        let projection = glm::perspective(width as f32 / height as f32, (camera_fov as f32).to_radians(), 0.01, 10.0); // 종횡비 16:9, 세로 화각 45도의 원근 투영
-        let scale = glm::scale(&glm::Mat4::identity(), &glm::vec3(0.5, 0.5, 0.5)); // 단위 행렬 기준 모든 축에 대해 0.5배 확대하는 행렬
-        // let result = translate * rotate * scale * vec4; // 확대, 회전, 평행 이동 순으로 점에 선형 변환 적용
-        // spdlog::info!("Transformated vec4: [{}, {}, {}]", result.x, result.y, result.z);
-        let transform = glm::Mat4::identity() * projection * view * model * scale;
-        program.set_uniform_matrix4fv("transform\0", &transform); // 프로그램의 전역 변수 `transform`에 4차원 형렬의 주소값을 할당
-
         // 카메라 정보
-        let camera_position = glm::vec3(0.0, 0.0, 4.0); // 카메라 위치
-        // let camera_direction = glm::normalize(&(self.camera_position - self.camera_target));
-        let camera_front = glm::vec3(0.0, 0.0, -1.0); // 카메라가 바라보는 방향
-        let camera_right = glm::normalize(&glm::cross(&glm::vec3(0.0, 1.0, 0.0), &-camera_front));
-        let camera_pitch = 0f32; // 끄덕끄덕
-        let camera_yaw = 0f32; // 도리도리
+        let mut camera = camera::Camera::new(glm::vec3(0.0, 0.0, 4.0)); // 카메라 위치
+        camera.set_perspective(45.0).set_clip_planes(0.01, 20.0);
+        let camera_controller = camera::Controller::new(camera::ControllerMode::FreeFly);
+        let camera_input = camera::Input::new();
+
+        let cull_stats = frustum::CullStats::new();
+        let selection = None;
+        let gizmo = gizmo::Gizmo::create()?;
+        let debug_renderer = debug_draw::Renderer::create()?;
+        let show_bounds = false;
+        let show_profiler = false;
+        let screenshot_path = None;
+        let render_queue = render_queue::RenderQueue::new();
 
         // 마우스 위치 정보
         let previous_mouse_position = glm::vec2(0.0, 0.0);
         let mouse_position = glm::vec2(0.0, 0.0);
 
-        let camera_control = false;
+        // 입력 연결 정보
+        let input = match input::InputMap::load("resources/config/input.cfg") {
+            Ok(input) => input,
+            Err(err) => {
+                spdlog::warn!("{:?}\nUse default input bindings", err);
+                input::InputMap::create_default()
+            }
+        };
 
-        // 키보드 정보
-        let key_down = [false; 6];
+        Ok(Context { width, height, clear_color, program, pbr_program, shading_model, environment, shadow_map, show_shadow_map, outline_program, max_light_count, default_material, scene, cubes, cull_stats, selection, gizmo, debug_renderer, show_bounds, show_profiler, screenshot_path, render_queue, camera, camera_controller, camera_input, previous_mouse_position, mouse_position, input, loader })
+    }
 
-        Ok(Context { width, height, clear_color, program, vao, vbo, ebo, tbo1, tbo2, cube_positions, camera_position, camera_front, camera_right, camera_pitch, camera_yaw, camera_fov, previous_mouse_position, mouse_position, camera_control, key_down })
+    // 로딩 스레드에서 읽는 동안 빈 노드를 장면에 먼저 붙여 둔다
+    pub fn load_model<S>(&mut self, file_path: S) where S: AsRef<str> {
+        let node = self.loader.load_model(file_path.as_ref());
+        scene::Node::add_child(&self.scene.root, node);
     }
 
-    pub fn render(&mut self, time: f32, delta_time: f32) {
-        // 카메라 이동
-        let camera_speed = delta_time * 2.5;
-        if self.key_down[0] { // W
-            self.camera_position += camera_speed * &self.camera_front;
+    // 매 프레임 호출한다, 다 불러올 때까지 화면 아래에 진행 막대를 그린다
+    pub fn update_loader(&mut self) {
+        let _scope = profiler::scope("loader");
+        self.loader.update(LOADER_BUDGET);
+        let progress = self.loader.get_progress();
+        if !progress.is_finished() {
+            let min = glm::vec2(self.width as f32 * 0.25, self.height as f32 - 40.0);
+            let max = glm::vec2(self.width as f32 * 0.75, self.height as f32 - 30.0);
+            debug_draw::screen_rect(&min, &max, &debug_draw::Style::new(glm::vec4(1.0, 1.0, 1.0, 0.3)));
+            // 화면 선분만 그릴 수 있으므로 가로선을 쌓아서 채운다
+            let fill_x = min.x + (max.x - min.x) * progress.get_fraction();
+            let fill_style = debug_draw::Style::new(glm::vec4(0.3, 0.8, 1.0, 1.0));
+            for row in 0..(max.y - min.y) as u32 {
+                let y = min.y + row as f32 + 0.5;
+                debug_draw::screen_line(&glm::vec2(min.x, y), &glm::vec2(fill_x, y), &fill_style);
+            }
         }
-        if self.key_down[1] { // A
-            self.camera_position -= camera_speed * &self.camera_right;
+    }
+
+    // equirectangular 형식의 HDR 이미지로 IBL 환경 맵을 다시 만든다
+    pub fn load_environment<S>(&mut self, file_path: S) -> Result<(), errors::Error> where S: AsRef<str> {
+        self.environment = ibl::Environment::create(Some(file_path.as_ref()))?;
+        Ok(())
+    }
+
+    // 그리기 전에 매 프레임 호출한다, 이번 프레임에 눌린 키로 상태를 바꾼다
+    pub fn update(&mut self) {
+        if self.input.is_pressed("camera_mode") {
+            self.cycle_camera_mode();
+        }
+        if self.input.is_pressed("camera_projection") {
+            self.toggle_camera_projection();
+        }
+        // 실행 중에 장면의 광원을 켜고 끈다
+        if self.input.is_pressed("toggle_light") && let Some(spot) = self.scene.find("spot") && let Some(light) = spot.borrow_mut().light.as_mut() {
+            light.enabled = !light.enabled;
+        }
+        if self.input.is_pressed("log_stats") {
+            spdlog::info!("Culling: {} drawn, {} culled", self.cull_stats.drawn, self.cull_stats.culled);
+            let queue_stats = self.render_queue.get_stats();
+            spdlog::info!("Render queue: {} items, {} program / {} material / {} mesh changes, {} saved", queue_stats.item_count, queue_stats.program_changes, queue_stats.material_changes, queue_stats.mesh_changes, queue_stats.saved);
+            let state_stats = gl_state::get_stats();
+            spdlog::info!("GL state calls: {} issued, {} skipped", state_stats.issued, state_stats.skipped);
         }
-        if self.key_down[2] { // S
-            self.camera_position -= camera_speed * &self.camera_front;
+        if self.input.is_pressed("toggle_pbr") {
+            self.shading_model = match self.shading_model {
+                ShadingModel::BlinnPhong => ShadingModel::Pbr,
+                ShadingModel::Pbr => ShadingModel::BlinnPhong,
+            };
+        }
+        if self.input.is_pressed("toggle_bounds") {
+            self.show_bounds = !self.show_bounds;
+            // 켤 때의 카메라 절두체를 잠시 남겨 두어 카메라를 옮긴 뒤에 컬링 범위를 확인할 수 있게 한다
+            if self.show_bounds {
+                let view_projection = self.camera.get_projection_matrix(self.width as f32 / self.height as f32) * self.camera.get_view_matrix();
+                debug_draw::frustum(&view_projection, debug_draw::Style::new(glm::vec4(0.3, 0.8, 1.0, 1.0)).set_duration(5.0));
+            }
+        }
+        // 그림자 맵을 보면서 bias를 조절한다
+        if self.input.is_pressed("toggle_shadow_map") {
+            self.show_shadow_map = !self.show_shadow_map;
+        }
+        if self.input.is_pressed("shadow_bias_up") || self.input.is_pressed("shadow_bias_down") {
+            let scale = if self.input.is_pressed("shadow_bias_up") { 1.25 } else { 0.8 };
+            self.shadow_map.settings.bias *= scale;
+            spdlog::info!("Shadow bias: {}", self.shadow_map.settings.bias);
+        }
+        if self.input.is_pressed("toggle_cascades") {
+            let mut settings = self.shadow_map.settings;
+            settings.cascade_count = if settings.cascade_count == 1 { shadow::MAX_CASCADE_COUNT } else { 1 };
+            match shadow::ShadowMap::create(settings) {
+                Ok(shadow_map) => self.shadow_map = shadow_map,
+                Err(err) => spdlog::error!("{:?}", err),
+            }
         }
-        if self.key_down[3] { // D
-            self.camera_position += camera_speed * &self.camera_right;
+        // 기즈모의 종류와 축의 기준 공간을 바꾼다
+        if self.input.is_pressed("gizmo_translate") {
+            self.gizmo.mode = gizmo::GizmoMode::Translate;
         }
-        if self.key_down[4] { // Space
-            self.camera_position += camera_speed * &glm::vec3(0.0, 1.0, 0.0);
+        if self.input.is_pressed("gizmo_rotate") {
+            self.gizmo.mode = gizmo::GizmoMode::Rotate;
         }
-        if self.key_down[5] { // LeftShift
-            self.camera_position -= camera_speed * &glm::vec3(0.0, 1.0, 0.0);
+        if self.input.is_pressed("gizmo_scale") {
+            self.gizmo.mode = gizmo::GizmoMode::Scale;
         }
+        if self.input.is_pressed("gizmo_space") {
+            self.gizmo.space = match self.gizmo.space {
+                gizmo::GizmoSpace::Local => gizmo::GizmoSpace::World,
+                gizmo::GizmoSpace::World => gizmo::GizmoSpace::Local,
+            };
+        }
+        if self.input.is_pressed("screenshot") {
+            self.screenshot_path = Some(format!("screenshot_{}.png", std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or(0)));
+        }
+        if self.input.is_pressed("toggle_profiler") {
+            self.show_profiler = !self.show_profiler;
+        }
+        if self.input.is_pressed("list_resources") {
+            resource::log_usage();
+        }
+        // 기록된 프레임을 로그로 요약하고 chrome://tracing 형식으로 저장한다
+        if self.input.is_pressed("profiler_dump") {
+            profiler::log_summary();
+            if let Err(err) = profiler::export_chrome_trace("profile.json") {
+                spdlog::error!("{:?}", err);
+            }
+        }
+    }
+
+    pub fn render(&mut self, time: f32, delta_time: f32) {
+        let _scope = profiler::scope("render");
+        // 카메라 이동
+        // 게임패드 입력이 먼저 채워져 있을 수 있으므로 더한 뒤 -1 ~ 1로 제한한다
+        let movement = self.camera_input.movement + glm::vec3(self.input.get_axis("move_right"), self.input.get_axis("move_up"), self.input.get_axis("move_forward"));
+        self.camera_input.movement = glm::clamp(&movement, -1.0, 1.0);
+        self.camera_input.zoom += self.input.get_axis("zoom");
+        self.camera_controller.update(&mut self.camera, &self.camera_input, delta_time);
+        self.camera_input = camera::Input::new();
         unsafe {
-            gl::Enable(gl::DEPTH_TEST);
-            self.camera_front = (glm::rotate(&glm::Mat4::identity(), self.camera_yaw.to_radians(), &glm::vec3(0.0, 1.0, 0.0)) * glm::rotate(&glm::Mat4::identity(), self.camera_pitch.to_radians(), &glm::vec3(1.0, 0.0, 0.0)) * glm::vec4(0.0, 0.0, -1.0, 0.0)).xyz();
-            self.camera_right = glm::normalize(&glm::cross(&glm::vec3(0.0, 1.0, 0.0), &-self.camera_front));
-            let view = glm::look_at(&self.camera_position, &(&self.camera_position + &self.camera_front), &glm::vec3(0.0, 1.0, 0.0));
-            let projection = glm::perspective(self.width as f32 / self.height as f32, (self.camera_fov as f32).to_radians(), 0.01, 20.0);
-
-            for (index, cube_position) in self.cube_positions.iter().enumerate() {
-                let position = cube_position;
-                let mut model = glm::translate(&glm::Mat4::identity(), position);
-                model = glm::rotate(&model, (time * 90.0).to_radians() + 10.0 * index as f32, &glm::vec3(1.0, 0.3 , 0.5));
-                let transform = projection * view * model;
-                gl::ActiveTexture(gl::TEXTURE0); // 0번 텍스쳐를 활성화
-                self.tbo1.bind(); // 사용할 tbo를 지정
-                gl::ActiveTexture(gl::TEXTURE1); // 1번 텍스쳐를 활성화
-                self.tbo2.bind(); // 사용할 tbo를 지정
-                self.program.use_(); // 사용할 프로그램을 지정
-                self.program.set_uniform_matrix4fv("transform\0", &transform);
-                self.vao.bind(); // 사용할 vao를 지정
-                gl::DrawElements(gl::TRIANGLES, 36, gl::UNSIGNED_INT, std::ptr::null());
+            gl_state::set_enabled(gl::DEPTH_TEST, true);
+            let view = self.camera.get_view_matrix();
+            let projection = self.camera.get_projection_matrix(self.width as f32 / self.height as f32);
+
+            for (index, cube) in self.cubes.iter().enumerate() {
+                let angle = (time * 90.0).to_radians() + 10.0 * index as f32;
+                cube.borrow_mut().set_rotation(glm::quat_angle_axis(angle, &glm::normalize(&glm::vec3(1.0, 0.3, 0.5))));
+            }
+
+            // 장면 그래프를 순회하며 world 행렬이 계산된 그리기 목록을 얻는다
+            let (draw_items, light_items) = self.scene.collect();
+            profiler::begin("shadow");
+            let shadow_indices = self.shadow_map.render(&draw_items, &light_items, &self.camera, self.width as f32 / self.height as f32);
+            profiler::end();
+            // 그림자는 화면 밖의 물체도 드리우므로 그림자를 그린 뒤에 카메라 절두체로 거른다
+            let (draw_items, cull_stats) = frustum::cull(draw_items, &frustum::Frustum::from_camera(&self.camera, self.width as f32 / self.height as f32));
+            self.cull_stats = cull_stats;
+            // 기즈모를 잡고 있지 않을 때만 화면에 보이는 물체 중에서 커서 아래의 물체를 선택한다
+            let mut gizmo_active = false;
+            if let Some(hit) = &self.selection {
+                self.gizmo.snap = self.input.is_active("gizmo_snap");
+                gizmo_active = self.gizmo.update(&hit.node, &self.camera, &self.mouse_position, self.width, self.height, self.input.is_active("select"));
+            }
+            if self.show_bounds {
+                self.draw_bounds(&draw_items, &light_items);
+            }
+            if !gizmo_active && self.input.is_pressed("select") {
+                let ray = picking::Ray::from_screen(&self.camera, &self.mouse_position, self.width, self.height);
+                self.selection = picking::pick(&draw_items, &ray);
+                // 경계 상자를 보여줄 때는 광선도 잠시 남겨 둔다
+                if self.show_bounds {
+                    let end = self.selection.as_ref().map(|hit| hit.position).unwrap_or_else(|| ray.get_point(self.camera.get_far()));
+                    debug_draw::line(&ray.origin, &end, debug_draw::Style::new(glm::vec4(1.0, 0.3, 0.3, 1.0)).set_duration(2.0));
+                }
+                match &self.selection {
+                    Some(hit) => spdlog::info!("Picked \"{}\" at ({:.3}, {:.3}, {:.3})", hit.node.borrow().name, hit.position.x, hit.position.y, hit.position.z),
+                    None => spdlog::info!("Picked nothing"),
+                }
+            }
+            let program = match self.shading_model {
+                ShadingModel::BlinnPhong => &self.program,
+                ShadingModel::Pbr => &self.pbr_program,
+            };
+            program.use_(); // 사용할 프로그램을 지정
+            light::set_uniforms(program, &light_items, &shadow_indices, self.max_light_count);
+            self.shadow_map.bind(program, 8, &view); // Blinn-Phong은 0번, PBR은 0 ~ 7번을 사용한다
+            program.set_uniform3fv("view_position\0", &self.camera.get_position());
+            if self.shading_model == ShadingModel::Pbr {
+                self.environment.bind(program, 5); // 0 ~ 4번은 재질의 텍스쳐
+            }
+            // 상태가 같은 물체끼리 모아서 재질과 vao를 바꾸는 횟수를 줄인다
+            profiler::begin("sort");
+            self.render_queue.clear();
+            for draw_item in &draw_items {
+                let material = draw_item.material.clone().unwrap_or(self.default_material.clone());
+                self.render_queue.push(draw_item.clone(), material, program.get(), &view, self.camera.get_far());
+            }
+            self.render_queue.sort();
+            profiler::end();
+
+            // 선택된 물체를 그린 곳에 스텐실 값 1을 써서 외곽선을 그릴 때 안쪽을 가린다
+            gl_state::set_enabled(gl::STENCIL_TEST, true);
+            gl::StencilOp(gl::KEEP, gl::KEEP, gl::REPLACE);
+            gl::StencilFunc(gl::ALWAYS, 1, 0xFF);
+            let selection = self.selection.as_ref().map(|hit| hit.node.clone());
+            let shading_model = self.shading_model;
+            let mut draw = |item: &render_queue::RenderItem, changes: render_queue::StateChanges| {
+                if changes.program {
+                    program.use_();
+                }
+                if changes.material {
+                    match shading_model {
+                        ShadingModel::BlinnPhong => item.material.set_uniforms(program),
+                        ShadingModel::Pbr => item.material.set_pbr_uniforms(program),
+                    }
+                }
+                if changes.mesh {
+                    item.draw_item.mesh.bind();
+                }
+                let selected = selection.as_ref().is_some_and(|node| Rc::ptr_eq(node, &item.draw_item.node));
+                gl::StencilMask(if selected { 0xFF } else { 0x00 });
+                program.set_uniform_matrix4fv("transform\0", &(projection * view * item.draw_item.world_matrix));
+                program.set_uniform_matrix4fv("model\0", &item.draw_item.world_matrix);
+                program.set_uniform_matrix3fv("normal_matrix\0", &item.draw_item.get_normal_matrix());
+                item.draw_item.mesh.draw_bound();
+            };
+            profiler::begin("opaque");
+            self.render_queue.submit(render_queue::Pass::Opaque, &mut draw);
+            profiler::end();
+            gl::StencilMask(0x00);
+            if self.shading_model == ShadingModel::Pbr {
+                self.environment.draw_skybox(&view, &projection);
+            }
+            // 반투명한 물체는 뒤의 물체를 가리지 않도록 깊이를 쓰지 않는다
+            profiler::begin("transparent");
+            gl_state::set_depth_mask(false);
+            self.render_queue.submit(render_queue::Pass::Transparent, &mut draw);
+            gl_state::set_depth_mask(true);
+            profiler::end();
+            gl::StencilMask(0x00);
+            self.draw_outline(&draw_items, &(projection * view));
+            if let Some(hit) = &self.selection {
+                self.gizmo.draw(&hit.node, &self.camera, self.width, self.height);
+            }
+            gl::StencilMask(0xFF); // 다음 프레임에 스텐실 버퍼를 지울 수 있도록 되돌린다
+            gl_state::set_enabled(gl::STENCIL_TEST, false);
+            if self.show_shadow_map {
+                self.shadow_map.draw_debug(self.width, self.height);
+            }
+            if self.show_profiler {
+                profiler::draw_overlay(&glm::vec2(10.0, 10.0), self.width as f32 * 0.5);
+            }
+        }
+
+        // 요청된 스크린샷은 장면을 모두 그린 뒤에 저장한다
+        if let Some(file_path) = self.screenshot_path.take() {
+            match self.save_screenshot(&file_path) {
+                Ok(()) => spdlog::info!("Saved screenshot \"{}\"", file_path),
+                Err(err) => spdlog::error!("{:?}", err),
             }
         }
     }
 
-    pub fn on_key_event(&mut self, key: glfw::Key, down: bool) {
-        match key {
-            glfw::Key::W => self.key_down[0] = down,
-            glfw::Key::A => self.key_down[1] = down,
-            glfw::Key::S => self.key_down[2] = down,
-            glfw::Key::D => self.key_down[3] = down,
-            glfw::Key::Space => self.key_down[4] = down,
-            glfw::Key::LeftShift => self.key_down[5] = down,
-            _ => {},
+    // 프레임의 마지막에 호출한다, 이번 프레임에만 유효한 입력을 지운다
+    pub fn end_frame(&mut self) {
+        self.input.end_frame();
+    }
+
+    // 그리는 물체의 경계 상자, 선택된 물체의 좌표축, 광원이 닿는 범위를 보여준다
+    fn draw_bounds(&self, draw_items: &[scene::DrawItem], light_items: &[scene::LightItem]) {
+        let style = debug_draw::Style::new(glm::vec4(0.2, 1.0, 0.4, 1.0));
+        for draw_item in draw_items {
+            debug_draw::aabb(&draw_item.get_bounding_box(), &style);
+            // 선택된 물체는 로컬 좌표축도 보여준다
+            if self.is_selected(draw_item) {
+                debug_draw::axes(&draw_item.world_matrix, 0.5, &style);
+            }
+        }
+        let style = *debug_draw::Style::new(glm::vec4(1.0, 0.9, 0.3, 1.0)).set_depth_test(false);
+        for light_item in light_items.iter().filter(|light_item| light_item.light.enabled) {
+            let position = light_item.get_position();
+            match light_item.light.type_ {
+                light::LightType::Directional => debug_draw::arrow(&position, &(position + light_item.get_direction()), 0.2, &style),
+                light::LightType::Point => debug_draw::sphere(&position, light_item.light.get_range().min(100.0), &style),
+                light::LightType::Spot => {
+                    let range = light_item.light.get_range().min(100.0);
+                    let radius = range * light_item.light.outer_angle.to_radians().tan();
+                    debug_draw::arrow(&position, &(position + light_item.get_direction() * range), 0.2, &style);
+                    debug_draw::circle(&(position + light_item.get_direction() * range), &light_item.get_direction(), radius, &style);
+                }
+            }
         }
+        debug_draw::grid(&glm::vec3(0.0, -1.0, 0.0), 20.0, 20, &debug_draw::Style::new(glm::vec4(0.5, 0.5, 0.5, 0.5)));
     }
 
-    pub fn on_frame_buffer_size_event(&mut self, width: i32, height: i32) {
-        self.width = width as u32;
-        self.height = height as u32;
+    // 프레임 동안 debug_draw로 쌓인 선을 그린다, 장면을 모두 그린 뒤에 호출한다
+    pub fn flush_debug_draw(&self, delta_time: f32) {
+        let view_projection = self.camera.get_projection_matrix(self.width as f32 / self.height as f32) * self.camera.get_view_matrix();
+        self.debug_renderer.flush(&view_projection, self.width, self.height, delta_time);
     }
 
-    pub fn on_cursor_pos_event(&mut self, x: f32, y: f32) {
-        self.mouse_position = glm::vec2(x, y);
-        if self.camera_control == false {
+    fn is_selected(&self, draw_item: &scene::DrawItem) -> bool {
+        self.selection.as_ref().is_some_and(|hit| Rc::ptr_eq(&hit.node, &draw_item.node))
+    }
+
+    // normal 방향으로 부풀린 메시를 스텐실 값이 1이 아닌 곳에만 그려서 외곽선을 만든다, 다른 물체에 가려져도 보이도록 깊이 검사를 끈다
+    fn draw_outline(&self, draw_items: &[scene::DrawItem], view_projection: &glm::Mat4) {
+        let Some(outline_program) = self.outline_program.get() else {
             return;
+        };
+        outline_program.use_();
+        outline_program.set_uniform1f("outline_width\0", 0.03);
+        outline_program.set_uniform4fv("outline_color\0", &glm::vec4(1.0, 0.6, 0.1, 1.0));
+        unsafe {
+            gl::StencilFunc(gl::NOTEQUAL, 1, 0xFF);
         }
-
-        let delta_position = self.mouse_position - self.previous_mouse_position;
-        
-        let sensitivity = 0.15f32;
-        self.camera_yaw -= delta_position.x * sensitivity;
-        self.camera_pitch -= delta_position.y * sensitivity;
-        if self.camera_yaw < 0.0 {
-            self.camera_yaw += 360.0;
+        gl_state::set_enabled(gl::DEPTH_TEST, false);
+        for draw_item in draw_items.iter().filter(|draw_item| self.is_selected(draw_item)) {
+            outline_program.set_uniform_matrix4fv("transform\0", &(view_projection * draw_item.world_matrix));
+            draw_item.mesh.draw();
         }
-        if self.camera_yaw > 360.0 {
-            self.camera_yaw -= 360.0;
+        gl_state::set_enabled(gl::DEPTH_TEST, true);
+    }
+
+    // 현재 프레임 버퍼의 내용을 png 파일로 저장한다
+    pub fn save_screenshot(&self, file_path: &str) -> Result<(), errors::Error> {
+        let mut pixels = vec![0u8; (self.width * self.height * 4) as usize];
+        unsafe {
+            gl::PixelStorei(gl::PACK_ALIGNMENT, 1);
+            gl::ReadPixels(0, 0, self.width as i32, self.height as i32, gl::RGBA, gl::UNSIGNED_BYTE, pixels.as_mut_ptr().cast());
         }
-        if self.camera_pitch < -89.0 {
-            self.camera_pitch = -89.0;
+        gl_check::get_error("Context::save_screenshot")?; // 읽기에 실패하면 빈 이미지를 저장하지 않는다
+        // OpenGL은 아래쪽 줄부터 읽으므로 상하를 뒤집는다
+        let screenshot = ::image::RgbaImage::from_raw(self.width, self.height, pixels).map(|screenshot| ::image::imageops::flip_vertical(&screenshot));
+        if let Some(screenshot) = screenshot {
+            screenshot.save(file_path)?;
         }
-        if self.camera_pitch > 89.0 {
-            self.camera_pitch = 89.0;
+        Ok(())
+    }
+
+    pub fn on_key_event(&mut self, key: glfw::Key, action: glfw::Action) {
+        self.input.on_key_event(key, action);
+    }
+
+    // 카메라 조작 방식을 자유 비행 -> 1인칭 -> 궤도 순서로 바꾼다
+    fn cycle_camera_mode(&mut self) {
+        self.camera_controller.mode = match self.camera_controller.mode {
+            camera::ControllerMode::FreeFly => camera::ControllerMode::FirstPerson,
+            camera::ControllerMode::FirstPerson => {
+                // 선택된 물체가 있으면 선택한 지점을 중심으로 돈다
+                let target = match &self.selection {
+                    Some(hit) => hit.position,
+                    None => self.camera.get_position() + self.camera.get_front() * FOCUS_DISTANCE,
+                };
+                self.camera.look_at(&target);
+                camera::ControllerMode::Orbit { target, distance: glm::distance(&self.camera.get_position(), &target).max(0.01) }
+            }
+            camera::ControllerMode::Orbit { .. } => camera::ControllerMode::FreeFly,
+        };
+        spdlog::info!("Camera mode: {:?}", self.camera_controller.mode);
+    }
+
+    // 원근 투영과 직교 투영을 바꾼다, 초점 거리에서 보이는 세로 길이가 같도록 맞춘다
+    fn toggle_camera_projection(&mut self) {
+        let distance = match self.camera_controller.mode {
+            camera::ControllerMode::Orbit { distance, .. } => distance,
+            _ => FOCUS_DISTANCE,
+        };
+        match self.camera.get_projection() {
+            camera::Projection::Perspective { fov } => self.camera.set_orthographic(2.0 * distance * (fov.to_radians() / 2.0).tan()),
+            camera::Projection::Orthographic { height } => self.camera.set_perspective((2.0 * (height / (2.0 * distance)).atan()).to_degrees().clamp(1.0, 120.0)),
+        };
+    }
+
+    pub fn on_frame_buffer_size_event(&mut self, width: i32, height: i32) {
+        self.width = width as u32;
+        self.height = height as u32;
+    }
+
+    pub fn on_cursor_pos_event(&mut self, x: f32, y: f32) {
+        self.mouse_position = glm::vec2(x, y);
+        if self.input.is_active("look") {
+            // 다음 프레임에 카메라 컨트롤러가 한꺼번에 처리한다
+            self.camera_input.rotation += self.mouse_position - self.previous_mouse_position;
         }
 
         self.previous_mouse_position = self.mouse_position;
     }
 
-    pub fn on_mouse_down_event(&mut self, mouse_press: bool) {
-        self.camera_control = mouse_press;
-        self.previous_mouse_position = self.mouse_position;
+    // 왼쪽 스틱은 이동, 오른쪽 스틱은 시점, 트리거는 위아래 이동
+    pub fn on_gamepad_update(&mut self, gamepad: &gamepad::Gamepad, stick_deadzone: f32, trigger_deadzone: f32) {
+        let look_speed = 120.0; // 초당 회전 각도(도)
+        let left_stick = gamepad.get_left_stick(stick_deadzone);
+        let right_stick = gamepad.get_right_stick(stick_deadzone);
+        let up = gamepad.get_trigger(glfw::GamepadAxis::AxisRightTrigger, trigger_deadzone) - gamepad.get_trigger(glfw::GamepadAxis::AxisLeftTrigger, trigger_deadzone);
+        // 스틱의 y축은 위로 밀수록 작아진다
+        self.camera_input.movement = glm::vec3(left_stick.x, up, -left_stick.y);
+        self.camera_input.turn = right_stick * look_speed;
+    }
+
+    pub fn on_scroll_event(&mut self, x: f32, y: f32) {
+        self.input.on_scroll_event(x, y);
+    }
+
+    pub fn on_mouse_button_event(&mut self, mouse_button: glfw::MouseButton, action: glfw::Action) {
+        self.input.on_mouse_button_event(mouse_button, action);
     }
 }
\ No newline at end of file
@@ -0,0 +1,67 @@
+use super::{gl_check, gl_state};
+
+pub struct CubeTexture {
+    texture: u32,
+    size: u32,
+}
+
+impl CubeTexture {
+    // 6개의 면이 모두 size x size인 부동 소수점 큐브맵, mipmap이 있으면 거칠기별 반사광을 mip level에 나눠 담을 수 있다
+    #[cfg_attr(any(debug_assertions, feature = "gl_check"), track_caller)]
+    pub fn create(size: u32, mipmap: bool) -> CubeTexture {
+        let mut texture = 0;
+
+        unsafe {
+            gl::GenTextures(1, &mut texture);
+            spdlog::info!("Created cube texture({})", texture);
+            gl_state::bind_texture(gl::TEXTURE_CUBE_MAP, texture);
+            for face in 0..6 {
+                gl::TexImage2D(gl::TEXTURE_CUBE_MAP_POSITIVE_X + face, 0, gl::RGB16F.cast_signed(), size.cast_signed(), size.cast_signed(), 0, gl::RGB, gl::FLOAT, std::ptr::null());
+            }
+            gl::TexParameteri(gl::TEXTURE_CUBE_MAP, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE.cast_signed());
+            gl::TexParameteri(gl::TEXTURE_CUBE_MAP, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE.cast_signed());
+            gl::TexParameteri(gl::TEXTURE_CUBE_MAP, gl::TEXTURE_WRAP_R, gl::CLAMP_TO_EDGE.cast_signed());
+            gl::TexParameteri(gl::TEXTURE_CUBE_MAP, gl::TEXTURE_MIN_FILTER, if mipmap { gl::LINEAR_MIPMAP_LINEAR } else { gl::LINEAR }.cast_signed());
+            gl::TexParameteri(gl::TEXTURE_CUBE_MAP, gl::TEXTURE_MAG_FILTER, gl::LINEAR.cast_signed());
+            if mipmap {
+                gl::GenerateMipmap(gl::TEXTURE_CUBE_MAP);
+            }
+        }
+        gl_check::check("CubeTexture::create");
+
+        CubeTexture { texture, size }
+    }
+
+    pub fn get(&self) -> u32 {
+        self.texture
+    }
+
+    pub fn get_size(&self) -> u32 {
+        self.size
+    }
+
+    pub fn bind(&self) -> &Self {
+        gl_state::bind_texture(gl::TEXTURE_CUBE_MAP, self.texture);
+        self
+    }
+
+    #[cfg_attr(any(debug_assertions, feature = "gl_check"), track_caller)]
+    pub fn generate_mipmap(&self) -> &Self {
+        self.bind();
+        unsafe {
+            gl::GenerateMipmap(gl::TEXTURE_CUBE_MAP);
+        }
+        gl_check::check("CubeTexture::generate_mipmap");
+        self
+    }
+}
+
+impl Drop for CubeTexture {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteTextures(1, &self.texture);
+        }
+        gl_state::on_texture_deleted(self.texture);
+        spdlog::info!("Dropped cube texture({})", self.texture);
+    }
+}
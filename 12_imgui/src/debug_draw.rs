@@ -0,0 +1,230 @@
+use super::{errors, resource, gl_state, buffer::Buffer, mesh::BoundingBox, program::Program, vertex_array::VertexArray};
+use nalgebra_glm as glm;
+use std::{cell::RefCell, rc::Rc};
+
+const CIRCLE_SEGMENT_COUNT: usize = 32;
+const FLOATS_PER_VERTEX: usize = 7; // 위치 3개, 색 4개
+
+// 호출마다 지정하는 그리기 옵션
+#[derive(Clone, Copy)]
+pub struct Style {
+    pub color: glm::Vec4,
+    pub depth_test: bool, // 끄면 다른 물체에 가려져도 보인다
+    pub duration: f32, // 화면에 남아 있는 시간(초), 0이면 한 프레임만 그린다
+}
+
+impl Style {
+    pub fn new(color: glm::Vec4) -> Self {
+        Self { color, depth_test: true, duration: 0.0 }
+    }
+
+    pub fn set_depth_test(&mut self, depth_test: bool) -> &mut Self {
+        self.depth_test = depth_test;
+        self
+    }
+
+    pub fn set_duration(&mut self, duration: f32) -> &mut Self {
+        self.duration = duration;
+        self
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Space {
+    World,
+    Screen, // 왼쪽 위가 원점인 픽셀 좌표, z는 사용하지 않는다
+}
+
+struct Line {
+    start: glm::Vec3,
+    end: glm::Vec3,
+    style: Style,
+    space: Space,
+}
+
+thread_local! {
+    // 프레임 중 어디서든 쌓을 수 있도록 GL 객체 없이 선분만 모아 둔다, 그리기는 Renderer가 맡는다
+    static LINES: RefCell<Vec<Line>> = const { RefCell::new(Vec::new()) };
+}
+
+fn push(start: glm::Vec3, end: glm::Vec3, style: &Style, space: Space) {
+    LINES.with_borrow_mut(|lines| lines.push(Line { start, end, style: *style, space }));
+}
+
+pub fn line(start: &glm::Vec3, end: &glm::Vec3, style: &Style) {
+    push(*start, *end, style, Space::World);
+}
+
+// 끝에 네 갈래의 화살촉이 달린 선분
+pub fn arrow(start: &glm::Vec3, end: &glm::Vec3, head_size: f32, style: &Style) {
+    line(start, end, style);
+    let direction = end - start;
+    if glm::length(&direction) < f32::EPSILON {
+        return;
+    }
+    let direction = glm::normalize(&direction);
+    let (side1, side2) = get_perpendicular(&direction);
+    for side in [side1, -side1, side2, -side2] {
+        line(end, &(end - direction * head_size + side * head_size * 0.4), style);
+    }
+}
+
+pub fn aabb(bounding_box: &BoundingBox, style: &Style) {
+    let corner = |index: usize| glm::vec3(
+        if index & 1 == 0 { bounding_box.min.x } else { bounding_box.max.x },
+        if index & 2 == 0 { bounding_box.min.y } else { bounding_box.max.y },
+        if index & 4 == 0 { bounding_box.min.z } else { bounding_box.max.z },
+    );
+    box_edges(&corner, style);
+}
+
+// 세 축에 수직인 원 세 개로 구를 나타낸다
+pub fn sphere(center: &glm::Vec3, radius: f32, style: &Style) {
+    for axis in [glm::vec3(1.0, 0.0, 0.0), glm::vec3(0.0, 1.0, 0.0), glm::vec3(0.0, 0.0, 1.0)] {
+        circle(center, &axis, radius, style);
+    }
+}
+
+pub fn circle(center: &glm::Vec3, normal: &glm::Vec3, radius: f32, style: &Style) {
+    let (side1, side2) = get_perpendicular(&glm::normalize(normal));
+    let point = |index: usize| {
+        let angle = index as f32 / CIRCLE_SEGMENT_COUNT as f32 * std::f32::consts::TAU;
+        center + (side1 * angle.cos() + side2 * angle.sin()) * radius
+    };
+    for index in 0..CIRCLE_SEGMENT_COUNT {
+        line(&point(index), &point(index + 1), style);
+    }
+}
+
+// projection * view 행렬의 역행렬로 NDC 상자의 꼭짓점을 world 공간으로 옮긴다
+pub fn frustum(view_projection: &glm::Mat4, style: &Style) {
+    let inverse = glm::inverse(view_projection);
+    let corner = |index: usize| {
+        let ndc = glm::vec4(if index & 1 == 0 { -1.0 } else { 1.0 }, if index & 2 == 0 { -1.0 } else { 1.0 }, if index & 4 == 0 { -1.0 } else { 1.0 }, 1.0);
+        let position = inverse * ndc;
+        position.xyz() / position.w
+    };
+    box_edges(&corner, style);
+}
+
+// center를 중심으로 xz 평면 위에 size 크기의 격자를 그린다
+pub fn grid(center: &glm::Vec3, size: f32, divisions: u32, style: &Style) {
+    let divisions = divisions.max(1);
+    let half = size / 2.0;
+    for index in 0..=divisions {
+        let offset = -half + size * index as f32 / divisions as f32;
+        line(&(center + glm::vec3(offset, 0.0, -half)), &(center + glm::vec3(offset, 0.0, half)), style);
+        line(&(center + glm::vec3(-half, 0.0, offset)), &(center + glm::vec3(half, 0.0, offset)), style);
+    }
+}
+
+// 행렬의 x, y, z 축을 빨강, 초록, 파랑 화살표로 그린다, style의 색은 알파 값만 사용한다
+pub fn axes(matrix: &glm::Mat4, size: f32, style: &Style) {
+    let origin = matrix.column(3).xyz();
+    let colors = [glm::vec3(1.0, 0.0, 0.0), glm::vec3(0.0, 1.0, 0.0), glm::vec3(0.0, 0.0, 1.0)];
+    for (axis, color) in colors.iter().enumerate() {
+        let style = Style { color: glm::vec4(color.x, color.y, color.z, style.color.w), ..*style };
+        let direction = glm::normalize(&matrix.column(axis).xyz());
+        arrow(&origin, &(origin + direction * size), size * 0.15, &style);
+    }
+}
+
+pub fn screen_line(start: &glm::Vec2, end: &glm::Vec2, style: &Style) {
+    push(glm::vec3(start.x, start.y, 0.0), glm::vec3(end.x, end.y, 0.0), style, Space::Screen);
+}
+
+pub fn screen_rect(min: &glm::Vec2, max: &glm::Vec2, style: &Style) {
+    let corners = [glm::vec2(min.x, min.y), glm::vec2(max.x, min.y), glm::vec2(max.x, max.y), glm::vec2(min.x, max.y)];
+    for index in 0..4 {
+        screen_line(&corners[index], &corners[(index + 1) % 4], style);
+    }
+}
+
+// 꼭짓점 번호의 비트가 x, y, z 방향을 나타내는 상자의 모서리 12개
+fn box_edges(corner: &dyn Fn(usize) -> glm::Vec3, style: &Style) {
+    for index in 0..8 {
+        for axis in 0..3 {
+            if index & (1 << axis) == 0 {
+                line(&corner(index), &corner(index | (1 << axis)), style);
+            }
+        }
+    }
+}
+
+fn get_perpendicular(direction: &glm::Vec3) -> (glm::Vec3, glm::Vec3) {
+    let up = if direction.y.abs() < 0.999 { glm::vec3(0.0, 1.0, 0.0) } else { glm::vec3(1.0, 0.0, 0.0) };
+    let side1 = glm::normalize(&glm::cross(direction, &up));
+    let side2 = glm::cross(direction, &side1);
+    (side1, side2)
+}
+
+// 쌓인 선분을 하나의 스트리밍 버퍼에 담아서 한꺼번에 그린다
+pub struct Renderer {
+    program: Rc<Program>,
+    vertex_array: VertexArray,
+    vertex_buffer: Buffer,
+}
+
+impl Renderer {
+    pub fn create() -> Result<Renderer, errors::Error> {
+        let program = resource::load_program("shader/debug_draw.vert", "shader/debug_draw.frag", &[])?;
+        let vertex_array = VertexArray::create();
+        vertex_array.bind();
+        let vertex_buffer = Buffer::create(gl::ARRAY_BUFFER, 0, std::ptr::null(), gl::STREAM_DRAW);
+        let stride = (size_of::<f32>() * FLOATS_PER_VERTEX) as i32;
+        vertex_array.set(0, 3, gl::FLOAT, gl::FALSE, stride, std::ptr::null());
+        vertex_array.set(1, 4, gl::FLOAT, gl::FALSE, stride, (size_of::<f32>() * 3) as *const _);
+        vertex_array.set_label("debug_draw vao");
+        vertex_buffer.set_label("debug_draw vbo");
+
+        Ok(Renderer { program, vertex_array, vertex_buffer })
+    }
+
+    // 깊이 검사를 하는 선, 하지 않는 선, 화면 공간의 선 순서로 그린 뒤에 시간이 다 된 선을 지운다
+    pub fn flush(&self, view_projection: &glm::Mat4, width: u32, height: u32, delta_time: f32) {
+        LINES.with_borrow_mut(|lines| {
+            if lines.is_empty() {
+                return;
+            }
+            let groups = [(Space::World, true), (Space::World, false), (Space::Screen, false)];
+            let mut vertices: Vec<f32> = Vec::with_capacity(lines.len() * 2 * FLOATS_PER_VERTEX);
+            let mut ranges = Vec::new();
+            for (space, depth_test) in groups {
+                let first = vertices.len() / FLOATS_PER_VERTEX;
+                for line in lines.iter().filter(|line| line.space == space && (space == Space::Screen || line.style.depth_test == depth_test)) {
+                    for point in [line.start, line.end] {
+                        // 화면 좌표는 CPU에서 NDC로 바꿔서 같은 프로그램으로 그린다
+                        let position = match space {
+                            Space::World => point,
+                            Space::Screen => glm::vec3(2.0 * point.x / width as f32 - 1.0, 1.0 - 2.0 * point.y / height as f32, 0.0),
+                        };
+                        vertices.extend_from_slice(position.as_slice());
+                        vertices.extend_from_slice(line.style.color.as_slice());
+                    }
+                }
+                ranges.push((space, depth_test, first as i32, (vertices.len() / FLOATS_PER_VERTEX - first) as i32));
+            }
+
+            self.vertex_buffer.set(gl::ARRAY_BUFFER, size_of_val(vertices.as_slice()).cast_signed(), vertices.as_ptr().cast(), gl::STREAM_DRAW);
+            self.program.use_();
+            self.vertex_array.bind();
+            for (space, depth_test, first, count) in ranges {
+                if count == 0 {
+                    continue;
+                }
+                let transform = if space == Space::World { *view_projection } else { glm::Mat4::identity() };
+                self.program.set_uniform_matrix4fv("transform\0", &transform);
+                gl_state::set_enabled(gl::DEPTH_TEST, depth_test);
+                unsafe {
+                    gl::DrawArrays(gl::LINES, first, count);
+                }
+            }
+            gl_state::set_enabled(gl::DEPTH_TEST, true);
+
+            for line in lines.iter_mut() {
+                line.style.duration -= delta_time;
+            }
+            lines.retain(|line| 0.0 < line.style.duration);
+        });
+    }
+}
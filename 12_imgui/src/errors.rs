@@ -1,10 +1,19 @@
+use super::gl_check;
+
 pub enum Error {
-    InitError(glfw::InitError),
-    CreateWindowError,
+    FrameworkError(framework::Error),
     ReadFileError(std::io::Error),
     CompileShaderError(String),
     LinkProgramError(String),
     ImageError(image::ImageError),
+    ParseModelError(String, usize, String),
+    GltfError(gltf::Error),
+    UnsupportedExtensionError(String, Vec<String>),
+    ParseConfigError(String, usize, String),
+    FramebufferError(u32),
+    GlError(String, String, Vec<u32>), // 래퍼 함수, 호출 위치, 오류 코드
+    AssetNotFoundError(String, Vec<String>), // 경로, 찾아본 위치
+    LoaderError(String),
 }
 
 impl std::error::Error for Error {}
@@ -12,11 +21,8 @@ impl std::error::Error for Error {}
 impl std::fmt::Debug for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Error::InitError(description) => {
-                write!(f, "Failed to initialize glfw\n{}", description)
-            }
-            Error::CreateWindowError => {
-                write!(f, "Failed to create GLFW window")
+            Error::FrameworkError(description) => {
+                write!(f, "{:?}", description)
             }
             Error::ReadFileError(description) => {
                 write!(f, "Failed to read file\n{}", description)
@@ -30,6 +36,33 @@ impl std::fmt::Debug for Error {
             Error::ImageError(description) => {
                 write!(f, "Failed to open image\n{}", description)
             }
+            Error::ParseModelError(file_path, 0, description) => {
+                write!(f, "Failed to parse model\n{}: {}", file_path, description) // 줄 번호가 없는 바이너리 형식(glTF 등)
+            }
+            Error::ParseModelError(file_path, line, description) => {
+                write!(f, "Failed to parse model\n{}:{}: {}", file_path, line, description)
+            }
+            Error::GltfError(description) => {
+                write!(f, "Failed to load glTF\n{}", description)
+            }
+            Error::UnsupportedExtensionError(file_path, extensions) => {
+                write!(f, "Failed to load glTF\n{}: unsupported required extensions: {}", file_path, extensions.join(", "))
+            }
+            Error::ParseConfigError(file_path, line, description) => {
+                write!(f, "Failed to parse config\n{}:{}: {}", file_path, line, description)
+            }
+            Error::FramebufferError(status) => {
+                write!(f, "Failed to complete framebuffer\nstatus: 0x{:X}", status)
+            }
+            Error::GlError(operation, location, codes) => {
+                write!(f, "Failed to execute OpenGL call\n{}: {}: {}", location, operation, gl_check::format_codes(codes))
+            }
+            Error::AssetNotFoundError(path, locations) => {
+                write!(f, "Failed to find asset \"{}\"\ntried:\n  {}", path, locations.join("\n  "))
+            }
+            Error::LoaderError(description) => {
+                write!(f, "Failed to finish loading\n{}", description)
+            }
         }
     }
 }
@@ -40,9 +73,9 @@ impl std::fmt::Display for Error {
     }
 }
 
-impl From<glfw::InitError> for Error {
-    fn from(description: glfw::InitError) -> Self {
-        Error::InitError(description)
+impl From<framework::Error> for Error {
+    fn from(description: framework::Error) -> Self {
+        Error::FrameworkError(description)
     }
 }
 
@@ -58,6 +91,12 @@ impl From<image::ImageError> for Error {
     }
 }
 
+impl From<gltf::Error> for Error {
+    fn from(description: gltf::Error) -> Self {
+        Error::GltfError(description)
+    }
+}
+
 pub struct Result<T, E>(std::result::Result<T, E>);
 
 impl<T, E> From<std::result::Result<T, E>> for Result<T, E> {
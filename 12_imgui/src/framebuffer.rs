@@ -0,0 +1,93 @@
+use super::{errors, gl_check};
+
+pub struct Framebuffer {
+    framebuffer: u32,
+    depth_renderbuffer: u32,
+}
+
+impl Framebuffer {
+    pub fn create() -> Framebuffer {
+        let mut framebuffer = 0;
+
+        unsafe {
+            gl::GenFramebuffers(1, &mut framebuffer);
+        }
+        spdlog::info!("Created framebuffer({})", framebuffer);
+
+        Framebuffer { framebuffer, depth_renderbuffer: 0 }
+    }
+
+    pub fn bind(&self) -> &Self {
+        unsafe {
+            gl::BindFramebuffer(gl::FRAMEBUFFER, self.framebuffer);
+        }
+        self
+    }
+
+    // 기본 프레임 버퍼(화면)로 되돌린다
+    pub fn unbind() {
+        unsafe {
+            gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+        }
+    }
+
+    // target은 gl::TEXTURE_2D 또는 gl::TEXTURE_CUBE_MAP_POSITIVE_X + 면 번호
+    #[cfg_attr(any(debug_assertions, feature = "gl_check"), track_caller)]
+    pub fn attach_texture(&self, attachment: u32, target: u32, texture: u32, level: i32) -> &Self {
+        self.bind();
+        unsafe {
+            gl::FramebufferTexture2D(gl::FRAMEBUFFER, attachment, target, texture, level);
+        }
+        gl_check::check("Framebuffer::attach_texture");
+        self
+    }
+
+    // 깊이 값을 읽을 필요가 없을 때 사용하는 깊이 버퍼, 크기가 바뀌면 다시 할당한다
+    #[cfg_attr(any(debug_assertions, feature = "gl_check"), track_caller)]
+    pub fn set_depth_renderbuffer(&mut self, width: u32, height: u32) -> &Self {
+        self.bind();
+        unsafe {
+            if self.depth_renderbuffer == 0 {
+                gl::GenRenderbuffers(1, &mut self.depth_renderbuffer);
+            }
+            gl::BindRenderbuffer(gl::RENDERBUFFER, self.depth_renderbuffer);
+            gl::RenderbufferStorage(gl::RENDERBUFFER, gl::DEPTH_COMPONENT24, width.cast_signed(), height.cast_signed());
+            gl::FramebufferRenderbuffer(gl::FRAMEBUFFER, gl::DEPTH_ATTACHMENT, gl::RENDERBUFFER, self.depth_renderbuffer);
+        }
+        gl_check::check("Framebuffer::set_depth_renderbuffer");
+        self
+    }
+
+    // 색상 attachment가 없는 프레임 버퍼(그림자 맵 등)는 그리기/읽기 버퍼를 꺼야 완전해진다
+    #[cfg_attr(any(debug_assertions, feature = "gl_check"), track_caller)]
+    pub fn set_no_color_buffer(&self) -> &Self {
+        self.bind();
+        unsafe {
+            gl::DrawBuffer(gl::NONE);
+            gl::ReadBuffer(gl::NONE);
+        }
+        gl_check::check("Framebuffer::set_no_color_buffer");
+        self
+    }
+
+    pub fn check(&self) -> Result<(), errors::Error> {
+        self.bind();
+        let status = unsafe { gl::CheckFramebufferStatus(gl::FRAMEBUFFER) };
+        if status != gl::FRAMEBUFFER_COMPLETE {
+            return Err(errors::Error::FramebufferError(status));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Framebuffer {
+    fn drop(&mut self) {
+        unsafe {
+            if self.depth_renderbuffer != 0 {
+                gl::DeleteRenderbuffers(1, &self.depth_renderbuffer);
+            }
+            gl::DeleteFramebuffers(1, &self.framebuffer);
+        }
+        spdlog::info!("Dropped framebuffer({})", self.framebuffer);
+    }
+}
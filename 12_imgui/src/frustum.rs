@@ -0,0 +1,88 @@
+use super::{camera::Camera, mesh::{BoundingBox, BoundingSphere}, scene::DrawItem};
+use nalgebra_glm as glm;
+
+// ax + by + cz + d = 0, (a, b, c)는 절두체 안쪽을 향하는 단위 벡터
+#[derive(Clone, Copy)]
+pub struct Plane {
+    pub normal: glm::Vec3,
+    pub distance: f32,
+}
+
+impl Plane {
+    fn from_vec4(plane: glm::Vec4) -> Self {
+        let length = glm::length(&plane.xyz());
+        Self { normal: plane.xyz() / length, distance: plane.w / length }
+    }
+
+    // 양수이면 평면의 안쪽
+    pub fn get_signed_distance(&self, point: &glm::Vec3) -> f32 {
+        glm::dot(&self.normal, point) + self.distance
+    }
+}
+
+// world 공간의 절두체, 순서는 left, right, bottom, top, near, far
+pub struct Frustum {
+    pub planes: [Plane; 6],
+}
+
+impl Frustum {
+    // projection * view 행렬의 행을 더하고 빼서 여섯 평면을 얻는다(Gribb-Hartmann)
+    pub fn from_matrix(view_projection: &glm::Mat4) -> Self {
+        let row = |index: usize| view_projection.row(index).transpose();
+        let planes = [
+            Plane::from_vec4(row(3) + row(0)),
+            Plane::from_vec4(row(3) - row(0)),
+            Plane::from_vec4(row(3) + row(1)),
+            Plane::from_vec4(row(3) - row(1)),
+            Plane::from_vec4(row(3) + row(2)),
+            Plane::from_vec4(row(3) - row(2)),
+        ];
+        Self { planes }
+    }
+
+    pub fn from_camera(camera: &Camera, aspect: f32) -> Self {
+        Self::from_matrix(&(camera.get_projection_matrix(aspect) * camera.get_view_matrix()))
+    }
+
+    pub fn intersects_sphere(&self, sphere: &BoundingSphere) -> bool {
+        self.planes.iter().all(|plane| plane.get_signed_distance(&sphere.center) >= -sphere.radius)
+    }
+
+    // 평면의 normal 방향으로 가장 먼 꼭짓점까지 바깥에 있으면 상자 전체가 바깥에 있다
+    pub fn intersects_box(&self, bounding_box: &BoundingBox) -> bool {
+        self.planes.iter().all(|plane| {
+            let farthest = glm::vec3(
+                if plane.normal.x >= 0.0 { bounding_box.max.x } else { bounding_box.min.x },
+                if plane.normal.y >= 0.0 { bounding_box.max.y } else { bounding_box.min.y },
+                if plane.normal.z >= 0.0 { bounding_box.max.z } else { bounding_box.min.z },
+            );
+            plane.get_signed_distance(&farthest) >= 0.0
+        })
+    }
+
+    // 빠른 구 검사로 먼저 거르고, 통과한 것만 더 정확한 상자 검사를 한다
+    pub fn is_visible(&self, draw_item: &DrawItem) -> bool {
+        self.intersects_sphere(&draw_item.get_bounding_sphere()) && self.intersects_box(&draw_item.get_bounding_box())
+    }
+}
+
+// 마지막으로 컬링한 프레임의 통계
+#[derive(Clone, Copy)]
+pub struct CullStats {
+    pub drawn: usize,
+    pub culled: usize,
+}
+
+impl CullStats {
+    pub fn new() -> Self {
+        Self { drawn: 0, culled: 0 }
+    }
+}
+
+// 절두체 밖의 그리기 항목을 빼고 통계를 돌려준다
+pub fn cull(draw_items: Vec<DrawItem>, frustum: &Frustum) -> (Vec<DrawItem>, CullStats) {
+    let total = draw_items.len();
+    let visible: Vec<DrawItem> = draw_items.into_iter().filter(|draw_item| frustum.is_visible(draw_item)).collect();
+    let stats = CullStats { drawn: visible.len(), culled: total - visible.len() };
+    (visible, stats)
+}
@@ -0,0 +1,104 @@
+use super::asset;
+use nalgebra_glm as glm;
+
+const JOYSTICK_IDS: [glfw::JoystickId; 16] = [
+    glfw::JoystickId::Joystick1, glfw::JoystickId::Joystick2, glfw::JoystickId::Joystick3, glfw::JoystickId::Joystick4,
+    glfw::JoystickId::Joystick5, glfw::JoystickId::Joystick6, glfw::JoystickId::Joystick7, glfw::JoystickId::Joystick8,
+    glfw::JoystickId::Joystick9, glfw::JoystickId::Joystick10, glfw::JoystickId::Joystick11, glfw::JoystickId::Joystick12,
+    glfw::JoystickId::Joystick13, glfw::JoystickId::Joystick14, glfw::JoystickId::Joystick15, glfw::JoystickId::Joystick16,
+];
+
+// SDL_GameControllerDB 형식의 추가 매핑, 없으면 glfw에 내장된 매핑만 사용한다
+const MAPPINGS_FILE_PATH: &str = "resources/config/gamecontrollerdb.txt";
+
+// 표준 배치(Xbox 컨트롤러 기준)로 매핑된 게임패드 하나의 상태
+pub struct Gamepad {
+    id: glfw::JoystickId,
+    name: String,
+    state: glfw::GamepadState,
+}
+
+impl Gamepad {
+    // 가운데에서 deadzone 이내는 0으로, 나머지는 0 ~ 1로 다시 늘린다
+    pub fn get_stick(&self, x: glfw::GamepadAxis, y: glfw::GamepadAxis, deadzone: f32) -> glm::Vec2 {
+        let stick = glm::vec2(self.state.get_axis(x), self.state.get_axis(y));
+        let length = glm::length(&stick);
+        if length <= deadzone {
+            return glm::vec2(0.0, 0.0);
+        }
+        stick * (((length - deadzone) / (1.0 - deadzone)).min(1.0) / length)
+    }
+
+    pub fn get_left_stick(&self, deadzone: f32) -> glm::Vec2 {
+        self.get_stick(glfw::GamepadAxis::AxisLeftX, glfw::GamepadAxis::AxisLeftY, deadzone)
+    }
+
+    pub fn get_right_stick(&self, deadzone: f32) -> glm::Vec2 {
+        self.get_stick(glfw::GamepadAxis::AxisRightX, glfw::GamepadAxis::AxisRightY, deadzone)
+    }
+
+    // 트리거는 -1(놓음) ~ 1(끝까지 당김)로 들어오므로 0 ~ 1로 바꾼다
+    pub fn get_trigger(&self, axis: glfw::GamepadAxis, deadzone: f32) -> f32 {
+        let value = (self.state.get_axis(axis) + 1.0) / 2.0;
+        if value <= deadzone {
+            return 0.0;
+        }
+        ((value - deadzone) / (1.0 - deadzone)).min(1.0)
+    }
+}
+
+// 연결된 게임패드 목록을 매 프레임 갱신한다, 연결과 해제는 목록의 변화로 감지한다
+pub struct Gamepads {
+    joysticks: Vec<glfw::Joystick>,
+    gamepads: Vec<Gamepad>,
+    pub stick_deadzone: f32,
+    pub trigger_deadzone: f32,
+}
+
+impl Gamepads {
+    pub fn create(glfw: &glfw::Glfw) -> Self {
+        if let Ok(mappings) = asset::read_to_string(MAPPINGS_FILE_PATH) {
+            if glfw.update_gamepad_mappings(&mappings) {
+                spdlog::info!("Loaded gamepad mappings \"{}\"", MAPPINGS_FILE_PATH);
+            } else {
+                spdlog::warn!("Failed to load gamepad mappings \"{}\"", MAPPINGS_FILE_PATH);
+            }
+        }
+        let joysticks = JOYSTICK_IDS.iter().map(|id| glfw.get_joystick(*id)).collect();
+
+        Self { joysticks, gamepads: Vec::new(), stick_deadzone: 0.15, trigger_deadzone: 0.05 }
+    }
+
+    pub fn update(&mut self) {
+        let mut gamepads = Vec::new();
+        for joystick in &self.joysticks {
+            // 표준 매핑이 없는 조이스틱은 게임패드로 사용하지 않는다
+            if !joystick.is_present() || !joystick.is_gamepad() {
+                continue;
+            }
+            let Some(state) = joystick.get_gamepad_state() else {
+                continue;
+            };
+            let name = match self.gamepads.iter().find(|gamepad| gamepad.id == joystick.id) {
+                Some(gamepad) => gamepad.name.clone(),
+                None => {
+                    let name = joystick.get_gamepad_name().unwrap_or_default();
+                    spdlog::info!("Connected gamepad {:?} \"{}\"", joystick.id, name);
+                    name
+                }
+            };
+            gamepads.push(Gamepad { id: joystick.id, name, state });
+        }
+        for gamepad in &self.gamepads {
+            if !gamepads.iter().any(|connected| connected.id == gamepad.id) {
+                spdlog::info!("Disconnected gamepad {:?} \"{}\"", gamepad.id, gamepad.name);
+            }
+        }
+        self.gamepads = gamepads;
+    }
+
+    // 가장 먼저 연결된 슬롯의 게임패드
+    pub fn get_active(&self) -> Option<&Gamepad> {
+        self.gamepads.first()
+    }
+}
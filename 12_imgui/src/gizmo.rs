@@ -0,0 +1,284 @@
+use super::{errors, resource, gl_state, buffer::Buffer, camera::{Camera, Projection}, picking::Ray, program::Program, scene::Node, vertex_array::VertexArray};
+use nalgebra_glm as glm;
+use std::{cell::RefCell, rc::Rc};
+
+const RING_SEGMENT_COUNT: usize = 48;
+const PICK_DISTANCE: f32 = 8.0; // 커서와 손잡이 사이의 거리가 이 픽셀 수 이내이면 잡을 수 있다
+const AXIS_COLORS: [[f32; 3]; 3] = [[0.9, 0.2, 0.2], [0.2, 0.9, 0.2], [0.2, 0.4, 1.0]];
+const ACTIVE_COLOR: [f32; 3] = [1.0, 0.9, 0.2];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum GizmoMode {
+    Translate, // 축 화살표
+    Rotate, // 회전 고리
+    Scale, // 끝에 상자가 달린 축, 항상 로컬 축을 사용한다
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum GizmoSpace {
+    Local,
+    World,
+}
+
+// 끌기를 시작할 때의 상태, 끄는 동안에는 시작 상태로부터의 변화량을 다시 계산한다
+struct Drag {
+    axis: usize,
+    direction: glm::Vec3, // world 공간의 축 방향
+    origin: glm::Vec3, // world 공간의 기즈모 중심
+    start: f32, // 축 위에서 잡은 지점의 위치
+    start_vector: glm::Vec3, // 회전 평면 위에서 잡은 지점의 방향
+    translation: glm::Vec3,
+    rotation: glm::Quat,
+    scale: glm::Vec3,
+    parent_matrix: glm::Mat4,
+}
+
+// 선택된 노드를 마우스로 옮기고, 돌리고, 늘리는 손잡이
+pub struct Gizmo {
+    pub mode: GizmoMode,
+    pub space: GizmoSpace,
+    pub size: f32, // 화면 높이에 대한 손잡이 길이의 비율
+    pub translate_snap: f32,
+    pub rotate_snap: f32, // 도
+    pub scale_snap: f32,
+    pub snap: bool, // 켜져 있으면 변화량을 위의 간격 단위로 맞춘다
+    program: Rc<Program>,
+    vertex_array: VertexArray,
+    vertex_buffer: Buffer,
+    hovered_axis: Option<usize>,
+    drag: Option<Drag>,
+    button_down: bool, // 이전 프레임의 버튼 상태, 새로 눌린 순간에만 끌기를 시작한다
+}
+
+impl Gizmo {
+    pub fn create() -> Result<Gizmo, errors::Error> {
+        let program = resource::load_program("shader/gizmo.vert", "shader/gizmo.frag", &[])?;
+        // 정점 하나는 위치 3개, 색 3개, 매 프레임 내용을 새로 채운다
+        let vertex_array = VertexArray::create();
+        vertex_array.bind();
+        let vertex_buffer = Buffer::create(gl::ARRAY_BUFFER, 0, std::ptr::null(), gl::STREAM_DRAW);
+        vertex_array.set(0, 3, gl::FLOAT, gl::FALSE, size_of::<f32>() as i32 * 6, std::ptr::null());
+        vertex_array.set(1, 3, gl::FLOAT, gl::FALSE, size_of::<f32>() as i32 * 6, (size_of::<f32>() * 3) as *const _);
+        vertex_array.set_label("gizmo vao");
+        vertex_buffer.set_label("gizmo vbo");
+
+        Ok(Gizmo { mode: GizmoMode::Translate, space: GizmoSpace::World, size: 0.15, translate_snap: 0.25, rotate_snap: 15.0, scale_snap: 0.1, snap: false, program, vertex_array, vertex_buffer, hovered_axis: None, drag: None, button_down: false })
+    }
+
+    // 커서 위치로 손잡이를 고르고 끄는 중이면 노드의 변환을 갱신한다, 손잡이가 커서 아래에 있거나 끄는 중이면 참을 돌려준다
+    pub fn update(&mut self, node: &Rc<RefCell<Node>>, camera: &Camera, cursor: &glm::Vec2, width: u32, height: u32, down: bool) -> bool {
+        let ray = Ray::from_screen(camera, cursor, width, height);
+        let pressed = down && !self.button_down;
+        self.button_down = down;
+        if !down {
+            self.drag = None;
+        }
+        if let Some(drag) = &self.drag {
+            self.apply_drag(drag, node, &ray);
+            return true;
+        }
+
+        let world_matrix = node.borrow_mut().get_world_matrix();
+        let origin = world_matrix.column(3).xyz();
+        let axes = self.get_axes(&world_matrix);
+        let scale = self.get_screen_scale(camera, &origin);
+        let view_projection = camera.get_projection_matrix(width as f32 / height as f32) * camera.get_view_matrix();
+        self.hovered_axis = (0..3)
+            .filter_map(|axis| {
+                let points = self.get_handle_points(&origin, &axes, axis, scale);
+                let distance = points.windows(2).filter_map(|segment| get_screen_distance(&view_projection, width, height, cursor, &segment[0], &segment[1])).fold(f32::MAX, f32::min);
+                (distance < PICK_DISTANCE).then_some((axis, distance))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(axis, _)| axis);
+
+        if pressed && let Some(axis) = self.hovered_axis {
+            let direction = axes[axis];
+            let start_vector = intersect_plane(&ray, &origin, &direction).map(|point| glm::normalize(&(point - origin))).unwrap_or(axes[(axis + 1) % 3]);
+            let node = node.borrow();
+            let parent_matrix = node.get_parent().map(|parent| parent.borrow_mut().get_world_matrix()).unwrap_or(glm::Mat4::identity());
+            let start = closest_on_axis(&ray, &origin, &direction).unwrap_or(0.0);
+            self.drag = Some(Drag { axis, direction, origin, start, start_vector, translation: node.get_translation(), rotation: node.get_rotation(), scale: node.get_scale(), parent_matrix });
+        }
+        self.hovered_axis.is_some()
+    }
+
+    fn apply_drag(&self, drag: &Drag, node: &Rc<RefCell<Node>>, ray: &Ray) {
+        let snapped = |value: f32, step: f32| if self.snap && 0.0 < step { (value / step).round() * step } else { value };
+        let mut node = node.borrow_mut();
+        match self.mode {
+            GizmoMode::Translate => {
+                let Some(position) = closest_on_axis(ray, &drag.origin, &drag.direction) else {
+                    return;
+                };
+                // world 공간의 이동량을 부모 공간으로 옮긴다
+                let offset = drag.direction * snapped(position - drag.start, self.translate_snap);
+                let offset = (glm::inverse(&drag.parent_matrix) * glm::vec4(offset.x, offset.y, offset.z, 0.0)).xyz();
+                node.set_translation(drag.translation + offset);
+            }
+            GizmoMode::Rotate => {
+                let Some(point) = intersect_plane(ray, &drag.origin, &drag.direction) else {
+                    return;
+                };
+                let vector = glm::normalize(&(point - drag.origin));
+                let angle = glm::dot(&glm::cross(&drag.start_vector, &vector), &drag.direction).atan2(glm::dot(&drag.start_vector, &vector));
+                let angle = snapped(angle.to_degrees(), self.rotate_snap).to_radians();
+                // world 공간의 회전을 부모 공간의 회전으로 바꿔서 원래 회전 앞에 곱한다
+                let parent_rotation = get_rotation(&drag.parent_matrix);
+                let rotation = glm::quat_inverse(&parent_rotation) * glm::quat_angle_axis(angle, &drag.direction) * parent_rotation;
+                node.set_rotation(glm::quat_normalize(&(rotation * drag.rotation)));
+            }
+            GizmoMode::Scale => {
+                let Some(position) = closest_on_axis(ray, &drag.origin, &drag.direction) else {
+                    return;
+                };
+                if drag.start.abs() < f32::EPSILON {
+                    return;
+                }
+                let mut scale = drag.scale;
+                scale[drag.axis] = snapped(drag.scale[drag.axis] * position / drag.start, self.scale_snap).max(0.001);
+                node.set_scale(scale);
+            }
+        }
+    }
+
+    // 장면을 모두 그린 뒤에 깊이 검사 없이 맨 위에 그린다
+    pub fn draw(&self, node: &Rc<RefCell<Node>>, camera: &Camera, width: u32, height: u32) {
+        let world_matrix = node.borrow_mut().get_world_matrix();
+        let origin = world_matrix.column(3).xyz();
+        let axes = self.get_axes(&world_matrix);
+        let scale = self.get_screen_scale(camera, &origin);
+
+        let mut vertices: Vec<f32> = Vec::new();
+        for axis in 0..3 {
+            let active = self.drag.as_ref().map(|drag| drag.axis).or(self.hovered_axis) == Some(axis);
+            let color = if active { ACTIVE_COLOR } else { AXIS_COLORS[axis] };
+            let points = self.get_handle_points(&origin, &axes, axis, scale);
+            for segment in points.windows(2) {
+                for point in segment {
+                    vertices.extend_from_slice(point.as_slice());
+                    vertices.extend_from_slice(&color);
+                }
+            }
+            // 이동은 화살촉, 확대는 상자를 축 끝에 단다
+            let tip = origin + axes[axis] * scale;
+            let (side1, side2) = (axes[(axis + 1) % 3], axes[(axis + 2) % 3]);
+            let lines = match self.mode {
+                GizmoMode::Translate => [side1, -side1, side2, -side2].iter().map(|side| (tip, tip - axes[axis] * scale * 0.15 + side * scale * 0.05)).collect(),
+                GizmoMode::Scale => get_box_lines(&tip, &[axes[axis] * scale * 0.05, side1 * scale * 0.05, side2 * scale * 0.05]),
+                GizmoMode::Rotate => Vec::new(),
+            };
+            for (start, end) in lines {
+                vertices.extend_from_slice(start.as_slice());
+                vertices.extend_from_slice(&color);
+                vertices.extend_from_slice(end.as_slice());
+                vertices.extend_from_slice(&color);
+            }
+        }
+
+        let view_projection = camera.get_projection_matrix(width as f32 / height as f32) * camera.get_view_matrix();
+        self.vertex_buffer.set(gl::ARRAY_BUFFER, size_of_val(vertices.as_slice()).cast_signed(), vertices.as_ptr().cast(), gl::STREAM_DRAW);
+        self.program.use_();
+        self.program.set_uniform_matrix4fv("transform\0", &view_projection);
+        self.vertex_array.bind();
+        gl_state::set_enabled(gl::DEPTH_TEST, false);
+        unsafe {
+            gl::DrawArrays(gl::LINES, 0, (vertices.len() / 6) as i32);
+        }
+        gl_state::set_enabled(gl::DEPTH_TEST, true);
+    }
+
+    // world 공간의 세 축, 확대는 항상 로컬 축을 따른다
+    fn get_axes(&self, world_matrix: &glm::Mat4) -> [glm::Vec3; 3] {
+        if self.space == GizmoSpace::World && self.mode != GizmoMode::Scale {
+            return [glm::vec3(1.0, 0.0, 0.0), glm::vec3(0.0, 1.0, 0.0), glm::vec3(0.0, 0.0, 1.0)];
+        }
+        [0, 1, 2].map(|axis| glm::normalize(&world_matrix.column(axis).xyz()))
+    }
+
+    // 카메라와의 거리와 상관없이 화면에서 같은 크기로 보이도록 하는 world 공간의 길이
+    fn get_screen_scale(&self, camera: &Camera, origin: &glm::Vec3) -> f32 {
+        match camera.get_projection() {
+            Projection::Perspective { fov } => {
+                let depth = glm::dot(&(origin - camera.get_position()), &camera.get_front()).max(camera.get_near());
+                2.0 * depth * (fov.to_radians() / 2.0).tan() * self.size
+            }
+            Projection::Orthographic { height } => height * self.size,
+        }
+    }
+
+    // 손잡이를 이루는 꺾은선, 이동과 확대는 축 방향의 선분, 회전은 축에 수직인 원
+    fn get_handle_points(&self, origin: &glm::Vec3, axes: &[glm::Vec3; 3], axis: usize, scale: f32) -> Vec<glm::Vec3> {
+        match self.mode {
+            GizmoMode::Translate | GizmoMode::Scale => vec![*origin, origin + axes[axis] * scale],
+            GizmoMode::Rotate => {
+                let (side1, side2) = (axes[(axis + 1) % 3], axes[(axis + 2) % 3]);
+                (0..=RING_SEGMENT_COUNT).map(|index| {
+                    let angle = index as f32 / RING_SEGMENT_COUNT as f32 * std::f32::consts::TAU;
+                    origin + (side1 * angle.cos() + side2 * angle.sin()) * scale
+                }).collect()
+            }
+        }
+    }
+}
+
+// world 공간의 점을 화면 좌표(왼쪽 위가 원점, 픽셀)로 옮긴다, 카메라 뒤에 있으면 None
+fn to_screen(view_projection: &glm::Mat4, width: u32, height: u32, point: &glm::Vec3) -> Option<glm::Vec2> {
+    let clip = view_projection * glm::vec4(point.x, point.y, point.z, 1.0);
+    if clip.w <= 0.0 {
+        return None;
+    }
+    let ndc = clip.xy() / clip.w;
+    Some(glm::vec2((ndc.x + 1.0) / 2.0 * width as f32, (1.0 - ndc.y) / 2.0 * height as f32))
+}
+
+// 화면에 투영된 선분과 커서 사이의 거리(픽셀)
+fn get_screen_distance(view_projection: &glm::Mat4, width: u32, height: u32, cursor: &glm::Vec2, start: &glm::Vec3, end: &glm::Vec3) -> Option<f32> {
+    let start = to_screen(view_projection, width, height, start)?;
+    let end = to_screen(view_projection, width, height, end)?;
+    let segment = end - start;
+    let length = glm::dot(&segment, &segment);
+    let t = if length < f32::EPSILON { 0.0 } else { (glm::dot(&(cursor - start), &segment) / length).clamp(0.0, 1.0) };
+    Some(glm::distance(cursor, &(start + segment * t)))
+}
+
+// 광선과 가장 가까운 축 위의 점의 위치, 광선과 축이 평행하면 None
+fn closest_on_axis(ray: &Ray, origin: &glm::Vec3, direction: &glm::Vec3) -> Option<f32> {
+    let b = glm::dot(direction, &ray.direction);
+    let denominator = 1.0 - b * b;
+    if denominator < 1e-4 {
+        return None;
+    }
+    let w = origin - ray.origin;
+    Some((b * glm::dot(&ray.direction, &w) - glm::dot(direction, &w)) / denominator)
+}
+
+// 광선과 origin을 지나고 normal에 수직인 평면의 교점
+fn intersect_plane(ray: &Ray, origin: &glm::Vec3, normal: &glm::Vec3) -> Option<glm::Vec3> {
+    let denominator = glm::dot(&ray.direction, normal);
+    if denominator.abs() < 1e-4 {
+        return None;
+    }
+    let distance = glm::dot(&(origin - ray.origin), normal) / denominator;
+    (0.0 <= distance).then(|| ray.get_point(distance))
+}
+
+// 확대가 섞여 있을 수 있으므로 각 축을 정규화한 뒤에 회전을 꺼낸다
+fn get_rotation(matrix: &glm::Mat4) -> glm::Quat {
+    let columns = [0, 1, 2].map(|axis| glm::normalize(&matrix.column(axis).xyz()));
+    glm::mat3_to_quat(&glm::Mat3::from_columns(&columns))
+}
+
+// center를 중심으로 반 크기가 half_axes인 상자의 모서리 12개
+fn get_box_lines(center: &glm::Vec3, half_axes: &[glm::Vec3; 3]) -> Vec<(glm::Vec3, glm::Vec3)> {
+    let corner = |index: usize| center + (0..3).map(|axis| if index & (1 << axis) == 0 { -half_axes[axis] } else { half_axes[axis] }).fold(glm::vec3(0.0, 0.0, 0.0), |sum, offset| sum + offset);
+    let mut lines = Vec::new();
+    for index in 0..8 {
+        for axis in 0..3 {
+            if index & (1 << axis) == 0 {
+                lines.push((corner(index), corner(index | (1 << axis))));
+            }
+        }
+    }
+    lines
+}
@@ -0,0 +1,113 @@
+// 래퍼 함수가 GL 호출 뒤에 glGetError로 오류를 확인한다
+// 디버그 빌드나 gl_check 기능을 켠 빌드에서만 동작하고, 릴리즈 빌드에서는 아무 일도 하지 않는 빈 함수가 된다
+// 래퍼 함수에 #[cfg_attr(any(debug_assertions, feature = "gl_check"), track_caller)]를 붙이면 래퍼를 부른 위치가 오류에 남는다
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Policy {
+    Panic, // 오류가 난 곳에서 바로 멈춘다
+    Log, // spdlog로 남기고 계속 진행한다
+    Ignore,
+}
+
+impl Policy {
+    // GL_CHECK 환경 변수 값("panic", "log", "ignore")을 해석한다
+    pub fn parse(text: &str) -> Option<Policy> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "panic" => Some(Policy::Panic),
+            "log" => Some(Policy::Log),
+            "ignore" => Some(Policy::Ignore),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(any(debug_assertions, feature = "gl_check"))]
+mod enabled {
+    use super::{super::errors, Policy};
+    use std::cell::Cell;
+
+    thread_local! {
+        static POLICY: Cell<Policy> = const { Cell::new(Policy::Log) };
+    }
+
+    pub fn set_policy(policy: Policy) {
+        POLICY.set(policy);
+    }
+
+    pub fn get_policy() -> Policy {
+        POLICY.get()
+    }
+
+    // 쌓여 있는 오류를 모두 꺼내서 하나의 오류로 만든다
+    #[track_caller]
+    pub fn get_error(operation: &str) -> Result<(), errors::Error> {
+        let mut codes = Vec::new();
+        loop {
+            let code = unsafe { gl::GetError() };
+            // 컨텍스트가 없으면 오류가 끝없이 나올 수 있으므로 개수를 제한한다
+            if code == gl::NO_ERROR || 16 <= codes.len() {
+                break;
+            }
+            codes.push(code);
+        }
+        if codes.is_empty() {
+            return Ok(());
+        }
+        let location = std::panic::Location::caller();
+        Err(errors::Error::GlError(operation.to_owned(), format!("{}:{}", location.file(), location.line()), codes))
+    }
+
+    #[track_caller]
+    pub fn check(operation: &str) {
+        let policy = get_policy();
+        if policy == Policy::Ignore {
+            return;
+        }
+        if let Err(err) = get_error(operation) {
+            match policy {
+                Policy::Panic => panic!("{:?}", err),
+                Policy::Log => spdlog::error!("{:?}", err),
+                Policy::Ignore => {}
+            }
+        }
+    }
+}
+
+#[cfg(not(any(debug_assertions, feature = "gl_check")))]
+mod enabled {
+    use super::{super::errors, Policy};
+
+    pub fn set_policy(_: Policy) {}
+
+    pub fn get_policy() -> Policy {
+        Policy::Ignore
+    }
+
+    #[inline(always)]
+    pub fn get_error(_: &str) -> Result<(), errors::Error> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn check(_: &str) {}
+}
+
+pub use enabled::{set_policy, get_policy, get_error, check};
+
+pub fn get_error_name(code: u32) -> &'static str {
+    match code {
+        gl::INVALID_ENUM => "GL_INVALID_ENUM",
+        gl::INVALID_VALUE => "GL_INVALID_VALUE",
+        gl::INVALID_OPERATION => "GL_INVALID_OPERATION",
+        gl::INVALID_FRAMEBUFFER_OPERATION => "GL_INVALID_FRAMEBUFFER_OPERATION",
+        gl::OUT_OF_MEMORY => "GL_OUT_OF_MEMORY",
+        gl::STACK_UNDERFLOW => "GL_STACK_UNDERFLOW",
+        gl::STACK_OVERFLOW => "GL_STACK_OVERFLOW",
+        _ => "unknown error",
+    }
+}
+
+// errors에서 사용하는 형식, "GL_INVALID_ENUM (0x500), ..."
+pub fn format_codes(codes: &[u32]) -> String {
+    codes.iter().map(|code| format!("{} (0x{:X})", get_error_name(*code), code)).collect::<Vec<_>>().join(", ")
+}
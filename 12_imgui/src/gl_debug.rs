@@ -0,0 +1,128 @@
+use super::common;
+use std::{cell::RefCell, collections::HashSet};
+
+// 드라이버가 자주 보내지만 도움이 되지 않는 메시지
+// 131169: 렌더버퍼 메모리 할당, 131185: 버퍼를 비디오 메모리에 둠, 131204: 텍스쳐가 완전하지 않음(사용하지 않는 유닛), 131218: 쉐이더 재컴파일
+pub const DEFAULT_IGNORED_IDS: [u32; 4] = [131169, 131185, 131204, 131218];
+
+struct DebugState {
+    enabled: bool, // 디버그 출력이 켜졌는지
+    max_label_length: usize,
+    ignored_ids: HashSet<u32>,
+}
+
+thread_local! {
+    // 콜백과 객체 래퍼 어디서든 확인할 수 있도록 둔다
+    static STATE: RefCell<DebugState> = RefCell::new(DebugState { enabled: false, max_label_length: 0, ignored_ids: HashSet::new() });
+}
+
+// OpenGL 4.3 이상이거나 GL_KHR_debug 확장이 있으면 사용할 수 있다
+pub fn is_supported() -> bool {
+    unsafe {
+        let (mut major, mut minor) = (0, 0);
+        gl::GetIntegerv(gl::MAJOR_VERSION, &mut major);
+        gl::GetIntegerv(gl::MINOR_VERSION, &mut minor);
+        if (4, 3) <= (major, minor) {
+            return true;
+        }
+        let mut extension_count = 0;
+        gl::GetIntegerv(gl::NUM_EXTENSIONS, &mut extension_count);
+        (0..extension_count as u32).any(|index| common::c_str_to_string(gl::GetStringi(gl::EXTENSIONS, index).cast()).is_some_and(|extension| extension == "GL_KHR_debug"))
+    }
+}
+
+// 드라이버 메시지를 spdlog로 보낸다, 디버그 컨텍스트가 아니면 드라이버가 메시지를 거의 보내지 않는다
+pub fn init(ignored_ids: &[u32]) -> bool {
+    if !is_supported() || !gl::DebugMessageCallback::is_loaded() {
+        spdlog::warn!("KHR_debug is not supported");
+        return false;
+    }
+    let mut flags = 0;
+    let mut max_label_length = 0;
+    unsafe {
+        gl::GetIntegerv(gl::CONTEXT_FLAGS, &mut flags);
+        gl::GetIntegerv(gl::MAX_LABEL_LENGTH, &mut max_label_length);
+        gl::Enable(gl::DEBUG_OUTPUT);
+        gl::Enable(gl::DEBUG_OUTPUT_SYNCHRONOUS); // 메시지를 일으킨 gl 함수 안에서 콜백이 불리도록 해서 호출 위치를 추적할 수 있게 한다
+        gl::DebugMessageCallback(Some(on_debug_message), std::ptr::null());
+        gl::DebugMessageControl(gl::DONT_CARE, gl::DONT_CARE, gl::DONT_CARE, 0, std::ptr::null(), gl::TRUE);
+    }
+    STATE.with_borrow_mut(|state| {
+        state.enabled = true;
+        state.max_label_length = max_label_length.max(0) as usize;
+    });
+    ignore_ids(ignored_ids);
+    spdlog::info!("Enabled KHR_debug output (debug context: {})", flags as u32 & gl::CONTEXT_FLAG_DEBUG_BIT != 0);
+    true
+}
+
+// 드라이버에서 거르고, 다른 곳에서 콜백이 켜져 있어도 거를 수 있도록 목록도 기억해 둔다
+pub fn ignore_ids(ids: &[u32]) {
+    STATE.with_borrow_mut(|state| {
+        state.ignored_ids.extend(ids);
+        if state.enabled && !ids.is_empty() {
+            unsafe {
+                gl::DebugMessageControl(gl::DONT_CARE, gl::DONT_CARE, gl::DONT_CARE, ids.len() as i32, ids.as_ptr(), gl::FALSE);
+            }
+        }
+    });
+}
+
+pub fn is_enabled() -> bool {
+    STATE.with_borrow(|state| state.enabled)
+}
+
+// identifier는 gl::SHADER, gl::PROGRAM, gl::BUFFER, gl::VERTEX_ARRAY, gl::TEXTURE 등 객체의 종류
+// KHR_debug를 켜지 못했으면 아무것도 하지 않는다
+pub fn set_label(identifier: u32, name: u32, label: &str) {
+    if !is_enabled() || !gl::ObjectLabel::is_loaded() {
+        return;
+    }
+    STATE.with_borrow(|state| {
+        // 최대 길이를 넘으면 GL_INVALID_VALUE가 발생하므로 글자 경계에서 자른다
+        let mut length = label.len().min(state.max_label_length.saturating_sub(1));
+        while !label.is_char_boundary(length) {
+            length -= 1;
+        }
+        unsafe {
+            gl::ObjectLabel(identifier, name, length as i32, label.as_ptr().cast());
+        }
+    });
+}
+
+extern "system" fn on_debug_message(source: u32, type_: u32, id: u32, severity: u32, length: i32, message: *const std::ffi::c_char, _: *mut std::ffi::c_void) {
+    if STATE.with_borrow(|state| state.ignored_ids.contains(&id)) {
+        return;
+    }
+    let message = if length < 0 {
+        common::c_str_to_string(message).unwrap_or_default()
+    } else {
+        let bytes = unsafe { std::slice::from_raw_parts(message.cast::<u8>(), length as usize) };
+        String::from_utf8_lossy(bytes).trim_end_matches('\0').to_owned()
+    };
+    let source = match source {
+        gl::DEBUG_SOURCE_API => "API",
+        gl::DEBUG_SOURCE_WINDOW_SYSTEM => "window system",
+        gl::DEBUG_SOURCE_SHADER_COMPILER => "shader compiler",
+        gl::DEBUG_SOURCE_THIRD_PARTY => "third party",
+        gl::DEBUG_SOURCE_APPLICATION => "application",
+        _ => "other",
+    };
+    let type_ = match type_ {
+        gl::DEBUG_TYPE_ERROR => "error",
+        gl::DEBUG_TYPE_DEPRECATED_BEHAVIOR => "deprecated behavior",
+        gl::DEBUG_TYPE_UNDEFINED_BEHAVIOR => "undefined behavior",
+        gl::DEBUG_TYPE_PORTABILITY => "portability",
+        gl::DEBUG_TYPE_PERFORMANCE => "performance",
+        gl::DEBUG_TYPE_MARKER => "marker",
+        gl::DEBUG_TYPE_PUSH_GROUP => "push group",
+        gl::DEBUG_TYPE_POP_GROUP => "pop group",
+        _ => "other",
+    };
+    match severity {
+        gl::DEBUG_SEVERITY_HIGH => spdlog::error!("GL {} {} ({}): {}", source, type_, id, message),
+        gl::DEBUG_SEVERITY_MEDIUM => spdlog::warn!("GL {} {} ({}): {}", source, type_, id, message),
+        gl::DEBUG_SEVERITY_LOW => spdlog::info!("GL {} {} ({}): {}", source, type_, id, message),
+        _ => spdlog::debug!("GL {} {} ({}): {}", source, type_, id, message),
+    }
+}
@@ -0,0 +1,171 @@
+use std::{cell::RefCell, collections::HashMap};
+
+// OpenGL 상태를 기억해 두고 바뀌지 않는 설정은 GL 함수를 부르지 않는다
+// None은 아직 모르는 상태로, 다음 호출은 항상 GL에 전달된다
+struct State {
+    program: Option<u32>,
+    vertex_array: Option<u32>,
+    active_unit: Option<u32>,
+    textures: HashMap<(u32, u32), u32>, // (텍스쳐 유닛, target) -> 텍스쳐
+    buffers: HashMap<u32, u32>, // target -> 버퍼
+    capabilities: HashMap<u32, bool>,
+    viewport: Option<[i32; 4]>,
+    blend_func: Option<(u32, u32)>,
+    depth_mask: Option<bool>,
+    stats: StateStats,
+}
+
+impl State {
+    fn new() -> Self {
+        Self { program: None, vertex_array: None, active_unit: None, textures: HashMap::new(), buffers: HashMap::new(), capabilities: HashMap::new(), viewport: None, blend_func: None, depth_mask: None, stats: StateStats::new() }
+    }
+}
+
+// issued는 GL에 전달한 호출, skipped는 상태가 같아서 건너뛴 호출
+#[derive(Clone, Copy)]
+pub struct StateStats {
+    pub issued: usize,
+    pub skipped: usize,
+}
+
+impl StateStats {
+    pub fn new() -> Self {
+        Self { issued: 0, skipped: 0 }
+    }
+}
+
+thread_local! {
+    // OpenGL 컨텍스트는 만든 스레드에서만 사용할 수 있으므로 스레드마다 따로 둔다
+    static STATE: RefCell<State> = RefCell::new(State::new());
+}
+
+// 기억한 값과 다를 때만 apply를 부르고 값을 갱신한다
+fn update<T, F>(cached: &mut Option<T>, stats: &mut StateStats, value: T, apply: F) where T: PartialEq, F: FnOnce() {
+    if cached.as_ref() == Some(&value) {
+        stats.skipped += 1;
+        return;
+    }
+    apply();
+    *cached = Some(value);
+    stats.issued += 1;
+}
+
+pub fn use_program(program: u32) {
+    STATE.with_borrow_mut(|state| update(&mut state.program, &mut state.stats, program, || unsafe { gl::UseProgram(program) }));
+}
+
+pub fn bind_vertex_array(vertex_array: u32) {
+    STATE.with_borrow_mut(|state| {
+        let changed = state.vertex_array != Some(vertex_array);
+        update(&mut state.vertex_array, &mut state.stats, vertex_array, || unsafe { gl::BindVertexArray(vertex_array) });
+        // ebo 바인딩은 vao에 저장되므로 vao가 바뀌면 알 수 없게 된다
+        if changed {
+            state.buffers.remove(&gl::ELEMENT_ARRAY_BUFFER);
+        }
+    });
+}
+
+// unit은 0부터 시작하는 텍스쳐 유닛 번호
+pub fn active_texture(unit: u32) {
+    STATE.with_borrow_mut(|state| update(&mut state.active_unit, &mut state.stats, unit, || unsafe { gl::ActiveTexture(gl::TEXTURE0 + unit) }));
+}
+
+// 현재 활성화된 텍스쳐 유닛에 바인딩한다
+pub fn bind_texture(target: u32, texture: u32) {
+    STATE.with_borrow_mut(|state| {
+        let Some(unit) = state.active_unit else {
+            unsafe {
+                gl::BindTexture(target, texture);
+            }
+            state.stats.issued += 1;
+            return;
+        };
+        let mut cached = state.textures.get(&(unit, target)).copied();
+        update(&mut cached, &mut state.stats, texture, || unsafe { gl::BindTexture(target, texture) });
+        state.textures.insert((unit, target), texture);
+    });
+}
+
+pub fn bind_buffer(target: u32, buffer: u32) {
+    STATE.with_borrow_mut(|state| {
+        let mut cached = state.buffers.get(&target).copied();
+        update(&mut cached, &mut state.stats, buffer, || unsafe { gl::BindBuffer(target, buffer) });
+        state.buffers.insert(target, buffer);
+    });
+}
+
+// BLEND, DEPTH_TEST, CULL_FACE, STENCIL_TEST처럼 gl::Enable로 켜고 끄는 기능
+pub fn set_enabled(capability: u32, enabled: bool) {
+    STATE.with_borrow_mut(|state| {
+        let mut cached = state.capabilities.get(&capability).copied();
+        update(&mut cached, &mut state.stats, enabled, || unsafe {
+            if enabled {
+                gl::Enable(capability);
+            } else {
+                gl::Disable(capability);
+            }
+        });
+        state.capabilities.insert(capability, enabled);
+    });
+}
+
+pub fn is_enabled(capability: u32) -> bool {
+    STATE.with_borrow_mut(|state| {
+        *state.capabilities.entry(capability).or_insert_with(|| unsafe { gl::IsEnabled(capability) == gl::TRUE })
+    })
+}
+
+pub fn set_viewport(x: i32, y: i32, width: i32, height: i32) {
+    STATE.with_borrow_mut(|state| update(&mut state.viewport, &mut state.stats, [x, y, width, height], || unsafe { gl::Viewport(x, y, width, height) }));
+}
+
+pub fn get_viewport() -> [i32; 4] {
+    STATE.with_borrow_mut(|state| {
+        *state.viewport.get_or_insert_with(|| {
+            let mut viewport = [0i32; 4];
+            unsafe {
+                gl::GetIntegerv(gl::VIEWPORT, viewport.as_mut_ptr());
+            }
+            viewport
+        })
+    })
+}
+
+pub fn set_blend_func(source: u32, destination: u32) {
+    STATE.with_borrow_mut(|state| update(&mut state.blend_func, &mut state.stats, (source, destination), || unsafe { gl::BlendFunc(source, destination) }));
+}
+
+pub fn set_depth_mask(enabled: bool) {
+    STATE.with_borrow_mut(|state| update(&mut state.depth_mask, &mut state.stats, enabled, || unsafe { gl::DepthMask(if enabled { gl::TRUE } else { gl::FALSE }) }));
+}
+
+// 삭제된 객체가 바인딩 되어 있던 곳은 0으로 돌아간다, 같은 이름이 다시 만들어져도 바인딩을 건너뛰지 않도록 한다
+pub fn on_program_deleted(program: u32) {
+    STATE.with_borrow_mut(|state| {
+        // 사용 중인 프로그램은 다른 프로그램으로 바꿀 때까지 남아 있으므로 모르는 상태로 둔다
+        if state.program == Some(program) {
+            state.program = None;
+        }
+    });
+}
+
+pub fn on_vertex_array_deleted(vertex_array: u32) {
+    STATE.with_borrow_mut(|state| {
+        if state.vertex_array == Some(vertex_array) {
+            state.vertex_array = Some(0);
+            state.buffers.remove(&gl::ELEMENT_ARRAY_BUFFER);
+        }
+    });
+}
+
+pub fn on_texture_deleted(texture: u32) {
+    STATE.with_borrow_mut(|state| state.textures.values_mut().filter(|bound| **bound == texture).for_each(|bound| *bound = 0));
+}
+
+pub fn on_buffer_deleted(buffer: u32) {
+    STATE.with_borrow_mut(|state| state.buffers.values_mut().filter(|bound| **bound == buffer).for_each(|bound| *bound = 0));
+}
+
+pub fn get_stats() -> StateStats {
+    STATE.with_borrow(|state| state.stats)
+}
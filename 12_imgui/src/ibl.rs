@@ -0,0 +1,166 @@
+use super::{errors, resource, asset, gl_state, cube_texture::CubeTexture, framebuffer::Framebuffer, mesh::Mesh, primitive::Primitive, program::Program, texture::Texture, vertex_array::VertexArray};
+use nalgebra_glm as glm;
+use std::rc::Rc;
+
+const ENVIRONMENT_SIZE: u32 = 512;
+const IRRADIANCE_SIZE: u32 = 32;
+const PREFILTER_SIZE: u32 = 128;
+const PREFILTER_MIP_COUNT: u32 = 5; // 거칠기 0.0, 0.25, 0.5, 0.75, 1.0
+const BRDF_LUT_SIZE: u32 = 512;
+
+// image-based lighting에 필요한 텍스쳐를 불러올 때 한 번만 미리 계산한다
+pub struct Environment {
+    environment_map: CubeTexture,
+    irradiance_map: CubeTexture,
+    prefilter_map: CubeTexture,
+    brdf_lut: Texture,
+    cube: Mesh,
+    skybox_program: Rc<Program>,
+    pub intensity: f32,
+}
+
+impl Environment {
+    // equirectangular 형식의 HDR 이미지(.hdr)가 없으면 단순한 하늘을 환경 맵으로 사용한다
+    pub fn create(file_path: Option<&str>) -> Result<Environment, errors::Error> {
+        let viewport = gl_state::get_viewport();
+        let blend = gl_state::is_enabled(gl::BLEND);
+        gl_state::set_enabled(gl::BLEND, false);
+        gl_state::set_enabled(gl::DEPTH_TEST, true);
+        gl_state::set_enabled(gl::TEXTURE_CUBE_MAP_SEAMLESS, true); // 큐브맵의 면 경계에서도 이웃한 면과 보간한다
+        let cube = Primitive::cube(2.0, 1).create_mesh();
+        let mut framebuffer = Framebuffer::create();
+
+        // 1. equirectangular 이미지를 큐브맵으로 옮긴다
+        let equirectangular_map = match file_path {
+            Some(file_path) => Some(load_hdr(file_path)?),
+            None => None,
+        };
+        let program = resource::load_program("shader/cubemap.vert", "shader/equirectangular.frag", &[])?;
+        program.use_();
+        program.set_uniform1i("equirectangular_map\0", 0);
+        program.set_uniform1i("has_equirectangular_map\0", equirectangular_map.is_some() as i32);
+        if let Some(equirectangular_map) = &equirectangular_map {
+            gl_state::active_texture(0);
+            equirectangular_map.bind();
+        }
+        let environment_map = CubeTexture::create(ENVIRONMENT_SIZE, true);
+        render_to_cube(&mut framebuffer, &environment_map, 0, &program, &cube)?;
+        environment_map.generate_mipmap();
+
+        // 2. diffuse 반사에 사용할 irradiance 맵
+        let program = resource::load_program("shader/cubemap.vert", "shader/irradiance.frag", &[])?;
+        program.use_();
+        program.set_uniform1i("environment_map\0", 0);
+        gl_state::active_texture(0);
+        environment_map.bind();
+        let irradiance_map = CubeTexture::create(IRRADIANCE_SIZE, false);
+        render_to_cube(&mut framebuffer, &irradiance_map, 0, &program, &cube)?;
+
+        // 3. specular 반사에 사용할 거칠기별 prefilter 맵
+        let program = resource::load_program("shader/cubemap.vert", "shader/prefilter.frag", &[])?;
+        program.use_();
+        program.set_uniform1i("environment_map\0", 0);
+        program.set_uniform1f("environment_size\0", ENVIRONMENT_SIZE as f32);
+        let prefilter_map = CubeTexture::create(PREFILTER_SIZE, true);
+        for level in 0..PREFILTER_MIP_COUNT {
+            program.set_uniform1f("roughness\0", level as f32 / (PREFILTER_MIP_COUNT - 1) as f32);
+            render_to_cube(&mut framebuffer, &prefilter_map, level as i32, &program, &cube)?;
+        }
+
+        // 4. split sum 근사에 사용할 BRDF 적분 표
+        let brdf_lut = Texture::create();
+        brdf_lut.set_label("brdf lut");
+        brdf_lut.set_storage(BRDF_LUT_SIZE, BRDF_LUT_SIZE, gl::RG16F, gl::RG, gl::FLOAT);
+        framebuffer.set_depth_renderbuffer(BRDF_LUT_SIZE, BRDF_LUT_SIZE);
+        framebuffer.attach_texture(gl::COLOR_ATTACHMENT0, gl::TEXTURE_2D, brdf_lut.get(), 0).check()?;
+        let program = resource::load_program("shader/fullscreen.vert", "shader/brdf.frag", &[])?;
+        program.use_();
+        let vertex_array = VertexArray::create(); // 정점 속성은 없지만 core profile에서는 vao가 필요하다
+        vertex_array.bind();
+        gl_state::set_viewport(0, 0, BRDF_LUT_SIZE.cast_signed(), BRDF_LUT_SIZE.cast_signed());
+        unsafe {
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+            gl::DrawArrays(gl::TRIANGLES, 0, 3);
+        }
+
+        Framebuffer::unbind();
+        gl_state::set_viewport(viewport[0], viewport[1], viewport[2], viewport[3]);
+        gl_state::set_enabled(gl::BLEND, blend);
+        spdlog::info!("Created environment ({})", file_path.unwrap_or("sky"));
+
+        let skybox_program = resource::load_program("shader/skybox.vert", "shader/skybox.frag", &[])?;
+
+        Ok(Environment { environment_map, irradiance_map, prefilter_map, brdf_lut, cube, skybox_program, intensity: 1.0 })
+    }
+
+    // PBR 프로그램의 IBL 전역 변수를 채우고, first_unit부터 텍스쳐 3개를 바인딩한다
+    pub fn bind(&self, program: &Program, first_unit: u32) {
+        program.set_uniform1i("has_environment\0", 1);
+        program.set_uniform1i("irradiance_map\0", first_unit.cast_signed());
+        program.set_uniform1i("prefilter_map\0", first_unit.cast_signed() + 1);
+        program.set_uniform1i("brdf_lut\0", first_unit.cast_signed() + 2);
+        program.set_uniform1f("prefilter_max_lod\0", (PREFILTER_MIP_COUNT - 1) as f32);
+        program.set_uniform1f("environment_intensity\0", self.intensity);
+        gl_state::active_texture(first_unit);
+        self.irradiance_map.bind();
+        gl_state::active_texture(first_unit + 1);
+        self.prefilter_map.bind();
+        gl_state::active_texture(first_unit + 2);
+        self.brdf_lut.bind();
+    }
+
+    // 장면을 모두 그린 뒤에 그려야 가려진 부분의 계산을 줄일 수 있다
+    pub fn draw_skybox(&self, view: &glm::Mat4, projection: &glm::Mat4) {
+        // 카메라가 움직여도 하늘이 따라오도록 평행 이동을 뺀다
+        let view = glm::mat3_to_mat4(&glm::mat4_to_mat3(view));
+        self.skybox_program.use_();
+        self.skybox_program.set_uniform_matrix4fv("view\0", &view);
+        self.skybox_program.set_uniform_matrix4fv("projection\0", projection);
+        self.skybox_program.set_uniform1i("environment_map\0", 0);
+        gl_state::active_texture(0);
+        self.environment_map.bind();
+        unsafe {
+            gl::DepthFunc(gl::LEQUAL); // 깊이가 1.0인 하늘도 통과하도록 한다
+            self.cube.draw();
+            gl::DepthFunc(gl::LESS);
+        }
+    }
+}
+
+// 이미지 파일과 OpenGL은 이미지의 시작점이 상하 대칭이므로 상하 반전시킨다
+fn load_hdr(file_path: &str) -> Result<Texture, errors::Error> {
+    let image = ::image::load_from_memory(&asset::read(file_path)?)?.flipv().to_rgb32f();
+    spdlog::info!("Loaded image file \"{}\" ({} x {}, HDR)", file_path, image.width(), image.height());
+    let texture = Texture::create();
+    texture.set_label(file_path).set_hdr_texture(image.width(), image.height(), image.as_raw()).set_wrap(gl::REPEAT, gl::CLAMP_TO_EDGE);
+    Ok(texture)
+}
+
+// 큐브의 중심에서 6개의 면을 90도 화각으로 하나씩 그린다
+fn render_to_cube(framebuffer: &mut Framebuffer, target: &CubeTexture, level: i32, program: &Program, cube: &Mesh) -> Result<(), errors::Error> {
+    let size = (target.get_size() >> level).max(1);
+    let projection = glm::perspective(1.0, 90.0f32.to_radians(), 0.1, 10.0);
+    let origin = glm::vec3(0.0, 0.0, 0.0);
+    let views = [
+        glm::look_at(&origin, &glm::vec3(1.0, 0.0, 0.0), &glm::vec3(0.0, -1.0, 0.0)),
+        glm::look_at(&origin, &glm::vec3(-1.0, 0.0, 0.0), &glm::vec3(0.0, -1.0, 0.0)),
+        glm::look_at(&origin, &glm::vec3(0.0, 1.0, 0.0), &glm::vec3(0.0, 0.0, 1.0)),
+        glm::look_at(&origin, &glm::vec3(0.0, -1.0, 0.0), &glm::vec3(0.0, 0.0, -1.0)),
+        glm::look_at(&origin, &glm::vec3(0.0, 0.0, 1.0), &glm::vec3(0.0, -1.0, 0.0)),
+        glm::look_at(&origin, &glm::vec3(0.0, 0.0, -1.0), &glm::vec3(0.0, -1.0, 0.0)),
+    ];
+
+    program.use_();
+    program.set_uniform_matrix4fv("projection\0", &projection);
+    framebuffer.set_depth_renderbuffer(size, size);
+    gl_state::set_viewport(0, 0, size.cast_signed(), size.cast_signed());
+    for (face, view) in views.iter().enumerate() {
+        program.set_uniform_matrix4fv("view\0", view);
+        framebuffer.attach_texture(gl::COLOR_ATTACHMENT0, gl::TEXTURE_CUBE_MAP_POSITIVE_X + face as u32, target.get(), level).check()?;
+        unsafe {
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+        }
+        cube.draw();
+    }
+    Ok(())
+}
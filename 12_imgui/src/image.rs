@@ -1,4 +1,4 @@
-use super::errors;
+use super::{errors, asset};
 use image::EncodableLayout;
 
 pub struct Image {
@@ -11,7 +11,7 @@ pub struct Image {
 impl Image {
     pub fn load<S>(file_path: S) -> Result<Image, errors::Error> where S: AsRef<str> {
         // 이미지 파일과 OpenGL은 이미지의 시작점이 상하 대칭 관계에 있기 때문에 불러온 이미지 파일을 상하 반전시켜야 한다
-        let image = image::open(file_path.as_ref())?.flipv();
+        let image = image::load_from_memory(&asset::read(file_path.as_ref())?)?.flipv();
         let width = image.width();
         let height = image.height();
         let channel_count = image.color().channel_count();
@@ -20,6 +20,11 @@ impl Image {
         Ok(Image { width, height, channel_count, data })
     }
 
+    // 이미 디코딩 된 픽셀 데이터로부터 이미지를 만든다 (상하 반전하지 않음)
+    pub fn from_raw(width: u32, height: u32, channel_count: u8, data: Vec<u8>) -> Image {
+        Image { width, height, channel_count, data }
+    }
+
     pub fn get_width(&self) -> u32 {
         self.width
     }
@@ -0,0 +1,356 @@
+use super::{errors, asset};
+use std::collections::{HashMap, HashSet};
+
+// 설정 파일에서 사용하는 키 이름은 glfw::Key의 이름과 같다
+macro_rules! key_names {
+    ($($name:ident),* $(,)?) => {
+        const KEY_NAMES: &[(&str, glfw::Key)] = &[$((stringify!($name), glfw::Key::$name)),*];
+    };
+}
+
+key_names!(
+    Space, Apostrophe, Comma, Minus, Period, Slash, Num0, Num1, Num2, Num3, Num4, Num5, Num6, Num7, Num8, Num9, Semicolon, Equal,
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+    LeftBracket, Backslash, RightBracket, GraveAccent, Escape, Enter, Tab, Backspace, Insert, Delete, Right, Left, Down, Up,
+    PageUp, PageDown, Home, End, CapsLock, ScrollLock, NumLock, PrintScreen, Pause,
+    F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+    Kp0, Kp1, Kp2, Kp3, Kp4, Kp5, Kp6, Kp7, Kp8, Kp9, KpDecimal, KpDivide, KpMultiply, KpSubtract, KpAdd, KpEnter, KpEqual,
+    LeftShift, LeftControl, LeftAlt, LeftSuper, RightShift, RightControl, RightAlt, RightSuper, Menu,
+);
+
+const MOUSE_BUTTON_NAMES: &[(&str, glfw::MouseButton)] = &[
+    ("MouseLeft", glfw::MouseButton::Button1),
+    ("MouseRight", glfw::MouseButton::Button2),
+    ("MouseMiddle", glfw::MouseButton::Button3),
+    ("Mouse4", glfw::MouseButton::Button4),
+    ("Mouse5", glfw::MouseButton::Button5),
+    ("Mouse6", glfw::MouseButton::Button6),
+    ("Mouse7", glfw::MouseButton::Button7),
+    ("Mouse8", glfw::MouseButton::Button8),
+];
+
+const MODIFIER_NAMES: &[(&str, glfw::Modifiers)] = &[
+    ("Ctrl", glfw::Modifiers::Control),
+    ("Shift", glfw::Modifiers::Shift),
+    ("Alt", glfw::Modifiers::Alt),
+    ("Super", glfw::Modifiers::Super),
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Trigger {
+    Key(glfw::Key),
+    MouseButton(glfw::MouseButton),
+    ScrollUp,
+    ScrollDown,
+    ScrollLeft,
+    ScrollRight,
+}
+
+// 트리거와 함께 눌려 있어야 하는 modifier
+#[derive(Clone, Copy, PartialEq)]
+pub struct Binding {
+    pub trigger: Trigger,
+    pub modifiers: glfw::Modifiers,
+}
+
+impl Binding {
+    pub fn new(trigger: Trigger) -> Self {
+        Self { trigger, modifiers: glfw::Modifiers::empty() }
+    }
+
+    // "Ctrl+Shift+S", "MouseRight", "ScrollUp" 형식의 문자열을 읽는다
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut names: Vec<&str> = text.split('+').map(|name| name.trim()).collect();
+        let trigger_name = names.pop()?;
+        let mut modifiers = glfw::Modifiers::empty();
+        for name in names {
+            modifiers |= MODIFIER_NAMES.iter().find(|(modifier_name, _)| modifier_name.eq_ignore_ascii_case(name))?.1;
+        }
+        let trigger = match trigger_name {
+            "ScrollUp" => Trigger::ScrollUp,
+            "ScrollDown" => Trigger::ScrollDown,
+            "ScrollLeft" => Trigger::ScrollLeft,
+            "ScrollRight" => Trigger::ScrollRight,
+            _ => {
+                if let Some((_, button)) = MOUSE_BUTTON_NAMES.iter().find(|(name, _)| *name == trigger_name) {
+                    Trigger::MouseButton(*button)
+                } else {
+                    Trigger::Key(KEY_NAMES.iter().find(|(name, _)| *name == trigger_name)?.1)
+                }
+            }
+        };
+        Some(Self { trigger, modifiers })
+    }
+}
+
+// 이름이 붙은 동작(action)과 축(axis)을 키, 마우스 버튼, 스크롤에 연결한다
+pub struct InputMap {
+    actions: HashMap<String, Vec<Binding>>,
+    axes: HashMap<String, Vec<(Binding, f32)>>,
+    keys_down: HashSet<glfw::Key>,
+    mouse_buttons_down: HashSet<glfw::MouseButton>,
+    pressed: HashSet<Trigger>, // 이번 프레임에 새로 눌린 트리거
+    scroll: (f32, f32),
+}
+
+impl InputMap {
+    pub fn new() -> Self {
+        Self { actions: HashMap::new(), axes: HashMap::new(), keys_down: HashSet::new(), mouse_buttons_down: HashSet::new(), pressed: HashSet::new(), scroll: (0.0, 0.0) }
+    }
+
+    // 설정 파일이 없을 때 사용하는 기본 연결
+    pub fn create_default() -> Self {
+        let mut input_map = Self::new();
+        input_map.bind_action("look", Binding::new(Trigger::MouseButton(glfw::MouseButton::Button2)));
+        input_map.bind_action("select", Binding::new(Trigger::MouseButton(glfw::MouseButton::Button1)));
+        input_map.bind_action("screenshot", Binding::new(Trigger::Key(glfw::Key::F12)));
+        input_map.bind_action("camera_mode", Binding::new(Trigger::Key(glfw::Key::V)));
+        input_map.bind_action("camera_projection", Binding::new(Trigger::Key(glfw::Key::Kp5)));
+        input_map.bind_action("toggle_light", Binding::new(Trigger::Key(glfw::Key::L)));
+        input_map.bind_action("toggle_pbr", Binding::new(Trigger::Key(glfw::Key::P)));
+        input_map.bind_action("log_stats", Binding::new(Trigger::Key(glfw::Key::F9)));
+        input_map.bind_action("toggle_bounds", Binding::new(Trigger::Key(glfw::Key::B)));
+        input_map.bind_action("toggle_shadow_map", Binding::new(Trigger::Key(glfw::Key::O)));
+        input_map.bind_action("toggle_cascades", Binding::new(Trigger::Key(glfw::Key::C)));
+        input_map.bind_action("shadow_bias_up", Binding::new(Trigger::Key(glfw::Key::RightBracket)));
+        input_map.bind_action("shadow_bias_down", Binding::new(Trigger::Key(glfw::Key::LeftBracket)));
+        input_map.bind_action("gizmo_translate", Binding::new(Trigger::Key(glfw::Key::T)));
+        input_map.bind_action("gizmo_rotate", Binding::new(Trigger::Key(glfw::Key::R)));
+        input_map.bind_action("gizmo_scale", Binding::new(Trigger::Key(glfw::Key::Y)));
+        input_map.bind_action("gizmo_space", Binding::new(Trigger::Key(glfw::Key::X)));
+        input_map.bind_action("gizmo_snap", Binding::new(Trigger::Key(glfw::Key::LeftControl)));
+        input_map.bind_action("toggle_profiler", Binding::new(Trigger::Key(glfw::Key::F3)));
+        input_map.bind_action("profiler_dump", Binding::new(Trigger::Key(glfw::Key::F11)));
+        input_map.bind_action("list_resources", Binding::new(Trigger::Key(glfw::Key::F10)));
+        input_map.bind_axis("move_right", Binding::new(Trigger::Key(glfw::Key::D)), 1.0);
+        input_map.bind_axis("move_right", Binding::new(Trigger::Key(glfw::Key::A)), -1.0);
+        input_map.bind_axis("move_up", Binding::new(Trigger::Key(glfw::Key::Space)), 1.0);
+        input_map.bind_axis("move_up", Binding::new(Trigger::Key(glfw::Key::LeftShift)), -1.0);
+        input_map.bind_axis("move_forward", Binding::new(Trigger::Key(glfw::Key::W)), 1.0);
+        input_map.bind_axis("move_forward", Binding::new(Trigger::Key(glfw::Key::S)), -1.0);
+        input_map.bind_axis("zoom", Binding::new(Trigger::ScrollUp), 1.0);
+        input_map.bind_axis("zoom", Binding::new(Trigger::ScrollDown), -1.0);
+        input_map
+    }
+
+    // 한 줄에 하나씩 "action <이름> = <바인딩>, ..." 또는 "axis <이름> = <바인딩> <배율>, ..." 형식으로 적는다
+    pub fn load<S>(file_path: S) -> Result<Self, errors::Error> where S: AsRef<str> {
+        let file_path = file_path.as_ref();
+        let source = asset::read_to_string(file_path)?;
+        let input_map = Self::parse(file_path, &source)?;
+        spdlog::info!("Loaded input config file \"{}\" ({} actions, {} axes)", file_path, input_map.actions.len(), input_map.axes.len());
+        Ok(input_map)
+    }
+
+    fn parse(file_path: &str, source: &str) -> Result<Self, errors::Error> {
+        let mut input_map = Self::new();
+        for (number, line) in source.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let parse_error = |description: &str| errors::Error::ParseConfigError(file_path.to_owned(), number + 1, description.to_owned());
+            let (declaration, bindings) = line.split_once('=').ok_or_else(|| parse_error("expected '='"))?;
+            let mut declaration = declaration.split_whitespace();
+            let (Some(kind), Some(name), None) = (declaration.next(), declaration.next(), declaration.next()) else {
+                return Err(parse_error("expected \"action <name>\" or \"axis <name>\""));
+            };
+            for binding in bindings.split(',').map(|binding| binding.trim()).filter(|binding| !binding.is_empty()) {
+                match kind {
+                    "action" => {
+                        let binding = Binding::parse(binding).ok_or_else(|| parse_error(&format!("unknown binding \"{}\"", binding)))?;
+                        input_map.bind_action(name, binding);
+                    }
+                    "axis" => {
+                        let (binding, scale) = match binding.rsplit_once(char::is_whitespace) {
+                            Some((binding, scale)) => (binding.trim(), scale.parse::<f32>().map_err(|_| parse_error(&format!("invalid scale \"{}\"", scale)))?),
+                            None => (binding, 1.0),
+                        };
+                        let binding = Binding::parse(binding).ok_or_else(|| parse_error(&format!("unknown binding \"{}\"", binding)))?;
+                        input_map.bind_axis(name, binding, scale);
+                    }
+                    _ => return Err(parse_error(&format!("unknown declaration \"{}\"", kind))),
+                }
+            }
+        }
+        Ok(input_map)
+    }
+
+    pub fn bind_action(&mut self, name: &str, binding: Binding) {
+        self.actions.entry(name.to_owned()).or_default().push(binding);
+    }
+
+    pub fn bind_axis(&mut self, name: &str, binding: Binding, scale: f32) {
+        self.axes.entry(name.to_owned()).or_default().push((binding, scale));
+    }
+
+    pub fn get_action_bindings(&self, name: &str) -> &[Binding] {
+        self.actions.get(name).map(|bindings| bindings.as_slice()).unwrap_or(&[])
+    }
+
+    pub fn get_axis_bindings(&self, name: &str) -> &[(Binding, f32)] {
+        self.axes.get(name).map(|bindings| bindings.as_slice()).unwrap_or(&[])
+    }
+
+    // 연결된 트리거 중 하나라도 눌려 있으면 참
+    pub fn is_active(&self, name: &str) -> bool {
+        self.get_action_bindings(name).iter().any(|binding| 0.0 < self.get_value(binding))
+    }
+
+    // 이번 프레임에 새로 눌렸으면 참
+    pub fn is_pressed(&self, name: &str) -> bool {
+        self.get_action_bindings(name).iter().any(|binding| self.pressed.contains(&binding.trigger) && self.is_matched(binding))
+    }
+
+    pub fn get_axis(&self, name: &str) -> f32 {
+        self.get_axis_bindings(name).iter().map(|(binding, scale)| self.get_value(binding) * scale).sum()
+    }
+
+    // 키 이벤트의 modifier는 플랫폼마다 다르게 채워지므로, 눌린 키로부터 직접 계산한다
+    fn get_modifiers(&self) -> glfw::Modifiers {
+        let mut modifiers = glfw::Modifiers::empty();
+        let pairs = [
+            (glfw::Key::LeftControl, glfw::Key::RightControl, glfw::Modifiers::Control),
+            (glfw::Key::LeftShift, glfw::Key::RightShift, glfw::Modifiers::Shift),
+            (glfw::Key::LeftAlt, glfw::Key::RightAlt, glfw::Modifiers::Alt),
+            (glfw::Key::LeftSuper, glfw::Key::RightSuper, glfw::Modifiers::Super),
+        ];
+        for (left, right, modifier) in pairs {
+            if self.keys_down.contains(&left) || self.keys_down.contains(&right) {
+                modifiers |= modifier;
+            }
+        }
+        modifiers
+    }
+
+    // 눌린 modifier가 바인딩의 modifier를 모두 포함하고, 같은 트리거에 더 많은 modifier가 맞는 바인딩이 없으면 참
+    // Ctrl+P를 누르면 Ctrl+P 바인딩만 동작하고 P 바인딩은 동작하지 않는다, Shift+W처럼 따로 연결하지 않은 조합은 W로 동작한다
+    fn is_matched(&self, binding: &Binding) -> bool {
+        let modifiers = self.get_modifiers();
+        if !modifiers.contains(binding.modifiers) {
+            return false;
+        }
+        let action_bindings = self.actions.values().flatten();
+        let axis_bindings = self.axes.values().flatten().map(|(binding, _)| binding);
+        !action_bindings.chain(axis_bindings).any(|other| {
+            other.trigger == binding.trigger && other.modifiers != binding.modifiers && other.modifiers.contains(binding.modifiers) && modifiers.contains(other.modifiers)
+        })
+    }
+
+    // 버튼은 눌려 있으면 1, 스크롤은 이번 프레임의 이동량
+    fn get_value(&self, binding: &Binding) -> f32 {
+        if !self.is_matched(binding) {
+            return 0.0;
+        }
+        let down = |down: bool| if down { 1.0 } else { 0.0 };
+        match binding.trigger {
+            Trigger::Key(key) => down(self.keys_down.contains(&key)),
+            Trigger::MouseButton(button) => down(self.mouse_buttons_down.contains(&button)),
+            Trigger::ScrollUp => self.scroll.1.max(0.0),
+            Trigger::ScrollDown => (-self.scroll.1).max(0.0),
+            Trigger::ScrollLeft => (-self.scroll.0).max(0.0),
+            Trigger::ScrollRight => self.scroll.0.max(0.0),
+        }
+    }
+
+    pub fn on_key_event(&mut self, key: glfw::Key, action: glfw::Action) {
+        match action {
+            glfw::Action::Press => {
+                self.keys_down.insert(key);
+                self.pressed.insert(Trigger::Key(key));
+            }
+            glfw::Action::Release => {
+                self.keys_down.remove(&key);
+            }
+            glfw::Action::Repeat => {}
+        }
+    }
+
+    pub fn on_mouse_button_event(&mut self, button: glfw::MouseButton, action: glfw::Action) {
+        match action {
+            glfw::Action::Press => {
+                self.mouse_buttons_down.insert(button);
+                self.pressed.insert(Trigger::MouseButton(button));
+            }
+            glfw::Action::Release => {
+                self.mouse_buttons_down.remove(&button);
+            }
+            glfw::Action::Repeat => {}
+        }
+    }
+
+    pub fn on_scroll_event(&mut self, x: f32, y: f32) {
+        self.scroll.0 += x;
+        self.scroll.1 += y;
+        let triggers = [(0.0 < y, Trigger::ScrollUp), (y < 0.0, Trigger::ScrollDown), (x < 0.0, Trigger::ScrollLeft), (0.0 < x, Trigger::ScrollRight)];
+        for (scrolled, trigger) in triggers {
+            if scrolled {
+                self.pressed.insert(trigger);
+            }
+        }
+    }
+
+    // 프레임이 끝나면 한 프레임 동안만 유효한 입력을 지운다
+    pub fn end_frame(&mut self) {
+        self.pressed.clear();
+        self.scroll = (0.0, 0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_binding() {
+        let binding = Binding::parse("Ctrl+Shift+S").unwrap();
+        assert!(binding.trigger == Trigger::Key(glfw::Key::S));
+        assert_eq!(binding.modifiers, glfw::Modifiers::Control | glfw::Modifiers::Shift);
+        assert!(Binding::parse("MouseRight").unwrap().trigger == Trigger::MouseButton(glfw::MouseButton::Button2));
+        assert!(Binding::parse("ScrollUp").unwrap().trigger == Trigger::ScrollUp);
+        assert!(Binding::parse("Ctrl+").is_none());
+        assert!(Binding::parse("Ctrl").is_none());
+        assert!(Binding::parse("Hyper+W").is_none());
+    }
+
+    #[test]
+    fn parse_axis_scale() {
+        let input_map = InputMap::parse("input.cfg", "axis zoom = ScrollUp 1, ScrollDown -0.5\naxis move_right = D").unwrap();
+        let zoom = input_map.get_axis_bindings("zoom");
+        assert!(zoom[0].0.trigger == Trigger::ScrollUp && zoom[0].1 == 1.0);
+        assert!(zoom[1].0.trigger == Trigger::ScrollDown && zoom[1].1 == -0.5);
+        assert_eq!(input_map.get_axis_bindings("move_right")[0].1, 1.0);
+        assert!(InputMap::parse("input.cfg", "axis zoom = ScrollUp fast").is_err());
+        assert!(InputMap::parse("input.cfg", "action close = Ctrl+").is_err());
+    }
+
+    #[test]
+    fn prefer_specific_modifiers() {
+        let input_map = InputMap::parse("input.cfg", "action close = Ctrl+W\naxis move_forward = W 1").unwrap();
+
+        // W만 누르면 이동한다
+        let mut w = input_map_with(&input_map, &[glfw::Key::W]);
+        assert_eq!(w.get_axis("move_forward"), 1.0);
+        assert!(!w.is_pressed("close"));
+        w.end_frame();
+        assert!(!w.is_pressed("close"));
+
+        // Ctrl+W는 close만 동작한다
+        let ctrl_w = input_map_with(&input_map, &[glfw::Key::LeftControl, glfw::Key::W]);
+        assert!(ctrl_w.is_pressed("close"));
+        assert_eq!(ctrl_w.get_axis("move_forward"), 0.0);
+
+        // 따로 연결하지 않은 Shift+W는 W로 동작한다
+        let shift_w = input_map_with(&input_map, &[glfw::Key::LeftShift, glfw::Key::W]);
+        assert_eq!(shift_w.get_axis("move_forward"), 1.0);
+        assert!(!shift_w.is_pressed("close"));
+    }
+
+    fn input_map_with(input_map: &InputMap, keys: &[glfw::Key]) -> InputMap {
+        let mut input_map = InputMap { actions: input_map.actions.clone(), axes: input_map.axes.clone(), ..InputMap::new() };
+        for key in keys {
+            input_map.on_key_event(*key, glfw::Action::Press);
+        }
+        input_map
+    }
+}
@@ -0,0 +1,97 @@
+use super::{program::Program, scene::LightItem};
+use nalgebra_glm as glm;
+
+// 셰이더의 lights 배열 크기, 프로그램을 만들 때 MAX_LIGHT_COUNT로 주입한다
+pub const DEFAULT_MAX_LIGHT_COUNT: usize = 8;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LightType {
+    Directional,
+    Point,
+    Spot,
+}
+
+// 위치와 방향은 광원이 붙어 있는 노드의 world 행렬로부터 정해진다, 빛은 노드의 -z 방향으로 나아간다
+#[derive(Clone, Copy)]
+pub struct Light {
+    pub type_: LightType,
+    pub enabled: bool,
+    pub cast_shadow: bool, // directional, spot 광원만 그림자를 만든다
+    pub color: glm::Vec3,
+    pub intensity: f32,
+
+    // point, spot 광원의 거리에 따른 감쇠
+    pub constant: f32,
+    pub linear: f32,
+    pub quadratic: f32,
+
+    // spot 광원의 원뿔 각도(도)
+    pub inner_angle: f32,
+    pub outer_angle: f32,
+}
+
+impl Light {
+    pub fn new(type_: LightType) -> Self {
+        // 감쇠 계수는 약 50 거리까지 닿는 값
+        Self { type_, enabled: true, cast_shadow: false, color: glm::vec3(1.0, 1.0, 1.0), intensity: 1.0, constant: 1.0, linear: 0.09, quadratic: 0.032, inner_angle: 12.5, outer_angle: 17.5 }
+    }
+
+    pub fn set_color(&mut self, color: glm::Vec3, intensity: f32) -> &mut Self {
+        self.color = color;
+        self.intensity = intensity;
+        self
+    }
+
+    pub fn set_cast_shadow(&mut self, cast_shadow: bool) -> &mut Self {
+        self.cast_shadow = cast_shadow;
+        self
+    }
+
+    // 감쇠된 밝기가 1/256 아래로 떨어지는 거리
+    pub fn get_range(&self) -> f32 {
+        let threshold = 256.0 * self.intensity.max(0.0);
+        if 0.0 < self.quadratic {
+            (-self.linear + (self.linear * self.linear - 4.0 * self.quadratic * (self.constant - threshold)).max(0.0).sqrt()) / (2.0 * self.quadratic)
+        } else if 0.0 < self.linear {
+            ((threshold - self.constant) / self.linear).max(0.0)
+        } else {
+            f32::MAX
+        }
+    }
+
+    pub fn set_cone(&mut self, inner_angle: f32, outer_angle: f32) -> &mut Self {
+        self.inner_angle = inner_angle;
+        self.outer_angle = outer_angle.max(inner_angle);
+        self
+    }
+}
+
+// 켜져 있는 광원을 최대 max_light_count개까지 셰이더의 lights 배열로 넘기고, 나머지는 무시한다
+// shadow_indices는 lights와 같은 순서로 각 광원의 그림자 맵 번호를 담고 있다(-1은 그림자 없음)
+pub fn set_uniforms(program: &Program, lights: &[LightItem], shadow_indices: &[i32], max_light_count: usize) {
+    let mut count: i32 = 0;
+    let shadow_index = |index: usize| shadow_indices.get(index).copied().unwrap_or(-1);
+    for (index, item) in lights.iter().enumerate().filter(|(_, item)| item.light.enabled).take(max_light_count) {
+        let light = &item.light;
+        let position = item.get_position();
+        let direction = item.get_direction();
+        let type_ = match light.type_ {
+            LightType::Directional => 0,
+            LightType::Point => 1,
+            LightType::Spot => 2,
+        };
+        let name = |field: &str| format!("lights[{}].{}\0", count, field);
+        program.set_uniform1i(name("type"), type_);
+        program.set_uniform3fv(name("position"), &position);
+        program.set_uniform3fv(name("direction"), &direction);
+        program.set_uniform3fv(name("color"), &(light.color * light.intensity));
+        program.set_uniform1f(name("constant"), light.constant);
+        program.set_uniform1f(name("linear"), light.linear);
+        program.set_uniform1f(name("quadratic"), light.quadratic);
+        program.set_uniform1f(name("inner_cutoff"), light.inner_angle.to_radians().cos());
+        program.set_uniform1f(name("outer_cutoff"), light.outer_angle.to_radians().cos());
+        program.set_uniform1i(name("shadow_index"), shadow_index(index));
+        count += 1;
+    }
+    program.set_uniform1i("light_count\0", count);
+}
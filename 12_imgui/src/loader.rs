@@ -0,0 +1,232 @@
+use super::{errors, image::Image, model::{self, Model}, program::Program, scene, shader::Shader, texture::Texture};
+use std::{cell::{OnceCell, RefCell}, collections::{HashMap, VecDeque}, rc::Rc, sync::{atomic::{AtomicBool, Ordering}, mpsc, Arc, Mutex}, thread, time::{Duration, Instant}};
+
+// 파일 읽기와 디코딩은 로딩 스레드에서 하고, GL 객체는 update에서 렌더 스레드가 만든다
+// load_* 함수는 바로 핸들을 돌려주고, 불러오기가 끝나면 같은 핸들의 내용이 바뀐다
+
+const MAX_WORKER_COUNT: usize = 3;
+const PLACEHOLDER_SIZE: u32 = 8; // 체크 무늬 한 변의 픽셀 수
+
+enum Job {
+    Image(String),
+    Model(String),
+    Program(String, String), // 정점, 프래그먼트 쉐이더 경로
+}
+
+enum Data {
+    Image(Image),
+    Model(model::Source),
+    Program(String, String), // 정점, 프래그먼트 쉐이더 소스
+}
+
+struct Loaded {
+    id: u64,
+    file_path: String,
+    result: Result<Data, errors::Error>,
+}
+
+// 불러오기가 끝나면 내용을 채울 핸들
+enum Target {
+    Texture(Rc<Texture>),
+    Model(Rc<RefCell<scene::Node>>),
+    Program(Rc<OnceCell<Program>>, String, String, Vec<(String, String)>), // 핸들, 정점, 프래그먼트 쉐이더 경로, define
+}
+
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Progress {
+    pub total: usize,
+    pub completed: usize, // 실패한 것도 포함한다
+    pub failed: usize,
+}
+
+impl Progress {
+    pub fn get_fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.completed as f32 / self.total as f32
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.completed == self.total
+    }
+}
+
+pub struct Loader {
+    sender: Option<mpsc::Sender<(u64, Job)>>,
+    receiver: mpsc::Receiver<Loaded>,
+    workers: Vec<thread::JoinHandle<()>>,
+    cancelled: Arc<AtomicBool>, // 켜지면 로딩 스레드가 남은 작업을 실행하지 않고 끝난다
+    next_id: u64,
+    targets: HashMap<u64, Target>,
+    ready: VecDeque<Loaded>, // 로딩 스레드에서 받았지만 아직 GL 객체를 만들지 않은 것
+    placeholder: Image,
+    progress: Progress,
+}
+
+impl Loader {
+    pub fn create() -> Loader {
+        let worker_count = thread::available_parallelism().map(|count| count.get().saturating_sub(1)).unwrap_or(1).clamp(1, MAX_WORKER_COUNT);
+        let (sender, job_receiver) = mpsc::channel::<(u64, Job)>();
+        let (result_sender, receiver) = mpsc::channel();
+        let job_receiver = Arc::new(Mutex::new(job_receiver));
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let mut workers = Vec::new();
+        for index in 0..worker_count {
+            let job_receiver = job_receiver.clone();
+            let result_sender = result_sender.clone();
+            let cancelled = cancelled.clone();
+            let worker = thread::Builder::new().name(format!("loader {}", index)).spawn(move || loop {
+                // sender가 사라지면 recv가 실패하므로 스레드가 끝난다
+                let Ok((id, job)) = job_receiver.lock().unwrap().recv() else {
+                    break;
+                };
+                if cancelled.load(Ordering::Relaxed) {
+                    break;
+                }
+                if result_sender.send(run(id, job)).is_err() {
+                    break;
+                }
+            });
+            match worker {
+                Ok(worker) => workers.push(worker),
+                Err(err) => spdlog::error!("Failed to spawn loader thread\n{}", err),
+            }
+        }
+        spdlog::info!("Created loader ({} threads)", workers.len());
+
+        let placeholder = create_checkerboard();
+        Loader { sender: Some(sender), receiver, workers, cancelled, next_id: 0, targets: HashMap::new(), ready: VecDeque::new(), placeholder, progress: Progress::default() }
+    }
+
+    // 불러오는 동안에는 체크 무늬 텍스쳐를 사용한다
+    pub fn load_texture(&mut self, file_path: &str, wrap: u32) -> Rc<Texture> {
+        let texture = Texture::create();
+        texture.set_label(file_path).set_texture(&self.placeholder).set_filter(gl::NEAREST, gl::NEAREST).set_wrap(wrap, wrap);
+        let texture = Rc::new(texture);
+        self.request(Job::Image(file_path.to_owned()), Target::Texture(texture.clone()));
+        texture
+    }
+
+    // 비어 있는 노드를 돌려주고, 불러오기가 끝나면 모델의 노드들을 자식으로 붙인다
+    pub fn load_model(&mut self, file_path: &str) -> Rc<RefCell<scene::Node>> {
+        let node = scene::Node::create(file_path);
+        self.request(Job::Model(file_path.to_owned()), Target::Model(node.clone()));
+        node
+    }
+
+    // 불러오기가 끝나기 전에는 비어 있으므로 get으로 확인하고 사용한다
+    pub fn load_program(&mut self, vertex_shader_path: &str, fragment_shader_path: &str, defines: &[(&str, String)]) -> Rc<OnceCell<Program>> {
+        let program = Rc::new(OnceCell::new());
+        let defines = defines.iter().map(|(name, value)| (name.to_string(), value.clone())).collect();
+        self.request(Job::Program(vertex_shader_path.to_owned(), fragment_shader_path.to_owned()), Target::Program(program.clone(), vertex_shader_path.to_owned(), fragment_shader_path.to_owned(), defines));
+        program
+    }
+
+    fn request(&mut self, job: Job, target: Target) {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.progress.total += 1;
+        self.targets.insert(id, target);
+        let sent = self.sender.as_ref().is_some_and(|sender| sender.send((id, job)).is_ok());
+        if !sent {
+            // 로딩 스레드를 만들지 못했으면 placeholder를 그대로 사용한다
+            spdlog::error!("Loader has no running threads");
+            self.targets.remove(&id);
+            self.progress.completed += 1;
+            self.progress.failed += 1;
+        }
+    }
+
+    // 매 프레임 렌더 스레드에서 호출한다, budget을 넘기면 남은 것은 다음 프레임에 처리하지만 적어도 하나는 처리한다
+    pub fn update(&mut self, budget: Duration) {
+        let start = Instant::now();
+        self.ready.extend(self.receiver.try_iter());
+        while let Some(loaded) = self.ready.pop_front() {
+            self.finish(loaded);
+            if start.elapsed() >= budget {
+                break;
+            }
+        }
+    }
+
+    fn finish(&mut self, loaded: Loaded) {
+        let Some(target) = self.targets.remove(&loaded.id) else {
+            return;
+        };
+        let result = loaded.result.and_then(|data| match (target, data) {
+            (Target::Texture(texture), Data::Image(image)) => {
+                texture.set_texture(&image).set_filter(gl::LINEAR_MIPMAP_LINEAR, gl::LINEAR);
+                Ok(())
+            }
+            (Target::Model(node), Data::Model(source)) => {
+                Model::build(source)?.add_to(&node);
+                Ok(())
+            }
+            (Target::Program(program, vertex_shader_path, fragment_shader_path, defines), Data::Program(vertex_shader_source, fragment_shader_source)) => {
+                let defines: Vec<(&str, String)> = defines.iter().map(|(name, value)| (name.as_str(), value.clone())).collect();
+                let _ = program.set(Program::load_from_source((&vertex_shader_path, vertex_shader_source), (&fragment_shader_path, fragment_shader_source), &defines)?);
+                Ok(())
+            }
+            _ => Err(errors::Error::LoaderError("loaded data does not match its target".to_owned())),
+        });
+        self.progress.completed += 1;
+        match result {
+            Ok(()) => spdlog::info!("Loaded \"{}\" ({}/{})", loaded.file_path, self.progress.completed, self.progress.total),
+            Err(err) => {
+                self.progress.failed += 1;
+                spdlog::error!("Failed to load \"{}\"\n{:?}", loaded.file_path, err);
+            }
+        }
+    }
+
+    pub fn get_progress(&self) -> Progress {
+        self.progress
+    }
+}
+
+impl Drop for Loader {
+    fn drop(&mut self) {
+        // 남은 작업은 버리고 로딩 스레드가 끝나기를 기다린다, 이미 실행 중인 작업은 끝날 때까지 기다린다
+        self.cancelled.store(true, Ordering::Relaxed);
+        self.sender = None;
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+// 로딩 스레드에서 실행한다, OpenGL을 사용하면 안 된다
+fn run(id: u64, job: Job) -> Loaded {
+    let (file_path, result) = match job {
+        Job::Image(file_path) => {
+            let result = Image::load(&file_path).map(Data::Image);
+            (file_path, result)
+        }
+        Job::Model(file_path) => {
+            let result = Model::read(&file_path).map(Data::Model);
+            (file_path, result)
+        }
+        Job::Program(vertex_shader_path, fragment_shader_path) => {
+            let result = Shader::load_source(&vertex_shader_path).and_then(|vertex_shader_source| Ok(Data::Program(vertex_shader_source, Shader::load_source(&fragment_shader_path)?)));
+            (format!("{} + {}", vertex_shader_path, fragment_shader_path), result)
+        }
+    };
+    Loaded { id, file_path, result }
+}
+
+// 자홍색과 검은색의 체크 무늬
+fn create_checkerboard() -> Image {
+    let mut data = Vec::new();
+    for y in 0..PLACEHOLDER_SIZE {
+        for x in 0..PLACEHOLDER_SIZE {
+            if (x + y) % 2 == 0 {
+                data.extend_from_slice(&[255, 0, 255, 255]);
+            } else {
+                data.extend_from_slice(&[0, 0, 0, 255]);
+            }
+        }
+    }
+    Image::from_raw(PLACEHOLDER_SIZE, PLACEHOLDER_SIZE, 4, data)
+}
@@ -1,5 +1,10 @@
 mod errors;
 mod common;
+mod pack;
+mod asset;
+mod gl_state;
+mod gl_debug;
+mod gl_check;
 mod shader;
 mod program;
 mod context;
@@ -7,162 +12,173 @@ mod vertex_array;
 mod buffer;
 mod texture;
 mod image;
+mod mesh;
+mod material;
+mod model;
+mod primitive;
+mod light;
+mod scene;
+mod framebuffer;
+mod cube_texture;
+mod ibl;
+mod shadow;
+mod frustum;
+mod picking;
+mod gizmo;
+mod debug_draw;
+mod render_queue;
+mod profiler;
+mod resource;
+mod loader;
+mod input;
+mod gamepad;
 mod ui;
 
-use glfw::Context;
+use framework::camera;
 
 const WINDOW_NAME: &'static str = "ImGui";
 const WINDOW_WIDTH: u32 = 1920;
 const WINDOW_HEIGHT: u32 = 1080;
 
-fn main() -> errors::Result<(), errors::Error> {
-    inner_main().into()
+struct Example {
+    context: context::Context,
+    gamepads: gamepad::Gamepads,
+    ui_manager: ui::Manager,
+    time: f32,
+    delta_time: f32,
 }
 
-fn inner_main() -> Result<(), errors::Error> {
-    // Initialize glfw
-    spdlog::info!("Initialize glfw");
-    
-    let mut glfw = glfw::init_no_callbacks()?; // From 트레이트 구현으로 errors::Error 타입으로 변환됨
-
-    glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
-    glfw.window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
-
-    // Create glfw window
-    spdlog::info!("Create glfw window");
-    let (mut window, events) = glfw.create_window(WINDOW_WIDTH, WINDOW_HEIGHT, WINDOW_NAME, glfw::WindowMode::Windowed).ok_or(errors::Error::CreateWindowError)?;
-    window.set_cursor_pos(WINDOW_WIDTH as f64 / 2.0, WINDOW_HEIGHT as f64 / 2.0);
-    // window.set_key_callback(on_key_event);
-    // window.set_framebuffer_size_callback(on_frame_buffer_size_event);
-    window.set_all_polling(true);
-    window.make_current();
-
-    // Initialize glad
-    spdlog::info!("Initialize glad");
-    gl::load_with(|s| window.get_proc_address(s) as *const _);
-    unsafe {
-        let gl_version = common::c_str_to_string(gl::GetString(gl::VERSION).cast());
-        if gl_version.is_none() {
-            spdlog::error!("Failed to initialize glad");
-            panic!();
+impl framework::App for Example {
+    type Error = errors::Error;
+
+    fn init(window: &mut framework::Window) -> Result<Self, errors::Error> {
+        window.get_window_mut().set_cursor_pos(WINDOW_WIDTH as f64 / 2.0, WINDOW_HEIGHT as f64 / 2.0);
+        gl_debug::init(&gl_debug::DEFAULT_IGNORED_IDS);
+        // GL_CHECK=panic|log|ignore로 GL 오류를 만났을 때의 동작을 고른다, 디버그 빌드나 gl_check 기능을 켰을 때만 의미가 있다
+        if let Ok(text) = std::env::var("GL_CHECK") {
+            match gl_check::Policy::parse(&text) {
+                Some(policy) => gl_check::set_policy(policy),
+                None => spdlog::warn!("Unknown GL_CHECK policy \"{}\"", text),
+            }
+        }
+        spdlog::info!("GL error check policy: {:?}", gl_check::get_policy());
+        // PROFILE=off|cpu로 프로파일러를 끄거나 GPU 시간 측정만 끈다
+        match std::env::var("PROFILE").as_deref() {
+            Ok("off") => profiler::set_enabled(false),
+            Ok("cpu") => profiler::set_gpu_enabled(false),
+            Ok(text) => spdlog::warn!("Unknown PROFILE mode \"{}\"", text),
+            Err(_) => {}
+        }
+        gl_state::set_viewport(0, 0, WINDOW_WIDTH as i32, WINDOW_HEIGHT as i32); // State-setting function
+        gl_state::set_blend_func(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
+        gl_state::set_enabled(gl::BLEND, true);
+
+        let mut context = context::Context::create()?;
+        // 고해상도 화면에서는 프레임 버퍼가 창 크기와 다르므로 실제 크기로 맞춘다
+        let (frame_buffer_width, frame_buffer_height) = window.get_frame_buffer_size();
+        gl_state::set_viewport(0, 0, frame_buffer_width, frame_buffer_height);
+        context.on_frame_buffer_size_event(frame_buffer_width, frame_buffer_height);
+        // 첫 번째 인자로 모델 파일(.obj, .gltf, .glb) 경로가 주어지면 모델을 불러온다
+        if let Some(model_path) = std::env::args().nth(1) {
+            context.load_model(model_path);
         }
-        spdlog::info!("Loaded OpenGL {}", gl_version.unwrap());
-        gl::Viewport(0, 0, WINDOW_WIDTH as i32, WINDOW_HEIGHT as i32); // State-setting function
-        gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
-        gl::Enable(gl::BLEND);
+        // 두 번째 인자로 HDR 환경 맵(.hdr) 경로가 주어지면 IBL에 사용한다
+        if let Some(environment_path) = std::env::args().nth(2) {
+            context.load_environment(environment_path)?;
+        }
+
+        let gamepads = gamepad::Gamepads::create(window.get_glfw());
+
+        let mut ui_manager = ui::Manager::create(WINDOW_WIDTH as f32, WINDOW_HEIGHT as f32)?;
+        ui_manager.add_window("window 1")?;
+        ui_manager.add_window("window 2")?;
+        ui_manager.add_window("window 3")?;
+
+        Ok(Example { context, gamepads, ui_manager, time: 0.0, delta_time: 0.0 })
     }
 
-    let mut context = context::Context::create()?;
-
-    let mut ui_manager = ui::Manager::create(WINDOW_WIDTH as f32, WINDOW_HEIGHT as f32)?;
-    let ui_window_1 = ui_manager.add_window("window 1")?;
-    let ui_window_2 = ui_manager.add_window("window 2")?;
-    let ui_window_3 = ui_manager.add_window("window 3")?;
-
-    // Start main loop
-    spdlog::info!("Start main loop");
-    let mut time;
-    let mut prev_time: f32 = 0.0;
-    let mut delta_time;
-    while !window.should_close() {
-        time = glfw.get_time() as f32;
-        delta_time = time - prev_time;
-        prev_time = time;
-
-        glfw.poll_events();
-        for (_, event) in glfw::flush_messages(&events) {
-            match event {
-                glfw::WindowEvent::FramebufferSize(width, height) => {
-                    unsafe {
-                        gl::Viewport(0, 0, width, height);
-                    }
-                    ui_manager.on_frame_buffer_size_event(width as f32, height as f32);
-                    context.on_frame_buffer_size_event(width, height);
-                    on_frame_buffer_size_event(&mut window, width, height);
-                }
-                glfw::WindowEvent::Key(key, scancode, action, modifiers) => {
-                    if action == glfw::Action::Release {
-                        context.on_key_event(key, false);
-                    } else {
-                        context.on_key_event(key, true);
-                    }
-                    on_key_event(&mut window, key, scancode, action, modifiers);
-                }
-                glfw::WindowEvent::CursorPos(x, y) => {
-                    ui_manager.on_cursor_pos_event(x as f32, y as f32);
-                    context.on_cursor_pos_event(x as f32, y as f32);
-                    // on_cursor_pos_event(&mut window, x, y);
-                }
-                glfw::WindowEvent::MouseButton(mouse_button, action, modifiers) => {
-                    if mouse_button == glfw::MouseButtonLeft {
-                        if action == glfw::Action::Release {
-                            ui_manager.on_mouse_down_event(false);
-                        } else {
-                            ui_manager.on_mouse_down_event(true);
-                        }
-                    } else if mouse_button == glfw::MouseButtonRight {
-                        if action == glfw::Action::Release {
-                            context.on_mouse_down_event(false);
-                        } else {
-                            context.on_mouse_down_event(true);
-                        }
-                    }
-                    on_mouse_button_event(&mut window, mouse_button, action, modifiers);
-                }
-                _ => {},
-            }
+    fn update(&mut self, _: &mut framework::Window, time: f32, delta_time: f32) {
+        profiler::begin_frame();
+        self.time = time;
+        self.delta_time = delta_time;
+        self.context.update();
+
+        // 게임패드는 이벤트가 없으므로 매 프레임 상태를 읽는다
+        self.gamepads.update();
+        if let Some(gamepad) = self.gamepads.get_active() {
+            self.context.on_gamepad_update(gamepad, self.gamepads.stick_deadzone, self.gamepads.trigger_deadzone);
         }
+    }
 
+    fn render(&mut self, _: &mut framework::Window) {
         unsafe {
-            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT); // State-using function
+            gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT | gl::STENCIL_BUFFER_BIT); // State-using function
         }
 
-        // context.render(time, delta_time);
-        ui_manager.render();
+        self.context.update_loader();
+        self.context.render(self.time, self.delta_time);
+        profiler::begin("debug_draw");
+        self.context.flush_debug_draw(self.delta_time);
+        profiler::end();
+    }
 
-        window.swap_buffers();
-        // std::thread::sleep(std::time::Duration::from_millis(1));
+    fn ui(&mut self, _: &mut framework::Window) {
+        profiler::begin("ui");
+        self.ui_manager.render();
+        profiler::end();
+        self.context.end_frame();
+        profiler::end_frame();
     }
 
-    Ok(())
-}
+    fn on_event(&mut self, window: &mut framework::Window, event: &glfw::WindowEvent) {
+        match *event {
+            glfw::WindowEvent::Key(key, _, action, _) => {
+                self.context.on_key_event(key, action);
+            }
+            glfw::WindowEvent::CursorPos(x, y) => {
+                self.ui_manager.on_cursor_pos_event(x as f32, y as f32);
+                // 피킹 광선은 프레임 버퍼의 픽셀 좌표로 계산하므로 커서 좌표를 맞춘다
+                let (frame_buffer_width, frame_buffer_height) = window.get_frame_buffer_size();
+                let (window_width, window_height) = window.get_window().get_size();
+                let scale_x = frame_buffer_width as f32 / window_width.max(1) as f32;
+                let scale_y = frame_buffer_height as f32 / window_height.max(1) as f32;
+                self.context.on_cursor_pos_event(x as f32 * scale_x, y as f32 * scale_y);
+            }
+            glfw::WindowEvent::MouseButton(mouse_button, action, _) => {
+                // UI 창을 누른 클릭은 장면의 물체를 선택하지 않는다, 떼는 이벤트는 눌린 상태가 남지 않도록 항상 넘긴다
+                let on_window = self.ui_manager.is_cursor_on_window();
+                if mouse_button == glfw::MouseButtonLeft {
+                    self.ui_manager.on_mouse_down_event(action != glfw::Action::Release);
+                }
+                if !on_window || action == glfw::Action::Release {
+                    self.context.on_mouse_button_event(mouse_button, action);
+                }
+            }
+            glfw::WindowEvent::Scroll(x, y) => {
+                self.context.on_scroll_event(x as f32, y as f32);
+            }
+            _ => {},
+        }
+    }
 
-fn on_frame_buffer_size_event(_: &mut glfw::Window, width: i32, height: i32) {
-    spdlog::info!("FramebufferSize changed: {} x {}", width, height);
-}
+    fn on_resize(&mut self, _: &mut framework::Window, width: i32, height: i32) {
+        gl_state::set_viewport(0, 0, width, height); // Runner가 gl::Viewport로 바꾼 값을 gl_state에도 기록한다
+        self.ui_manager.on_frame_buffer_size_event(width as f32, height as f32);
+        self.context.on_frame_buffer_size_event(width, height);
+    }
 
-fn on_key_event(window: &mut glfw::Window, key: glfw::Key, scancode: glfw::Scancode, action: glfw::Action, modifiers: glfw::Modifiers) {
-    spdlog::info!("key: {}, scancode: {}, action: {}, mods: {}{}{}",
-        key as usize, scancode,
-        match action {
-            glfw::Action::Press => "Press",
-            glfw::Action::Release => "Release",
-            glfw::Action::Repeat => "Repeat",
-        },
-        if modifiers.contains(glfw::Modifiers::Control) { "C" } else { "-" },
-        if modifiers.contains(glfw::Modifiers::Shift) { "S" } else { "-" },
-        if modifiers.contains(glfw::Modifiers::Alt) { "A" } else { "-" },
-    );
-
-    if (key == glfw::Key::Escape) && (action == glfw::Action::Press) {
-        window.set_should_close(true);
+    fn shutdown(&mut self, _: &mut framework::Window) {
+        profiler::destroy();
     }
 }
 
-fn on_cursor_pos_event(_: &mut glfw::Window, x: f64, y: f64) {
-    spdlog::info!("CursurPosition changed: {} x {}", x, y);
+fn main() -> errors::Result<(), errors::Error> {
+    // 쉐이더와 리소스를 찾을 위치
+    asset::mount_defaults();
+
+    framework::Runner::new(WINDOW_NAME)
+        .set_size(WINDOW_WIDTH, WINDOW_HEIGHT)
+        .set_debug_context(cfg!(debug_assertions)) // 디버그 빌드에서만 드라이버가 자세한 메시지를 보내도록 한다
+        .run::<Example>()
+        .into()
 }
-
-fn on_mouse_button_event(_: &mut glfw::Window, mouse_button: glfw::MouseButton, action: glfw::Action, modifiers: glfw::Modifiers) {
-    spdlog::info!("mouse: {}, action: {}, mods: {}{}{}",
-    mouse_button as usize,
-    match action {
-        glfw::Action::Press => "Press",
-        glfw::Action::Release => "Release",
-        glfw::Action::Repeat => "Repeat",
-    },
-    if modifiers.contains(glfw::Modifiers::Control) { "C" } else { "-" },
-    if modifiers.contains(glfw::Modifiers::Shift) { "S" } else { "-" },
-    if modifiers.contains(glfw::Modifiers::Alt) { "A" } else { "-" },
-    );
-}
\ No newline at end of file
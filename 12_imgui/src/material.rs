@@ -0,0 +1,94 @@
+use super::{gl_state, program::Program, texture};
+use nalgebra_glm as glm;
+use std::rc::Rc;
+
+pub struct Material {
+    pub name: String,
+    pub ambient: glm::Vec3,
+    pub diffuse: glm::Vec3,
+    pub specular: glm::Vec3,
+    pub shininess: f32,
+    pub opacity: f32,
+    pub diffuse_texture: Option<Rc<texture::Texture>>,
+
+    // metallic-roughness
+    pub base_color: glm::Vec4,
+    pub metallic: f32,
+    pub roughness: f32,
+    pub emissive: glm::Vec3,
+    pub normal_scale: f32,
+    pub occlusion_strength: f32,
+    pub base_color_texture: Option<Rc<texture::Texture>>,
+    pub metallic_roughness_texture: Option<Rc<texture::Texture>>,
+    pub normal_texture: Option<Rc<texture::Texture>>,
+    pub occlusion_texture: Option<Rc<texture::Texture>>,
+    pub emissive_texture: Option<Rc<texture::Texture>>,
+}
+
+impl Material {
+    pub fn new(name: &str) -> Self {
+        let name = name.to_owned();
+        let ambient = glm::vec3(0.2, 0.2, 0.2);
+        let diffuse = glm::vec3(0.8, 0.8, 0.8);
+        let specular = glm::vec3(0.0, 0.0, 0.0);
+        let shininess = 32.0;
+        let opacity = 1.0;
+        let diffuse_texture = None;
+
+        // metallic-roughness
+        let base_color = glm::vec4(1.0, 1.0, 1.0, 1.0);
+        let metallic = 0.0;
+        let roughness = 1.0;
+        let emissive = glm::vec3(0.0, 0.0, 0.0);
+        let normal_scale = 1.0;
+        let occlusion_strength = 1.0;
+
+        Self { name, ambient, diffuse, specular, shininess, opacity, diffuse_texture, base_color, metallic, roughness, emissive, normal_scale, occlusion_strength,
+            base_color_texture: None, metallic_roughness_texture: None, normal_texture: None, occlusion_texture: None, emissive_texture: None }
+    }
+
+    // 블렌딩이 필요한 재질은 불투명한 물체를 모두 그린 뒤에 뒤에서부터 그린다
+    pub fn is_transparent(&self) -> bool {
+        self.opacity < 1.0 || self.base_color.w < 1.0
+    }
+
+    // Blinn-Phong 셰이더의 material 구조체에 값을 넘기고, diffuse 텍스쳐는 0번에 바인딩한다
+    pub fn set_uniforms(&self, program: &Program) {
+        program.set_uniform3fv("material.ambient\0", &self.ambient);
+        program.set_uniform3fv("material.diffuse\0", &self.diffuse);
+        program.set_uniform3fv("material.specular\0", &self.specular);
+        program.set_uniform1f("material.shininess\0", self.shininess);
+        program.set_uniform1f("material.opacity\0", self.opacity);
+        program.set_uniform1i("material.has_diffuse_texture\0", self.diffuse_texture.is_some() as i32);
+        program.set_uniform1i("material.diffuse_texture\0", 0);
+        if let Some(texture) = &self.diffuse_texture {
+            gl_state::active_texture(0);
+            texture.bind();
+        }
+    }
+
+    // PBR 셰이더의 material 구조체에 값을 넘기고, 텍스쳐는 0 ~ 4번에 바인딩한다
+    pub fn set_pbr_uniforms(&self, program: &Program) {
+        program.set_uniform4fv("material.base_color\0", &self.base_color);
+        program.set_uniform1f("material.metallic\0", self.metallic);
+        program.set_uniform1f("material.roughness\0", self.roughness);
+        program.set_uniform3fv("material.emissive\0", &self.emissive);
+        program.set_uniform1f("material.normal_scale\0", self.normal_scale);
+        program.set_uniform1f("material.occlusion_strength\0", self.occlusion_strength);
+        let textures = [
+            ("base_color_texture", &self.base_color_texture),
+            ("metallic_roughness_texture", &self.metallic_roughness_texture),
+            ("normal_texture", &self.normal_texture),
+            ("occlusion_texture", &self.occlusion_texture),
+            ("emissive_texture", &self.emissive_texture),
+        ];
+        for (unit, (name, texture)) in textures.iter().enumerate() {
+            program.set_uniform1i(format!("material.has_{}\0", name), texture.is_some() as i32);
+            program.set_uniform1i(format!("material.{}\0", name), unit as i32);
+            if let Some(texture) = texture {
+                gl_state::active_texture(unit as u32);
+                texture.bind();
+            }
+        }
+    }
+}
@@ -0,0 +1,374 @@
+use super::{buffer, gl_check, vertex_array};
+use nalgebra_glm as glm;
+
+pub struct Mesh {
+    vao: vertex_array::VertexArray,
+    vbo: buffer::Buffer,
+    ebo: Option<buffer::Buffer>,
+    primitive_type: u32,
+    vertex_count: i32,
+    index_count: i32,
+    index_type: u32,
+    bounding_box: BoundingBox,
+    bounding_sphere: BoundingSphere,
+
+    // 마우스 피킹처럼 CPU에서 삼각형을 검사할 때 사용하는 로컬 좌표계의 사본
+    positions: Vec<glm::Vec3>,
+    indices: Vec<u32>,
+}
+
+impl Mesh {
+    // `attribute_sizes`는 정점 하나를 구성하는 속성들의 성분 개수로, 0번 속성은 항상 위치(position)여야 한다
+    pub fn create<I>(vertices: &[f32], attribute_sizes: &[i32], indices: &[I], primitive_type: u32) -> Mesh where I: Index {
+        let mut mesh = Self::create_non_indexed(vertices, attribute_sizes, primitive_type);
+        // vao가 바인딩 된 상태에서 ebo를 생성해야 vao에 ebo가 저장된다
+        mesh.vao.bind();
+        let ebo = buffer::Buffer::create(gl::ELEMENT_ARRAY_BUFFER, size_of_val(indices).cast_signed(), indices.as_ptr().cast(), gl::STATIC_DRAW);
+        mesh.ebo = Some(ebo);
+        mesh.index_count = indices.len() as i32;
+        mesh.index_type = I::GL_TYPE;
+        mesh.indices = indices.iter().map(|index| index.to_u32()).collect();
+        mesh
+    }
+
+    pub fn create_non_indexed(vertices: &[f32], attribute_sizes: &[i32], primitive_type: u32) -> Mesh {
+        let stride = attribute_sizes.iter().sum::<i32>();
+
+        let vao = vertex_array::VertexArray::create();
+        vao.bind();
+        let vbo = buffer::Buffer::create(gl::ARRAY_BUFFER, size_of_val(vertices).cast_signed(), vertices.as_ptr().cast(), gl::STATIC_DRAW);
+        let mut offset = 0;
+        for (index, size) in attribute_sizes.iter().enumerate() {
+            vao.set(index as u32, *size, gl::FLOAT, gl::FALSE, size_of::<f32>() as i32 * stride, (size_of::<f32>() * offset) as *const _);
+            offset += *size as usize;
+        }
+
+        let vertex_count = if stride == 0 { 0 } else { vertices.len() as i32 / stride };
+        let position_size = attribute_sizes.first().copied().unwrap_or(0) as usize;
+        let bounding_box = BoundingBox::from_vertices(vertices, stride as usize, position_size);
+        let bounding_sphere = BoundingSphere::from_vertices(vertices, stride as usize, position_size, bounding_box.get_center());
+        let positions = if stride == 0 { Vec::new() } else {
+            vertices.chunks_exact(stride as usize).map(|vertex| {
+                let mut position = glm::vec3(0.0, 0.0, 0.0);
+                for axis in 0..position_size.min(3) {
+                    position[axis] = vertex[axis];
+                }
+                position
+            }).collect()
+        };
+
+        Mesh { vao, vbo, ebo: None, primitive_type, vertex_count, index_count: 0, index_type: gl::UNSIGNED_INT, bounding_box, bounding_sphere, positions, indices: Vec::new() }
+    }
+
+    // 속성 0번: position, 속성 1번: normal, 속성 2번: texture coordinate, 속성 3번: tangent
+    pub fn from_vertices(vertices: &[Vertex], indices: &[u32], primitive_type: u32) -> Mesh {
+        let mut data = Vec::with_capacity(vertices.len() * 12);
+        for vertex in vertices {
+            data.extend_from_slice(vertex.position.as_slice());
+            data.extend_from_slice(vertex.normal.as_slice());
+            data.extend_from_slice(vertex.texture_coord.as_slice());
+            data.extend_from_slice(vertex.tangent.as_slice());
+        }
+        if indices.is_empty() {
+            Self::create_non_indexed(&data, &[3, 3, 2, 4], primitive_type)
+        } else {
+            Self::create(&data, &[3, 3, 2, 4], indices, primitive_type)
+        }
+    }
+
+    // vao, vbo, ebo에 label 뒤에 종류를 붙인 이름을 단다
+    pub fn set_label(&self, label: &str) {
+        self.vao.set_label(&format!("{} vao", label));
+        self.vbo.set_label(&format!("{} vbo", label));
+        if let Some(ebo) = &self.ebo {
+            ebo.set_label(&format!("{} ebo", label));
+        }
+    }
+
+    // GPU 버퍼와 피킹용 CPU 사본을 합친 바이트 수
+    pub fn get_memory_size(&self) -> usize {
+        let buffer_size = self.vbo.get_size() + self.ebo.as_ref().map(|ebo| ebo.get_size()).unwrap_or(0);
+        buffer_size as usize + size_of_val(self.positions.as_slice()) + size_of_val(self.indices.as_slice())
+    }
+
+    pub fn get_vertex_array(&self) -> &vertex_array::VertexArray {
+        &self.vao
+    }
+
+    pub fn get_bounding_box(&self) -> &BoundingBox {
+        &self.bounding_box
+    }
+
+    pub fn get_bounding_sphere(&self) -> &BoundingSphere {
+        &self.bounding_sphere
+    }
+
+    // 로컬 좌표계의 광선과 가장 가까운 삼각형의 교차 거리, 삼각형 목록이 아니면 검사하지 않는다
+    pub fn intersect_ray(&self, origin: &glm::Vec3, direction: &glm::Vec3) -> Option<f32> {
+        if self.primitive_type != gl::TRIANGLES {
+            return None;
+        }
+        let triangle = |index: usize| if self.indices.is_empty() { index } else { self.indices[index] as usize };
+        let count = if self.indices.is_empty() { self.positions.len() } else { self.indices.len() };
+        (0..count / 3)
+            .filter_map(|face| intersect_triangle(origin, direction, &self.positions[triangle(face * 3)], &self.positions[triangle(face * 3 + 1)], &self.positions[triangle(face * 3 + 2)]))
+            .min_by(f32::total_cmp)
+    }
+
+    pub fn bind(&self) {
+        self.vao.bind();
+    }
+
+    pub fn draw(&self) {
+        self.bind();
+        self.draw_bound();
+    }
+
+    // vao가 이미 바인딩 되어 있을 때 그리기 명령만 보낸다
+    #[cfg_attr(any(debug_assertions, feature = "gl_check"), track_caller)]
+    pub fn draw_bound(&self) {
+        unsafe {
+            if self.ebo.is_some() {
+                gl::DrawElements(self.primitive_type, self.index_count, self.index_type, std::ptr::null());
+            } else {
+                gl::DrawArrays(self.primitive_type, 0, self.vertex_count);
+            }
+        }
+        gl_check::check("Mesh::draw_bound");
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Vertex {
+    pub position: glm::Vec3,
+    pub normal: glm::Vec3,
+    pub texture_coord: glm::Vec2,
+    pub tangent: glm::Vec4, // w는 bitangent의 방향(handedness)
+}
+
+impl Vertex {
+    pub fn new() -> Self {
+        Self { position: glm::vec3(0.0, 0.0, 0.0), normal: glm::vec3(0.0, 0.0, 0.0), texture_coord: glm::vec2(0.0, 0.0), tangent: glm::vec4(1.0, 0.0, 0.0, 1.0) }
+    }
+}
+
+// 삼각형 목록의 텍스쳐 좌표로부터 정점별 tangent를 계산한다
+pub fn generate_tangents(vertices: &mut [Vertex], indices: &[u32]) {
+    let mut tangents = vec![glm::vec3(0.0, 0.0, 0.0); vertices.len()];
+    let mut bitangents = vec![glm::vec3(0.0, 0.0, 0.0); vertices.len()];
+    for triangle in indices.chunks_exact(3) {
+        let [i0, i1, i2] = [triangle[0] as usize, triangle[1] as usize, triangle[2] as usize];
+        let edge1 = vertices[i1].position - vertices[i0].position;
+        let edge2 = vertices[i2].position - vertices[i0].position;
+        let delta_uv1 = vertices[i1].texture_coord - vertices[i0].texture_coord;
+        let delta_uv2 = vertices[i2].texture_coord - vertices[i0].texture_coord;
+        let determinant = delta_uv1.x * delta_uv2.y - delta_uv2.x * delta_uv1.y;
+        if determinant.abs() < f32::EPSILON {
+            continue;
+        }
+        let r = 1.0 / determinant;
+        let tangent = (edge1 * delta_uv2.y - edge2 * delta_uv1.y) * r;
+        let bitangent = (edge2 * delta_uv1.x - edge1 * delta_uv2.x) * r;
+        for index in [i0, i1, i2] {
+            tangents[index] += tangent;
+            bitangents[index] += bitangent;
+        }
+    }
+    for (index, vertex) in vertices.iter_mut().enumerate() {
+        let normal = vertex.normal;
+        // Gram-Schmidt 과정으로 normal과 직교하도록 보정한다
+        let mut tangent = tangents[index] - normal * glm::dot(&normal, &tangents[index]);
+        if glm::length(&tangent) < f32::EPSILON {
+            // 텍스쳐 좌표가 없으면 normal과 직교하는 임의의 축을 사용한다
+            let axis = if normal.x.abs() < 0.9 { glm::vec3(1.0, 0.0, 0.0) } else { glm::vec3(0.0, 1.0, 0.0) };
+            tangent = glm::cross(&glm::cross(&normal, &axis), &normal);
+        }
+        let tangent = glm::normalize(&tangent);
+        let handedness = if glm::dot(&glm::cross(&normal, &tangent), &bitangents[index]) < 0.0 { -1.0 } else { 1.0 };
+        vertex.tangent = glm::vec4(tangent.x, tangent.y, tangent.z, handedness);
+    }
+}
+
+// Möller-Trumbore 알고리즘, 양면 모두 검사한다
+fn intersect_triangle(origin: &glm::Vec3, direction: &glm::Vec3, v0: &glm::Vec3, v1: &glm::Vec3, v2: &glm::Vec3) -> Option<f32> {
+    let edge1 = v1 - v0;
+    let edge2 = v2 - v0;
+    let p = glm::cross(direction, &edge2);
+    let determinant = glm::dot(&edge1, &p);
+    if determinant.abs() < f32::EPSILON {
+        return None; // 광선이 삼각형과 평행하다
+    }
+    let inverse = 1.0 / determinant;
+    let s = origin - v0;
+    let u = glm::dot(&s, &p) * inverse;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = glm::cross(&s, &edge1);
+    let v = glm::dot(direction, &q) * inverse;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let t = glm::dot(&edge2, &q) * inverse;
+    if t < 0.0 { None } else { Some(t) }
+}
+
+// ebo에 저장할 수 있는 인덱스 타입
+pub trait Index: Copy {
+    const GL_TYPE: u32;
+    fn to_u32(self) -> u32;
+}
+
+impl Index for u8 {
+    const GL_TYPE: u32 = gl::UNSIGNED_BYTE;
+    fn to_u32(self) -> u32 {
+        self as u32
+    }
+}
+
+impl Index for u16 {
+    const GL_TYPE: u32 = gl::UNSIGNED_SHORT;
+    fn to_u32(self) -> u32 {
+        self as u32
+    }
+}
+
+impl Index for u32 {
+    const GL_TYPE: u32 = gl::UNSIGNED_INT;
+    fn to_u32(self) -> u32 {
+        self
+    }
+}
+
+// 로컬 좌표계 기준의 축 정렬 경계 상자
+#[derive(Clone, Copy)]
+pub struct BoundingBox {
+    pub min: glm::Vec3,
+    pub max: glm::Vec3,
+}
+
+impl BoundingBox {
+    pub fn new() -> Self {
+        Self { min: glm::vec3(0.0, 0.0, 0.0), max: glm::vec3(0.0, 0.0, 0.0) }
+    }
+
+    pub fn from_vertices(vertices: &[f32], stride: usize, position_size: usize) -> Self {
+        if stride == 0 || position_size == 0 || vertices.len() < stride {
+            return Self::new();
+        }
+        let mut min = glm::vec3(f32::MAX, f32::MAX, f32::MAX);
+        let mut max = glm::vec3(f32::MIN, f32::MIN, f32::MIN);
+        for vertex in vertices.chunks_exact(stride) {
+            for axis in 0..position_size.min(3) {
+                min[axis] = min[axis].min(vertex[axis]);
+                max[axis] = max[axis].max(vertex[axis]);
+            }
+        }
+        // 위치 성분이 3개보다 적으면 나머지 축은 0으로 둔다
+        for axis in position_size.min(3)..3 {
+            min[axis] = 0.0;
+            max[axis] = 0.0;
+        }
+        Self { min, max }
+    }
+
+    pub fn get_center(&self) -> glm::Vec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn get_size(&self) -> glm::Vec3 {
+        self.max - self.min
+    }
+
+    // slab 방법으로 광선이 상자에 들어가는 거리를 구한다, 광선이 상자 안에서 시작하면 0
+    pub fn intersect_ray(&self, origin: &glm::Vec3, direction: &glm::Vec3) -> Option<f32> {
+        let mut near = 0.0f32;
+        let mut far = f32::MAX;
+        for axis in 0..3 {
+            if direction[axis].abs() < f32::EPSILON {
+                if origin[axis] < self.min[axis] || self.max[axis] < origin[axis] {
+                    return None;
+                }
+                continue;
+            }
+            let t0 = (self.min[axis] - origin[axis]) / direction[axis];
+            let t1 = (self.max[axis] - origin[axis]) / direction[axis];
+            near = near.max(t0.min(t1));
+            far = far.min(t0.max(t1));
+            if far < near {
+                return None;
+            }
+        }
+        Some(near)
+    }
+
+    // 변환된 상자를 다시 감싸는 축 정렬 상자, 중심은 그대로 옮기고 반 크기는 행렬 성분의 절댓값으로 늘린다
+    pub fn transform(&self, matrix: &glm::Mat4) -> Self {
+        let center = (matrix * glm::vec4(self.get_center().x, self.get_center().y, self.get_center().z, 1.0)).xyz();
+        let half_size = glm::abs(&glm::mat4_to_mat3(matrix)) * (self.get_size() * 0.5);
+        Self { min: center - half_size, max: center + half_size }
+    }
+}
+
+// 로컬 좌표계 기준의 경계 구, 회전해도 모양이 변하지 않아 검사가 빠르다
+#[derive(Clone, Copy)]
+pub struct BoundingSphere {
+    pub center: glm::Vec3,
+    pub radius: f32,
+}
+
+impl BoundingSphere {
+    pub fn new() -> Self {
+        Self { center: glm::vec3(0.0, 0.0, 0.0), radius: 0.0 }
+    }
+
+    // 중심은 경계 상자의 중심으로 정하고, 가장 먼 정점까지의 거리를 반지름으로 한다
+    pub fn from_vertices(vertices: &[f32], stride: usize, position_size: usize, center: glm::Vec3) -> Self {
+        if stride == 0 || position_size == 0 {
+            return Self::new();
+        }
+        let mut radius: f32 = 0.0;
+        for vertex in vertices.chunks_exact(stride) {
+            let mut position = glm::vec3(0.0, 0.0, 0.0);
+            for axis in 0..position_size.min(3) {
+                position[axis] = vertex[axis];
+            }
+            radius = radius.max(glm::distance(&position, &center));
+        }
+        Self { center, radius }
+    }
+
+    // 비균등 확대는 가장 큰 축의 배율로 반지름을 늘린다
+    pub fn transform(&self, matrix: &glm::Mat4) -> Self {
+        let center = (matrix * glm::vec4(self.center.x, self.center.y, self.center.z, 1.0)).xyz();
+        let scale = (0..3).map(|axis| glm::length(&matrix.column(axis).xyz())).fold(0.0f32, f32::max);
+        Self { center, radius: self.radius * scale }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangle() -> [glm::Vec3; 3] {
+        [glm::vec3(-1.0, -1.0, 0.0), glm::vec3(1.0, -1.0, 0.0), glm::vec3(0.0, 1.0, 0.0)]
+    }
+
+    #[test]
+    fn ray_hits_triangle() {
+        let [v0, v1, v2] = triangle();
+        let distance = intersect_triangle(&glm::vec3(0.0, 0.0, 2.0), &glm::vec3(0.0, 0.0, -1.0), &v0, &v1, &v2);
+        assert!(distance.is_some_and(|distance| (distance - 2.0).abs() < 1e-5));
+    }
+
+    #[test]
+    fn ray_misses_triangle() {
+        let [v0, v1, v2] = triangle();
+        // 삼각형 바깥을 지나는 광선
+        assert!(intersect_triangle(&glm::vec3(2.0, 2.0, 2.0), &glm::vec3(0.0, 0.0, -1.0), &v0, &v1, &v2).is_none());
+        // 평면과 나란한 광선
+        assert!(intersect_triangle(&glm::vec3(0.0, 0.0, 2.0), &glm::vec3(1.0, 0.0, 0.0), &v0, &v1, &v2).is_none());
+        // 뒤쪽에 있는 삼각형
+        assert!(intersect_triangle(&glm::vec3(0.0, 0.0, 2.0), &glm::vec3(0.0, 0.0, 1.0), &v0, &v1, &v2).is_none());
+    }
+}
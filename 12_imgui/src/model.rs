@@ -0,0 +1,97 @@
+pub mod obj;
+pub mod gltf;
+
+use crate::{errors, material::Material, mesh::Mesh, scene};
+
+use nalgebra_glm as glm;
+use std::{cell::RefCell, rc::Rc};
+
+pub struct Model {
+    pub meshes: Vec<SubMesh>,
+    pub materials: Vec<Rc<Material>>,
+    pub nodes: Vec<Node>,
+    pub root_nodes: Vec<usize>,
+}
+
+// 하나의 재질을 사용하는 메시 단위
+pub struct SubMesh {
+    pub mesh: Rc<Mesh>,
+    pub material_index: Option<usize>,
+}
+
+pub struct Node {
+    pub name: String,
+    pub translation: glm::Vec3,
+    pub rotation: glm::Quat,
+    pub scale: glm::Vec3,
+    pub children: Vec<usize>,
+    pub meshes: Vec<usize>,
+}
+
+impl Node {
+    pub fn new(name: &str) -> Self {
+        let name = name.to_owned();
+        let translation = glm::vec3(0.0, 0.0, 0.0);
+        let rotation = glm::quat_identity();
+        let scale = glm::vec3(1.0, 1.0, 1.0);
+        let children = Vec::new();
+        let meshes = Vec::new();
+
+        Self { name, translation, rotation, scale, children, meshes }
+    }
+}
+
+// 다른 스레드에서 읽은 모델 파일, build로 GL 객체를 만든다
+pub enum Source {
+    Obj(obj::Source),
+    Gltf(Box<gltf::Source>), // Document가 커서 Box에 담는다
+}
+
+impl Model {
+    // 파일 읽기와 디코딩만 한다, OpenGL을 사용하지 않으므로 로딩 스레드에서 호출할 수 있다
+    pub fn read<S>(file_path: S) -> Result<Source, errors::Error> where S: AsRef<str> {
+        let file_path = file_path.as_ref();
+        let extension = std::path::Path::new(file_path).extension().map(|extension| extension.to_string_lossy().to_lowercase());
+        match extension.as_deref() {
+            Some("gltf") | Some("glb") => Ok(Source::Gltf(Box::new(gltf::read(file_path)?))),
+            _ => Ok(Source::Obj(obj::read(file_path)?)),
+        }
+    }
+
+    pub fn build(source: Source) -> Result<Model, errors::Error> {
+        match source {
+            Source::Obj(source) => obj::build(source),
+            Source::Gltf(source) => gltf::build(*source),
+        }
+    }
+
+    // 모델의 최상위 노드들을 parent의 자식으로 붙인다
+    pub fn add_to(&self, parent: &Rc<RefCell<scene::Node>>) {
+        for root_node in &self.root_nodes {
+            scene::Node::add_child(parent, self.create_scene_node(*root_node));
+        }
+    }
+
+    fn create_scene_node(&self, index: usize) -> Rc<RefCell<scene::Node>> {
+        let node = &self.nodes[index];
+        let scene_node = scene::Node::create(&node.name);
+        scene_node.borrow_mut().set_translation(node.translation).set_rotation(node.rotation).set_scale(node.scale);
+        for (number, mesh_index) in node.meshes.iter().enumerate() {
+            let sub_mesh = &self.meshes[*mesh_index];
+            let target = if node.meshes.len() == 1 {
+                scene_node.clone()
+            } else {
+                scene::Node::add_child(&scene_node, scene::Node::create(&format!("{} #{}", node.name, number)))
+            };
+            target.borrow_mut().set_mesh(sub_mesh.mesh.clone());
+            if let Some(material_index) = sub_mesh.material_index {
+                target.borrow_mut().set_material(self.materials[material_index].clone());
+            }
+        }
+        for child in &node.children {
+            scene::Node::add_child(&scene_node, self.create_scene_node(*child));
+        }
+        scene_node
+    }
+
+}
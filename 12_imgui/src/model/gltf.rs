@@ -0,0 +1,250 @@
+use crate::{errors, asset, image::Image, material::Material, mesh::{self, Mesh, Vertex}, texture::Texture};
+use crate::model::{Model, Node, SubMesh};
+
+use nalgebra_glm as glm;
+use std::{path::Path, rc::Rc};
+
+// 지원하는 확장 기능이 없으므로, 필수(required) 확장 기능이 있는 파일은 불러올 수 없다
+const SUPPORTED_EXTENSIONS: &[&str] = &[];
+
+// 파일을 읽고 버퍼와 이미지를 디코딩한 결과, GL 객체가 없으므로 다른 스레드에서 만들 수 있다
+pub struct Source {
+    file_path: String,
+    document: ::gltf::Document,
+    buffers: Vec<::gltf::buffer::Data>,
+    images: Vec<::gltf::image::Data>,
+}
+
+pub fn read<S>(file_path: S) -> Result<Source, errors::Error> where S: AsRef<str> {
+    let file_path = file_path.as_ref();
+    let data = asset::read(file_path)?;
+
+    // 검증 단계에서 실패하기 전에 지원하지 않는 확장 기능을 먼저 확인한다
+    let unvalidated = ::gltf::Gltf::from_slice_without_validation(&data)?;
+    let unsupported: Vec<String> = unvalidated.extensions_required().filter(|name| !SUPPORTED_EXTENSIONS.contains(name)).map(|name| name.to_owned()).collect();
+    if !unsupported.is_empty() {
+        return Err(errors::Error::UnsupportedExtensionError(file_path.to_owned(), unsupported));
+    }
+    for name in unvalidated.extensions_used().filter(|name| !SUPPORTED_EXTENSIONS.contains(name)) {
+        spdlog::warn!("{}: Ignored unsupported extension \"{}\"", file_path, name);
+    }
+
+    // .gltf의 외부 파일, base64로 포함된 데이터, .glb의 바이너리 청크를 모두 읽는다
+    // 외부 파일은 gltf 파일이 디스크에 있을 때만 같은 디렉토리에서 찾을 수 있다, 팩이나 실행 파일 안의 모델은 .glb를 사용한다
+    let ::gltf::Gltf { document, blob } = ::gltf::Gltf::from_slice(&data)?;
+    let resolved_path = asset::resolve(file_path);
+    let base_path = resolved_path.as_deref().and_then(Path::parent);
+    let buffers = ::gltf::import_buffers(&document, base_path, blob)?;
+    let images = ::gltf::import_images(&document, base_path, &buffers)?;
+    Ok(Source { file_path: file_path.to_owned(), document, buffers, images })
+}
+
+// 메시와 텍스쳐를 만들어서 GPU에 올린다, OpenGL 컨텍스트의 스레드에서 호출한다
+pub fn build(source: Source) -> Result<Model, errors::Error> {
+    let Source { file_path, document, buffers, images } = source;
+    let file_path = file_path.as_str();
+
+    let textures = document.textures().map(|texture| create_texture(&texture, &images[texture.source().index()])).collect::<Vec<_>>();
+    let materials = document.materials().map(|material| Rc::new(create_material(&material, &textures))).collect::<Vec<_>>();
+
+    // 하나의 glTF 메시는 여러 개의 primitive로 이루어지며, primitive 하나가 SubMesh 하나가 된다
+    let mut meshes = Vec::new();
+    let mut mesh_primitives = Vec::new();
+    for gltf_mesh in document.meshes() {
+        let mut primitives = Vec::new();
+        for primitive in gltf_mesh.primitives() {
+            if let Some(sub_mesh) = create_sub_mesh(file_path, &primitive, &buffers)? {
+                sub_mesh.mesh.set_label(&format!("{} {}[{}]", file_path, gltf_mesh.name().unwrap_or("mesh"), primitive.index()));
+                primitives.push(meshes.len());
+                meshes.push(sub_mesh);
+            }
+        }
+        mesh_primitives.push(primitives);
+    }
+
+    let mut nodes = Vec::new();
+    for gltf_node in document.nodes() {
+        let mut node = Node::new(gltf_node.name().unwrap_or(""));
+        let (translation, rotation, scale) = gltf_node.transform().decomposed();
+        node.translation = glm::make_vec3(&translation);
+        node.rotation = glm::quat(rotation[0], rotation[1], rotation[2], rotation[3]);
+        node.scale = glm::make_vec3(&scale);
+        node.children = gltf_node.children().map(|child| child.index()).collect();
+        if let Some(gltf_mesh) = gltf_node.mesh() {
+            node.meshes = mesh_primitives[gltf_mesh.index()].clone();
+        }
+        nodes.push(node);
+    }
+
+    let root_nodes = match document.default_scene().or_else(|| document.scenes().next()) {
+        Some(scene) => scene.nodes().map(|node| node.index()).collect(),
+        // 장면 정보가 없으면 부모가 없는 노드를 모두 루트로 사용한다
+        None => (0..nodes.len()).filter(|index| nodes.iter().all(|node| !node.children.contains(index))).collect(),
+    };
+    spdlog::info!("Loaded model file \"{}\" ({} meshes, {} materials, {} nodes)", file_path, meshes.len(), materials.len(), nodes.len());
+
+    Ok(Model { meshes, materials, nodes, root_nodes })
+}
+
+fn create_sub_mesh(file_path: &str, primitive: &::gltf::Primitive, buffers: &[::gltf::buffer::Data]) -> Result<Option<SubMesh>, errors::Error> {
+    let primitive_type = match primitive.mode() {
+        ::gltf::mesh::Mode::Points => gl::POINTS,
+        ::gltf::mesh::Mode::Lines => gl::LINES,
+        ::gltf::mesh::Mode::LineLoop => gl::LINE_LOOP,
+        ::gltf::mesh::Mode::LineStrip => gl::LINE_STRIP,
+        ::gltf::mesh::Mode::Triangles => gl::TRIANGLES,
+        ::gltf::mesh::Mode::TriangleStrip => gl::TRIANGLE_STRIP,
+        ::gltf::mesh::Mode::TriangleFan => gl::TRIANGLE_FAN,
+    };
+    let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
+
+    let Some(positions) = reader.read_positions() else {
+        spdlog::warn!("{}: Skipped primitive without positions", file_path);
+        return Ok(None);
+    };
+    let mut vertices: Vec<Vertex> = positions.map(|position| {
+        let mut vertex = Vertex::new();
+        vertex.position = glm::make_vec3(&position);
+        vertex
+    }).collect();
+
+    let indices: Vec<u32> = match reader.read_indices() {
+        Some(indices) => indices.into_u32().collect(),
+        None => Vec::new(),
+    };
+    // 잘못되었거나 잘린 파일의 인덱스로 정점 배열 밖을 읽지 않도록 미리 확인한다
+    if let Some(&max_index) = indices.iter().max() && vertices.len() <= max_index as usize {
+        let description = format!("primitive {} uses vertex index {} but has only {} vertices", primitive.index(), max_index, vertices.len());
+        return Err(errors::Error::ParseModelError(file_path.to_owned(), 0, description));
+    }
+    // 삼각형 목록이 아닌 경우에는 normal, tangent를 계산할 수 없으므로 그대로 둔다
+    let triangle_indices: Vec<u32> = if primitive_type != gl::TRIANGLES {
+        Vec::new()
+    } else if indices.is_empty() {
+        (0..vertices.len() as u32).collect()
+    } else {
+        indices.clone()
+    };
+
+    if let Some(texture_coords) = reader.read_tex_coords(0) {
+        for (vertex, texture_coord) in vertices.iter_mut().zip(texture_coords.into_f32()) {
+            vertex.texture_coord = glm::make_vec2(&texture_coord);
+        }
+    }
+    match reader.read_normals() {
+        Some(normals) => {
+            for (vertex, normal) in vertices.iter_mut().zip(normals) {
+                vertex.normal = glm::make_vec3(&normal);
+            }
+        }
+        None => generate_flat_normals(&mut vertices, &triangle_indices),
+    }
+    match reader.read_tangents() {
+        Some(tangents) => {
+            for (vertex, tangent) in vertices.iter_mut().zip(tangents) {
+                vertex.tangent = glm::make_vec4(&tangent);
+            }
+        }
+        None => mesh::generate_tangents(&mut vertices, &triangle_indices),
+    }
+
+    let mesh = Mesh::from_vertices(&vertices, &indices, primitive_type);
+    let material_index = primitive.material().index();
+
+    Ok(Some(SubMesh { mesh: Rc::new(mesh), material_index }))
+}
+
+// normal이 없으면 면의 normal을 정점에 누적한다
+fn generate_flat_normals(vertices: &mut [Vertex], indices: &[u32]) {
+    for triangle in indices.chunks_exact(3) {
+        let [i0, i1, i2] = [triangle[0] as usize, triangle[1] as usize, triangle[2] as usize];
+        let face_normal = glm::cross(&(vertices[i1].position - vertices[i0].position), &(vertices[i2].position - vertices[i0].position));
+        for index in [i0, i1, i2] {
+            vertices[index].normal += face_normal;
+        }
+    }
+    for vertex in vertices.iter_mut() {
+        vertex.normal = if 0.0 < glm::length(&vertex.normal) { glm::normalize(&vertex.normal) } else { glm::vec3(0.0, 1.0, 0.0) };
+    }
+}
+
+fn create_texture(gltf_texture: &::gltf::Texture, data: &::gltf::image::Data) -> Rc<Texture> {
+    let image = convert_image(data);
+    let texture = Texture::create();
+    texture.set_label(gltf_texture.name().unwrap_or(&format!("texture {}", gltf_texture.index())));
+
+    let sampler = gltf_texture.sampler();
+    let wrap = |mode: ::gltf::texture::WrappingMode| match mode {
+        ::gltf::texture::WrappingMode::ClampToEdge => gl::CLAMP_TO_EDGE,
+        ::gltf::texture::WrappingMode::MirroredRepeat => gl::MIRRORED_REPEAT,
+        ::gltf::texture::WrappingMode::Repeat => gl::REPEAT,
+    };
+    let min_filter = match sampler.min_filter() {
+        Some(::gltf::texture::MinFilter::Nearest) => gl::NEAREST,
+        Some(::gltf::texture::MinFilter::Linear) => gl::LINEAR,
+        Some(::gltf::texture::MinFilter::NearestMipmapNearest) => gl::NEAREST_MIPMAP_NEAREST,
+        Some(::gltf::texture::MinFilter::LinearMipmapNearest) => gl::LINEAR_MIPMAP_NEAREST,
+        Some(::gltf::texture::MinFilter::NearestMipmapLinear) => gl::NEAREST_MIPMAP_LINEAR,
+        Some(::gltf::texture::MinFilter::LinearMipmapLinear) | None => gl::LINEAR_MIPMAP_LINEAR,
+    };
+    let mag_filter = match sampler.mag_filter() {
+        Some(::gltf::texture::MagFilter::Nearest) => gl::NEAREST,
+        Some(::gltf::texture::MagFilter::Linear) | None => gl::LINEAR,
+    };
+    texture.set_wrap(wrap(sampler.wrap_s()), wrap(sampler.wrap_t())).set_filter(min_filter, mag_filter).set_texture(&image);
+
+    Rc::new(texture)
+}
+
+// glTF의 텍스쳐 좌표는 이미지의 왼쪽 위가 원점이므로 상하 반전 없이 그대로 업로드 한다
+fn convert_image(data: &::gltf::image::Data) -> Image {
+    use ::gltf::image::Format;
+    let (channel_count, bytes_per_channel) = match data.format {
+        Format::R8 => (1, 1),
+        Format::R8G8 => (2, 1),
+        Format::R8G8B8 => (3, 1),
+        Format::R8G8B8A8 => (4, 1),
+        Format::R16 => (1, 2),
+        Format::R16G16 => (2, 2),
+        Format::R16G16B16 => (3, 2),
+        Format::R16G16B16A16 => (4, 2),
+        Format::R32G32B32FLOAT => (3, 4),
+        Format::R32G32B32A32FLOAT => (4, 4),
+    };
+    // 채널당 8비트가 아닌 이미지는 8비트로 변환한다
+    let pixels = match bytes_per_channel {
+        1 => data.pixels.clone(),
+        2 => data.pixels.chunks_exact(2).map(|channel| (u16::from_le_bytes([channel[0], channel[1]]) >> 8) as u8).collect(),
+        _ => data.pixels.chunks_exact(4).map(|channel| (f32::from_le_bytes([channel[0], channel[1], channel[2], channel[3]]).clamp(0.0, 1.0) * 255.0) as u8).collect(),
+    };
+    Image::from_raw(data.width, data.height, channel_count, pixels)
+}
+
+fn create_material(gltf_material: &::gltf::Material, textures: &[Rc<Texture>]) -> Material {
+    let mut material = Material::new(gltf_material.name().unwrap_or(""));
+    let pbr = gltf_material.pbr_metallic_roughness();
+    let texture = |info: Option<::gltf::texture::Texture>| info.map(|texture| textures[texture.index()].clone());
+
+    material.base_color = glm::make_vec4(&pbr.base_color_factor());
+    material.metallic = pbr.metallic_factor();
+    material.roughness = pbr.roughness_factor();
+    material.emissive = glm::make_vec3(&gltf_material.emissive_factor());
+    material.base_color_texture = texture(pbr.base_color_texture().map(|info| info.texture()));
+    material.metallic_roughness_texture = texture(pbr.metallic_roughness_texture().map(|info| info.texture()));
+    material.emissive_texture = texture(gltf_material.emissive_texture().map(|info| info.texture()));
+    if let Some(normal_texture) = gltf_material.normal_texture() {
+        material.normal_scale = normal_texture.scale();
+        material.normal_texture = texture(Some(normal_texture.texture()));
+    }
+    if let Some(occlusion_texture) = gltf_material.occlusion_texture() {
+        material.occlusion_strength = occlusion_texture.strength();
+        material.occlusion_texture = texture(Some(occlusion_texture.texture()));
+    }
+
+    // Blinn-Phong 셰이더에서도 쓸 수 있도록 diffuse 값을 함께 채운다
+    material.diffuse = material.base_color.xyz();
+    material.opacity = material.base_color.w;
+    material.diffuse_texture = material.base_color_texture.clone();
+
+    material
+}
@@ -0,0 +1,308 @@
+use crate::{errors, asset, resource, material::Material, mesh::{self, Mesh, Vertex}};
+use crate::model::{Model, Node, SubMesh};
+
+use nalgebra_glm as glm;
+use std::{collections::HashMap, path::Path, rc::Rc};
+
+// 파일을 읽고 정점을 만든 결과, GL 객체가 없으므로 다른 스레드에서 만들 수 있다
+pub struct Source {
+    file_path: String,
+    groups: Vec<Group>,
+    material_libraries: Vec<String>,
+}
+
+// 같은 재질을 사용하는 면들의 모음
+struct Group {
+    material: Option<String>,
+    vertices: Vec<Vertex>,
+    indices: Vec<u32>,
+    lookup: HashMap<(usize, Option<usize>, Option<usize>), u32>,
+    generated_normals: Vec<bool>,
+}
+
+impl Group {
+    fn new(material: Option<String>) -> Self {
+        Self { material, vertices: Vec::new(), indices: Vec::new(), lookup: HashMap::new(), generated_normals: Vec::new() }
+    }
+}
+
+pub fn read<S>(file_path: S) -> Result<Source, errors::Error> where S: AsRef<str> {
+    let file_path = file_path.as_ref();
+    let text = asset::read_to_string(file_path)?;
+
+    // positions, texture coordinates, normals
+    let mut positions: Vec<glm::Vec3> = Vec::new();
+    let mut texture_coords: Vec<glm::Vec2> = Vec::new();
+    let mut normals: Vec<glm::Vec3> = Vec::new();
+    let mut material_libraries = Vec::new();
+    // 같은 재질을 쓰는 면은 usemtl이 여러 번 나와도 하나의 그룹으로 모은다
+    let mut groups = vec![Group::new(None)];
+    let mut group_indices = HashMap::from([(None, 0)]);
+    let mut current_group = 0;
+
+    for (line_index, line) in text.lines().enumerate() {
+        let line_number = line_index + 1;
+        let line = line.split('#').next().unwrap_or("").trim();
+        let mut tokens = line.split_whitespace();
+        let Some(keyword) = tokens.next() else {
+            continue;
+        };
+        let arguments: Vec<&str> = tokens.collect();
+
+        match keyword {
+            "v" => {
+                let value = parse_floats(&arguments, 3, file_path, line_number)?;
+                positions.push(glm::vec3(value[0], value[1], value[2]));
+            }
+            "vt" => {
+                let value = parse_floats(&arguments, 1, file_path, line_number)?;
+                texture_coords.push(glm::vec2(value[0], value.get(1).copied().unwrap_or(0.0)));
+            }
+            "vn" => {
+                let value = parse_floats(&arguments, 3, file_path, line_number)?;
+                normals.push(glm::vec3(value[0], value[1], value[2]));
+            }
+            "f" => {
+                if arguments.len() < 3 {
+                    return Err(parse_error(file_path, line_number, format!("face needs at least 3 vertices, found {}", arguments.len())));
+                }
+                let mut corners = Vec::with_capacity(arguments.len());
+                for argument in &arguments {
+                    corners.push(parse_face_vertex(argument, positions.len(), texture_coords.len(), normals.len(), file_path, line_number)?);
+                }
+                let group = &mut groups[current_group];
+                // 볼록 다각형을 가정하고 첫 번째 정점을 기준으로 부채꼴 모양으로 삼각형을 나눈다
+                for i in 1..corners.len() - 1 {
+                    add_triangle(group, [corners[0], corners[i], corners[i + 1]], &positions, &texture_coords, &normals);
+                }
+            }
+            "usemtl" => {
+                let name = arguments.join(" ");
+                if name.is_empty() {
+                    return Err(parse_error(file_path, line_number, "usemtl needs a material name".to_owned()));
+                }
+                current_group = *group_indices.entry(Some(name.clone())).or_insert_with(|| {
+                    groups.push(Group::new(Some(name)));
+                    groups.len() - 1
+                });
+            }
+            "mtllib" => {
+                if arguments.is_empty() {
+                    return Err(parse_error(file_path, line_number, "mtllib needs a file name".to_owned()));
+                }
+                for argument in &arguments {
+                    material_libraries.push(resolve_path(file_path, argument));
+                }
+            }
+            "o" | "g" | "s" | "l" | "p" => {}
+            _ => {
+                spdlog::warn!("{}:{}: Ignored unsupported statement \"{}\"", file_path, line_number, keyword);
+            }
+        }
+    }
+
+    let mut groups: Vec<Group> = groups.into_iter().filter(|group| !group.indices.is_empty()).collect();
+    for group in &mut groups {
+        for (vertex, generated) in group.vertices.iter_mut().zip(&group.generated_normals) {
+            if *generated {
+                vertex.normal = if 0.0 < glm::length(&vertex.normal) { glm::normalize(&vertex.normal) } else { glm::vec3(0.0, 1.0, 0.0) };
+            }
+        }
+        mesh::generate_tangents(&mut group.vertices, &group.indices);
+    }
+    Ok(Source { file_path: file_path.to_owned(), groups, material_libraries })
+}
+
+// 재질 파일과 텍스쳐를 읽고 메시를 GPU에 올린다, OpenGL 컨텍스트의 스레드에서 호출한다
+pub fn build(source: Source) -> Result<Model, errors::Error> {
+    let Source { file_path, groups, material_libraries } = source;
+    let file_path = file_path.as_str();
+
+    // 재질 파일을 읽는다, map_Kd 텍스쳐는 resource 캐시로 다른 모델과도 공유한다
+    let mut materials = Vec::new();
+    let mut material_indices = HashMap::new();
+    for material_library in &material_libraries {
+        // 재질 파일이 없으면 해당 재질을 쓰는 면은 기본 재질로 그린다
+        let library = match load_mtl(material_library) {
+            Ok(library) => library,
+            Err(error) if is_not_found(&error) => {
+                spdlog::warn!("{}: Material library \"{}\" is not found, using the default material", file_path, material_library);
+                continue;
+            }
+            Err(error) => return Err(error),
+        };
+        for material in library {
+            material_indices.insert(material.name.clone(), materials.len());
+            materials.push(Rc::new(material));
+        }
+    }
+
+    let mut meshes = Vec::new();
+    for group in groups {
+        let material_index = match &group.material {
+            Some(name) => {
+                let index = material_indices.get(name).copied();
+                if index.is_none() {
+                    spdlog::warn!("{}: Material \"{}\" is not defined", file_path, name);
+                }
+                index
+            }
+            None => None,
+        };
+        let mesh = Mesh::from_vertices(&group.vertices, &group.indices, gl::TRIANGLES);
+        mesh.set_label(&format!("{} {}", file_path, group.material.as_deref().unwrap_or("group")));
+        meshes.push(SubMesh { mesh: Rc::new(mesh), material_index });
+    }
+    spdlog::info!("Loaded model file \"{}\" ({} meshes, {} materials)", file_path, meshes.len(), materials.len());
+
+    // OBJ에는 계층 구조가 없으므로 모든 메시를 하나의 노드에 담는다
+    let mut root = Node::new(file_path);
+    root.meshes = (0..meshes.len()).collect();
+    let nodes = vec![root];
+    let root_nodes = vec![0];
+
+    Ok(Model { meshes, materials, nodes, root_nodes })
+}
+
+fn load_mtl(file_path: &str) -> Result<Vec<Material>, errors::Error> {
+    let text = asset::read_to_string(file_path)?;
+    let mut materials: Vec<Material> = Vec::new();
+
+    for (line_index, line) in text.lines().enumerate() {
+        let line_number = line_index + 1;
+        let line = line.split('#').next().unwrap_or("").trim();
+        let mut tokens = line.split_whitespace();
+        let Some(keyword) = tokens.next() else {
+            continue;
+        };
+        let arguments: Vec<&str> = tokens.collect();
+
+        if keyword == "newmtl" {
+            materials.push(Material::new(&arguments.join(" ")));
+            continue;
+        }
+        let Some(material) = materials.last_mut() else {
+            return Err(parse_error(file_path, line_number, format!("\"{}\" appears before newmtl", keyword)));
+        };
+        match keyword {
+            "Ka" => material.ambient = parse_color(&arguments, file_path, line_number)?,
+            "Kd" => {
+                material.diffuse = parse_color(&arguments, file_path, line_number)?;
+                material.base_color = glm::vec4(material.diffuse.x, material.diffuse.y, material.diffuse.z, material.base_color.w);
+            }
+            "Ks" => material.specular = parse_color(&arguments, file_path, line_number)?,
+            "Ns" => material.shininess = parse_floats(&arguments, 1, file_path, line_number)?[0],
+            "d" => {
+                material.opacity = parse_floats(&arguments, 1, file_path, line_number)?[0];
+                material.base_color.w = material.opacity;
+            }
+            "Tr" => {
+                material.opacity = 1.0 - parse_floats(&arguments, 1, file_path, line_number)?[0];
+                material.base_color.w = material.opacity;
+            }
+            "map_Kd" => {
+                // `-o`, `-s` 같은 옵션은 무시하고 마지막 인자를 파일 이름으로 사용한다
+                let Some(name) = arguments.last() else {
+                    return Err(parse_error(file_path, line_number, "map_Kd needs a file name".to_owned()));
+                };
+                let texture = resource::load_texture(&resolve_path(file_path, name), gl::REPEAT)?;
+                material.diffuse_texture = Some(texture.clone());
+                material.base_color_texture = Some(texture);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(materials)
+}
+
+fn add_triangle(group: &mut Group, corners: [(usize, Option<usize>, Option<usize>); 3], positions: &[glm::Vec3], texture_coords: &[glm::Vec2], normals: &[glm::Vec3]) {
+    // 외적의 크기가 면적에 비례하므로 정규화하지 않은 채로 누적하면 면적 가중 평균이 된다
+    let p0 = positions[corners[0].0];
+    let face_normal = glm::cross(&(positions[corners[1].0] - p0), &(positions[corners[2].0] - p0));
+
+    for corner in corners {
+        let index = match group.lookup.get(&corner) {
+            Some(index) => *index,
+            None => {
+                let mut vertex = Vertex::new();
+                vertex.position = positions[corner.0];
+                if let Some(texture_coord) = corner.1 {
+                    vertex.texture_coord = texture_coords[texture_coord];
+                }
+                if let Some(normal) = corner.2 {
+                    vertex.normal = normals[normal];
+                }
+                let index = group.vertices.len() as u32;
+                group.vertices.push(vertex);
+                group.generated_normals.push(corner.2.is_none());
+                group.lookup.insert(corner, index);
+                index
+            }
+        };
+        if group.generated_normals[index as usize] {
+            group.vertices[index as usize].normal += face_normal;
+        }
+        group.indices.push(index);
+    }
+}
+
+fn parse_face_vertex(argument: &str, position_count: usize, texture_coord_count: usize, normal_count: usize, file_path: &str, line_number: usize) -> Result<(usize, Option<usize>, Option<usize>), errors::Error> {
+    let mut parts = argument.split('/');
+    let position = parse_index(parts.next(), position_count, file_path, line_number)?;
+    let texture_coord = parse_index(parts.next(), texture_coord_count, file_path, line_number)?;
+    let normal = parse_index(parts.next(), normal_count, file_path, line_number)?;
+    match position {
+        Some(position) => Ok((position, texture_coord, normal)),
+        None => Err(parse_error(file_path, line_number, format!("face vertex \"{}\" has no position index", argument))),
+    }
+}
+
+// OBJ의 인덱스는 1부터 시작하며, 음수는 현재까지 정의된 요소의 끝에서부터 센다
+fn parse_index(part: Option<&str>, count: usize, file_path: &str, line_number: usize) -> Result<Option<usize>, errors::Error> {
+    let part = match part {
+        Some(part) if !part.is_empty() => part,
+        _ => return Ok(None),
+    };
+    let index: i64 = part.parse().map_err(|_| parse_error(file_path, line_number, format!("invalid index \"{}\"", part)))?;
+    let resolved = if 0 < index { index - 1 } else { count as i64 + index };
+    if index == 0 || resolved < 0 || count as i64 <= resolved {
+        return Err(parse_error(file_path, line_number, format!("index {} is out of range (1..={})", index, count)));
+    }
+    Ok(Some(resolved as usize))
+}
+
+fn parse_floats(arguments: &[&str], count: usize, file_path: &str, line_number: usize) -> Result<Vec<f32>, errors::Error> {
+    if arguments.len() < count {
+        return Err(parse_error(file_path, line_number, format!("expected {} numbers, found {}", count, arguments.len())));
+    }
+    arguments.iter().map(|argument| {
+        argument.parse::<f32>().map_err(|_| parse_error(file_path, line_number, format!("invalid number \"{}\"", argument)))
+    }).collect()
+}
+
+fn parse_color(arguments: &[&str], file_path: &str, line_number: usize) -> Result<glm::Vec3, errors::Error> {
+    let value = parse_floats(arguments, 3, file_path, line_number)?;
+    Ok(glm::vec3(value[0], value[1], value[2]))
+}
+
+// mtllib, map_Kd 경로는 현재 파일이 있는 디렉터리를 기준으로 한다
+fn resolve_path(base_file_path: &str, relative_path: &str) -> String {
+    match Path::new(base_file_path).parent() {
+        Some(directory) => directory.join(relative_path).to_string_lossy().into_owned(),
+        None => relative_path.to_owned(),
+    }
+}
+
+fn parse_error(file_path: &str, line_number: usize, description: String) -> errors::Error {
+    errors::Error::ParseModelError(file_path.to_owned(), line_number, description)
+}
+
+// 마운트한 곳이 없으면 디스크의 NotFound, 있으면 AssetNotFoundError가 된다
+fn is_not_found(error: &errors::Error) -> bool {
+    match error {
+        errors::Error::ReadFileError(error) => error.kind() == std::io::ErrorKind::NotFound,
+        errors::Error::AssetNotFoundError(..) => true,
+        _ => false,
+    }
+}
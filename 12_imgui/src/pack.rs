@@ -0,0 +1,217 @@
+// 여러 파일을 하나로 묶은 읽기 전용 팩 파일
+// 실행 파일과 packer 도구가 함께 사용하므로 std 외의 모듈에 의존하지 않는다
+//
+// 형식(정수는 모두 little endian)
+// "PAK1" | 항목 개수(u32) | 항목 * 개수 | 파일 데이터
+// 항목: 경로 길이(u32) | 경로(UTF-8, '/'로 구분) | 데이터 위치(u64, 파일 처음부터) | 데이터 크기(u64)
+use std::{collections::HashMap, fs::File, io::{self, Read, Seek, SeekFrom, Write}, path::{Path, PathBuf}};
+
+const MAGIC: &[u8; 4] = b"PAK1";
+
+pub struct Pack {
+    file_path: PathBuf,
+    entries: HashMap<String, (u64, u64)>, // 경로 -> (위치, 크기)
+}
+
+impl Pack {
+    // 목록만 읽어 두고 데이터는 필요할 때 읽는다
+    pub fn open<P>(file_path: P) -> io::Result<Pack> where P: AsRef<Path> {
+        let file_path = file_path.as_ref().to_path_buf();
+        let file = File::open(&file_path)?;
+        let file_len = file.metadata()?.len();
+        let mut file = io::BufReader::new(file);
+        let mut magic = [0u8; 4];
+        file.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("{} is not a pack file", file_path.display())));
+        }
+        let count = read_u32(&mut file)?;
+        let mut entries = HashMap::new();
+        for _ in 0..count {
+            let length = read_u32(&mut file)? as u64;
+            // 손상된 파일의 길이만큼 메모리를 잡지 않도록 남은 크기와 비교한다
+            if file_len.saturating_sub(file.stream_position()?) < length {
+                return Err(invalid_data(&file_path, "entry path is longer than the file"));
+            }
+            let mut key = vec![0u8; length as usize];
+            file.read_exact(&mut key)?;
+            let key = String::from_utf8(key).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+            let offset = read_u64(&mut file)?;
+            let size = read_u64(&mut file)?;
+            if offset.checked_add(size).is_none_or(|end| file_len < end) {
+                return Err(invalid_data(&file_path, &format!("entry \"{}\" is out of the file", key)));
+            }
+            entries.insert(key, (offset, size));
+        }
+        Ok(Pack { file_path, entries })
+    }
+
+    pub fn get_file_path(&self) -> &Path {
+        &self.file_path
+    }
+
+    pub fn contains(&self, path: &str) -> bool {
+        self.entries.contains_key(&normalize(path))
+    }
+
+    // 여러 스레드에서 읽을 수 있도록 읽을 때마다 파일을 연다
+    pub fn read(&self, path: &str) -> io::Result<Option<Vec<u8>>> {
+        let Some(&(offset, size)) = self.entries.get(&normalize(path)) else {
+            return Ok(None);
+        };
+        let mut file = File::open(&self.file_path)?;
+        // 연 뒤에 파일이 바뀌었을 수 있으므로 다시 확인한다
+        let file_len = file.metadata()?.len();
+        if offset.checked_add(size).is_none_or(|end| file_len < end) {
+            return Err(invalid_data(&self.file_path, &format!("entry \"{}\" is out of the file", path)));
+        }
+        file.seek(SeekFrom::Start(offset))?;
+        let mut data = vec![0u8; size as usize];
+        file.read_exact(&mut data)?;
+        Ok(Some(data))
+    }
+
+    pub fn get_paths(&self) -> Vec<&str> {
+        let mut paths: Vec<&str> = self.entries.keys().map(|key| key.as_str()).collect();
+        paths.sort();
+        paths
+    }
+}
+
+// files는 (팩 안의 경로, 디스크의 파일)
+#[allow(dead_code)] // 실행 파일은 팩을 읽기만 하고 packer 도구가 쓴다
+pub fn write<P>(file_path: P, files: &[(String, PathBuf)]) -> io::Result<()> where P: AsRef<Path> {
+    let keys: Vec<String> = files.iter().map(|(key, _)| normalize(key)).collect();
+    let index_size: u64 = 8 + keys.iter().map(|key| 4 + key.len() as u64 + 16).sum::<u64>();
+    let mut sizes = Vec::new();
+    for (_, path) in files {
+        sizes.push(std::fs::metadata(path)?.len());
+    }
+
+    let mut file = io::BufWriter::new(File::create(file_path)?);
+    file.write_all(MAGIC)?;
+    file.write_all(&(files.len() as u32).to_le_bytes())?;
+    let mut offset = index_size;
+    for (key, size) in keys.iter().zip(&sizes) {
+        file.write_all(&(key.len() as u32).to_le_bytes())?;
+        file.write_all(key.as_bytes())?;
+        file.write_all(&offset.to_le_bytes())?;
+        file.write_all(&size.to_le_bytes())?;
+        offset += size;
+    }
+    for ((_, path), size) in files.iter().zip(&sizes) {
+        let copied = io::copy(&mut File::open(path)?.take(*size), &mut file)?;
+        if copied != *size {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, format!("{} changed while packing", path.display())));
+        }
+    }
+    file.flush()
+}
+
+// "./shader\\ui.vert" 처럼 적어도 "shader/ui.vert"로 맞춘다
+pub fn normalize(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let mut components: Vec<&str> = Vec::new();
+    for component in path.split('/') {
+        match component {
+            "" | "." => {}
+            ".." if components.last().is_some_and(|last| *last != "..") => {
+                components.pop();
+            }
+            component => components.push(component),
+        }
+    }
+    components.join("/")
+}
+
+fn invalid_data(file_path: &Path, description: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", file_path.display(), description))
+}
+
+fn read_u32<R>(reader: &mut R) -> io::Result<u32> where R: Read {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64<R>(reader: &mut R) -> io::Result<u64> where R: Read {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 테스트마다 다른 임시 디렉토리를 사용한다
+    fn create_temp_dir(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("pack_test_{}_{}", std::process::id(), name));
+        std::fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    // 항목 하나짜리 팩을 직접 만든다
+    fn write_raw(file_path: &Path, key: &str, offset: u64, size: u64, data: &[u8]) {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend(1u32.to_le_bytes());
+        bytes.extend((key.len() as u32).to_le_bytes());
+        bytes.extend(key.as_bytes());
+        bytes.extend(offset.to_le_bytes());
+        bytes.extend(size.to_le_bytes());
+        bytes.extend(data);
+        std::fs::write(file_path, bytes).unwrap();
+    }
+
+    #[test]
+    fn round_trip() {
+        let directory = create_temp_dir("round_trip");
+        std::fs::write(directory.join("a.txt"), b"hello").unwrap();
+        std::fs::write(directory.join("b.bin"), [0u8, 1, 2, 3]).unwrap();
+        let pack_path = directory.join("test.pak");
+        write(&pack_path, &[("./shader\\a.txt".to_owned(), directory.join("a.txt")), ("resources/b.bin".to_owned(), directory.join("b.bin"))]).unwrap();
+
+        let pack = Pack::open(&pack_path).unwrap();
+        assert_eq!(pack.get_paths(), vec!["resources/b.bin", "shader/a.txt"]);
+        assert!(pack.contains("shader/a.txt"));
+        assert_eq!(pack.read("shader/a.txt").unwrap().unwrap(), b"hello");
+        assert_eq!(pack.read("resources/../resources/b.bin").unwrap().unwrap(), [0u8, 1, 2, 3]);
+        assert!(pack.read("missing.txt").unwrap().is_none());
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn truncated_pack_is_rejected() {
+        let directory = create_temp_dir("truncated");
+        std::fs::write(directory.join("a.txt"), b"hello").unwrap();
+        let pack_path = directory.join("test.pak");
+        write(&pack_path, &[("a.txt".to_owned(), directory.join("a.txt"))]).unwrap();
+        let length = std::fs::metadata(&pack_path).unwrap().len();
+        // 데이터의 일부가 잘린 경우
+        File::options().write(true).open(&pack_path).unwrap().set_len(length - 2).unwrap();
+        assert_eq!(Pack::open(&pack_path).err().map(|err| err.kind()), Some(io::ErrorKind::InvalidData));
+        // 목록 중간에서 잘린 경우
+        File::options().write(true).open(&pack_path).unwrap().set_len(10).unwrap();
+        assert!(Pack::open(&pack_path).is_err());
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn out_of_range_entry_is_rejected() {
+        let directory = create_temp_dir("out_of_range");
+        let pack_path = directory.join("test.pak");
+        write_raw(&pack_path, "a.txt", 1 << 40, 5, b"hello");
+        assert_eq!(Pack::open(&pack_path).err().map(|err| err.kind()), Some(io::ErrorKind::InvalidData));
+        // 위치와 크기를 더하면 넘치는 경우
+        write_raw(&pack_path, "a.txt", u64::MAX, 2, b"hello");
+        assert_eq!(Pack::open(&pack_path).err().map(|err| err.kind()), Some(io::ErrorKind::InvalidData));
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn normalize_paths() {
+        assert_eq!(normalize("./shader\\ui.vert"), "shader/ui.vert");
+        assert_eq!(normalize("resources/images/../config/input.cfg"), "resources/config/input.cfg");
+        assert_eq!(normalize("../a/b"), "../a/b");
+    }
+}
@@ -0,0 +1,87 @@
+use super::{camera::Camera, scene::{DrawItem, Node}};
+use nalgebra_glm as glm;
+use std::{cell::RefCell, rc::Rc};
+
+// world 공간의 반직선, direction은 단위 벡터
+#[derive(Clone, Copy)]
+pub struct Ray {
+    pub origin: glm::Vec3,
+    pub direction: glm::Vec3,
+}
+
+impl Ray {
+    pub fn new(origin: glm::Vec3, direction: glm::Vec3) -> Self {
+        Self { origin, direction: glm::normalize(&direction) }
+    }
+
+    // 화면의 픽셀 좌표(왼쪽 위가 원점)에서 카메라의 near 평면을 지나 far 평면으로 향하는 반직선
+    pub fn from_screen(camera: &Camera, screen_position: &glm::Vec2, width: u32, height: u32) -> Self {
+        let ndc = glm::vec2(2.0 * screen_position.x / width as f32 - 1.0, 1.0 - 2.0 * screen_position.y / height as f32);
+        let inverse = glm::inverse(&(camera.get_projection_matrix(width as f32 / height as f32) * camera.get_view_matrix()));
+        let near = inverse * glm::vec4(ndc.x, ndc.y, -1.0, 1.0);
+        let far = inverse * glm::vec4(ndc.x, ndc.y, 1.0, 1.0);
+        let near = near.xyz() / near.w;
+        let far = far.xyz() / far.w;
+        Self::new(near, far - near)
+    }
+
+    pub fn get_point(&self, distance: f32) -> glm::Vec3 {
+        self.origin + self.direction * distance
+    }
+}
+
+pub struct Hit {
+    pub node: Rc<RefCell<Node>>,
+    pub position: glm::Vec3, // world 공간의 교차 지점
+    pub distance: f32,
+}
+
+// 경계 상자로 먼저 거른 뒤 삼각형과 검사해서 가장 가까운 물체를 찾는다
+pub fn pick(draw_items: &[DrawItem], ray: &Ray) -> Option<Hit> {
+    let mut closest: Option<Hit> = None;
+    for draw_item in draw_items {
+        let Some(box_distance) = draw_item.get_bounding_box().intersect_ray(&ray.origin, &ray.direction) else {
+            continue;
+        };
+        if let Some(hit) = &closest && hit.distance < box_distance {
+            continue;
+        }
+        // 광선을 로컬 좌표계로 옮겨서 메시의 정점을 변환하지 않고 검사한다
+        let inverse = glm::inverse(&draw_item.world_matrix);
+        let origin = (inverse * glm::vec4(ray.origin.x, ray.origin.y, ray.origin.z, 1.0)).xyz();
+        let direction = (inverse * glm::vec4(ray.direction.x, ray.direction.y, ray.direction.z, 0.0)).xyz();
+        let Some(local_distance) = draw_item.mesh.intersect_ray(&origin, &direction) else {
+            continue;
+        };
+        let local_position = origin + direction * local_distance;
+        let position = (draw_item.world_matrix * glm::vec4(local_position.x, local_position.y, local_position.z, 1.0)).xyz();
+        let distance = glm::distance(&ray.origin, &position);
+        if closest.as_ref().is_none_or(|hit| distance < hit.distance) {
+            closest = Some(Hit { node: draw_item.node.clone(), position, distance });
+        }
+    }
+    closest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn center_ray_follows_camera_front() {
+        let mut camera = Camera::new(glm::vec3(1.0, 2.0, 3.0));
+        camera.look_at(&glm::vec3(0.0, 0.0, 0.0));
+        let ray = Ray::from_screen(&camera, &glm::vec2(400.0, 300.0), 800, 600);
+        assert!(glm::distance(&ray.direction, &camera.get_front()) < 1e-4);
+        // 원점은 near 평면 위에 있다
+        assert!((glm::distance(&ray.origin, &camera.get_position()) - camera.get_near()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn corner_ray_points_up_left() {
+        let camera = Camera::new(glm::vec3(0.0, 0.0, 0.0));
+        let ray = Ray::from_screen(&camera, &glm::vec2(0.0, 0.0), 800, 600);
+        let local = glm::vec3(glm::dot(&ray.direction, &camera.get_right()), glm::dot(&ray.direction, &camera.get_up()), glm::dot(&ray.direction, &camera.get_front()));
+        assert!(local.x < 0.0 && 0.0 < local.y && 0.0 < local.z);
+    }
+}
@@ -0,0 +1,278 @@
+use super::mesh::{self, Mesh, Vertex};
+use nalgebra_glm as glm;
+use std::{collections::HashMap, f32::consts::PI};
+
+// 모든 도형은 바깥쪽에서 봤을 때 반시계 방향(CCW)으로 감긴 삼각형 목록이다
+pub struct Primitive {
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<u32>,
+}
+
+impl Primitive {
+    // XZ 평면 위의 정사각형, normal은 +Y
+    pub fn plane(size: f32, subdivisions: u32) -> Self {
+        let mut primitive = Self::new();
+        let half = size * 0.5;
+        primitive.add_grid(glm::vec3(-half, 0.0, half), glm::vec3(size, 0.0, 0.0), glm::vec3(0.0, 0.0, -size), subdivisions.max(1));
+        primitive.finish()
+    }
+
+    pub fn cube(size: f32, subdivisions: u32) -> Self {
+        let mut primitive = Self::new();
+        let half = size * 0.5;
+        // (normal, u 방향, v 방향), u x v = normal
+        let faces = [
+            (glm::vec3(1.0, 0.0, 0.0), glm::vec3(0.0, 0.0, -1.0), glm::vec3(0.0, 1.0, 0.0)),
+            (glm::vec3(-1.0, 0.0, 0.0), glm::vec3(0.0, 0.0, 1.0), glm::vec3(0.0, 1.0, 0.0)),
+            (glm::vec3(0.0, 1.0, 0.0), glm::vec3(1.0, 0.0, 0.0), glm::vec3(0.0, 0.0, -1.0)),
+            (glm::vec3(0.0, -1.0, 0.0), glm::vec3(1.0, 0.0, 0.0), glm::vec3(0.0, 0.0, 1.0)),
+            (glm::vec3(0.0, 0.0, 1.0), glm::vec3(1.0, 0.0, 0.0), glm::vec3(0.0, 1.0, 0.0)),
+            (glm::vec3(0.0, 0.0, -1.0), glm::vec3(-1.0, 0.0, 0.0), glm::vec3(0.0, 1.0, 0.0)),
+        ];
+        for (normal, u, v) in faces {
+            let origin = (normal - u - v) * half;
+            primitive.add_grid(origin, u * size, v * size, subdivisions.max(1));
+        }
+        primitive.finish()
+    }
+
+    pub fn uv_sphere(radius: f32, segments: u32, rings: u32) -> Self {
+        let mut primitive = Self::new();
+        let segments = segments.max(3);
+        let rings = rings.max(2);
+        for i in 0..=rings {
+            let phi = PI * i as f32 / rings as f32;
+            for j in 0..=segments {
+                let theta = 2.0 * PI * j as f32 / segments as f32;
+                let normal = glm::vec3(phi.sin() * theta.sin(), phi.cos(), phi.sin() * theta.cos());
+                primitive.push_vertex(normal * radius, normal, glm::vec2(j as f32 / segments as f32, 1.0 - i as f32 / rings as f32));
+            }
+        }
+        primitive.add_rows(0, rings + 1, segments);
+        primitive.finish()
+    }
+
+    pub fn icosphere(radius: f32, subdivisions: u32) -> Self {
+        let t = (1.0 + 5.0f32.sqrt()) * 0.5;
+        let mut positions: Vec<glm::Vec3> = [
+            (-1.0, t, 0.0), (1.0, t, 0.0), (-1.0, -t, 0.0), (1.0, -t, 0.0),
+            (0.0, -1.0, t), (0.0, 1.0, t), (0.0, -1.0, -t), (0.0, 1.0, -t),
+            (t, 0.0, -1.0), (t, 0.0, 1.0), (-t, 0.0, -1.0), (-t, 0.0, 1.0),
+        ].iter().map(|(x, y, z)| glm::normalize(&glm::vec3(*x, *y, *z))).collect();
+        let mut faces: Vec<[u32; 3]> = vec![
+            [0, 11, 5], [0, 5, 1], [0, 1, 7], [0, 7, 10], [0, 10, 11],
+            [1, 5, 9], [5, 11, 4], [11, 10, 2], [10, 7, 6], [7, 1, 8],
+            [3, 9, 4], [3, 4, 2], [3, 2, 6], [3, 6, 8], [3, 8, 9],
+            [4, 9, 5], [2, 4, 11], [6, 2, 10], [8, 6, 7], [9, 8, 1],
+        ];
+
+        // 각 삼각형을 4개로 나누고, 새로 생긴 중점을 구 표면으로 밀어낸다
+        for _ in 0..subdivisions {
+            let mut midpoints: HashMap<(u32, u32), u32> = HashMap::new();
+            let mut midpoint = |a: u32, b: u32, positions: &mut Vec<glm::Vec3>| {
+                let key = (a.min(b), a.max(b));
+                *midpoints.entry(key).or_insert_with(|| {
+                    positions.push(glm::normalize(&(positions[a as usize] + positions[b as usize])));
+                    positions.len() as u32 - 1
+                })
+            };
+            let mut next_faces = Vec::with_capacity(faces.len() * 4);
+            for [a, b, c] in faces {
+                let ab = midpoint(a, b, &mut positions);
+                let bc = midpoint(b, c, &mut positions);
+                let ca = midpoint(c, a, &mut positions);
+                next_faces.extend_from_slice(&[[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]);
+            }
+            faces = next_faces;
+        }
+
+        let mut primitive = Self::new();
+        let texture_coord = |normal: &glm::Vec3| glm::vec2(0.5 + normal.x.atan2(normal.z) / (2.0 * PI), 0.5 + normal.y.asin() / PI);
+        for normal in &positions {
+            primitive.push_vertex(normal * radius, *normal, texture_coord(normal));
+        }
+        for face in faces {
+            let mut face = face;
+            // 텍스쳐 좌표의 이음매(u = 0 / 1)를 가로지르는 삼각형은 정점을 복제해서 u를 이어 붙인다
+            let u = face.map(|index| primitive.vertices[index as usize].texture_coord.x);
+            if 0.5 < u.iter().cloned().fold(f32::MIN, f32::max) - u.iter().cloned().fold(f32::MAX, f32::min) {
+                for corner in face.iter_mut() {
+                    let vertex = primitive.vertices[*corner as usize];
+                    if vertex.texture_coord.x < 0.5 {
+                        primitive.push_vertex(vertex.position, vertex.normal, vertex.texture_coord + glm::vec2(1.0, 0.0));
+                        *corner = primitive.vertices.len() as u32 - 1;
+                    }
+                }
+            }
+            primitive.push_triangle(face[0], face[1], face[2]);
+        }
+        primitive.finish()
+    }
+
+    pub fn cylinder(radius: f32, height: f32, segments: u32, height_segments: u32) -> Self {
+        Self::frustum(radius, radius, height, segments, height_segments, true)
+    }
+
+    // 밑면이 XZ 평면과 평행하고, 꼭짓점이 +Y 방향을 향하는 원뿔
+    pub fn cone(radius: f32, height: f32, segments: u32, height_segments: u32) -> Self {
+        Self::frustum(radius, 0.0, height, segments, height_segments, false)
+    }
+
+    // XZ 평면 위에 놓인 도넛 모양
+    pub fn torus(major_radius: f32, minor_radius: f32, segments: u32, sides: u32) -> Self {
+        let mut primitive = Self::new();
+        let segments = segments.max(3);
+        let sides = sides.max(3);
+        for i in 0..=sides {
+            // 바깥쪽에서 봤을 때 위에서 아래로 내려가도록 phi를 감소시킨다
+            let phi = -2.0 * PI * i as f32 / sides as f32;
+            for j in 0..=segments {
+                let theta = 2.0 * PI * j as f32 / segments as f32;
+                let center = glm::vec3(theta.sin(), 0.0, theta.cos()) * major_radius;
+                let normal = glm::vec3(phi.cos() * theta.sin(), phi.sin(), phi.cos() * theta.cos());
+                primitive.push_vertex(center + normal * minor_radius, normal, glm::vec2(j as f32 / segments as f32, 1.0 - i as f32 / sides as f32));
+            }
+        }
+        primitive.add_rows(0, sides + 1, segments);
+        primitive.finish()
+    }
+
+    // `height`는 양 끝 반구를 제외한 원기둥 부분의 높이
+    pub fn capsule(radius: f32, height: f32, segments: u32, rings: u32) -> Self {
+        let mut primitive = Self::new();
+        let segments = segments.max(3);
+        let rings = rings.max(1);
+        let half = height * 0.5;
+        let total_length = height + PI * radius;
+        let mut row_count = 0;
+        // 위쪽 반구(phi: 0 ~ PI/2)와 아래쪽 반구(phi: PI/2 ~ PI)를 위에서부터 차례로 쌓는다
+        for (offset, start) in [(half, 0.0), (-half, PI * 0.5)] {
+            for i in 0..=rings {
+                let phi = start + PI * 0.5 * i as f32 / rings as f32;
+                let arc_length = phi * radius + if offset < 0.0 { height } else { 0.0 };
+                for j in 0..=segments {
+                    let theta = 2.0 * PI * j as f32 / segments as f32;
+                    let normal = glm::vec3(phi.sin() * theta.sin(), phi.cos(), phi.sin() * theta.cos());
+                    primitive.push_vertex(normal * radius + glm::vec3(0.0, offset, 0.0), normal, glm::vec2(j as f32 / segments as f32, 1.0 - arc_length / total_length));
+                }
+                row_count += 1;
+            }
+        }
+        primitive.add_rows(0, row_count, segments);
+        primitive.finish()
+    }
+
+    pub fn create_mesh(&self) -> Mesh {
+        Mesh::from_vertices(&self.vertices, &self.indices, gl::TRIANGLES)
+    }
+
+    fn new() -> Self {
+        Self { vertices: Vec::new(), indices: Vec::new() }
+    }
+
+    // 원기둥과 원뿔의 공통 구현, 중심은 원점이고 높이 방향은 Y축
+    fn frustum(bottom_radius: f32, top_radius: f32, height: f32, segments: u32, height_segments: u32, top_cap: bool) -> Self {
+        let mut primitive = Self::new();
+        let segments = segments.max(3);
+        let height_segments = height_segments.max(1);
+        let half = height * 0.5;
+
+        // side
+        for i in 0..=height_segments {
+            let ratio = i as f32 / height_segments as f32;
+            let radius = top_radius + (bottom_radius - top_radius) * ratio;
+            let y = half - height * ratio;
+            for j in 0..=segments {
+                let theta = 2.0 * PI * j as f32 / segments as f32;
+                let normal = glm::normalize(&glm::vec3(theta.sin() * height, bottom_radius - top_radius, theta.cos() * height));
+                primitive.push_vertex(glm::vec3(theta.sin() * radius, y, theta.cos() * radius), normal, glm::vec2(j as f32 / segments as f32, 1.0 - ratio));
+            }
+        }
+        primitive.add_rows(0, height_segments + 1, segments);
+
+        // caps
+        if top_cap {
+            primitive.add_cap(half, top_radius, segments, 1.0);
+        }
+        primitive.add_cap(-half, bottom_radius, segments, -1.0);
+        primitive.finish()
+    }
+
+    fn add_cap(&mut self, y: f32, radius: f32, segments: u32, direction: f32) {
+        let normal = glm::vec3(0.0, direction, 0.0);
+        let center = self.vertices.len() as u32;
+        self.push_vertex(glm::vec3(0.0, y, 0.0), normal, glm::vec2(0.5, 0.5));
+        for j in 0..=segments {
+            let theta = 2.0 * PI * j as f32 / segments as f32;
+            self.push_vertex(glm::vec3(theta.sin() * radius, y, theta.cos() * radius), normal, glm::vec2(0.5 + 0.5 * theta.sin(), 0.5 + 0.5 * theta.cos()));
+        }
+        for j in 0..segments {
+            if 0.0 < direction {
+                self.push_triangle(center, center + 1 + j, center + 2 + j);
+            } else {
+                self.push_triangle(center, center + 2 + j, center + 1 + j);
+            }
+        }
+    }
+
+    // origin + u * s + v * t (s, t: 0 ~ 1) 격자, u x v 방향이 앞면이 된다
+    fn add_grid(&mut self, origin: glm::Vec3, u: glm::Vec3, v: glm::Vec3, subdivisions: u32) {
+        let normal = glm::normalize(&glm::cross(&u, &v));
+        let first = self.vertices.len() as u32;
+        for i in 0..=subdivisions {
+            let t = i as f32 / subdivisions as f32;
+            for j in 0..=subdivisions {
+                let s = j as f32 / subdivisions as f32;
+                self.push_vertex(origin + u * s + v * t, normal, glm::vec2(s, t));
+            }
+        }
+        let columns = subdivisions + 1;
+        for i in 0..subdivisions {
+            for j in 0..subdivisions {
+                let a = first + i * columns + j;
+                let b = a + 1;
+                let c = a + columns + 1;
+                let d = a + columns;
+                self.push_triangle(a, b, c);
+                self.push_triangle(a, c, d);
+            }
+        }
+    }
+
+    // 위에서 아래로 쌓인 정점 행(row)들을 잇는다, 각 행은 `segments + 1`개의 정점을 가진다
+    fn add_rows(&mut self, first: u32, rows: u32, segments: u32) {
+        let columns = segments + 1;
+        for i in 0..rows - 1 {
+            for j in 0..segments {
+                let a = first + i * columns + j;
+                let b = a + columns;
+                let c = b + 1;
+                let d = a + 1;
+                self.push_triangle(a, b, c);
+                self.push_triangle(a, c, d);
+            }
+        }
+    }
+
+    fn push_vertex(&mut self, position: glm::Vec3, normal: glm::Vec3, texture_coord: glm::Vec2) {
+        let mut vertex = Vertex::new();
+        vertex.position = position;
+        vertex.normal = normal;
+        vertex.texture_coord = texture_coord;
+        self.vertices.push(vertex);
+    }
+
+    // 극점처럼 두 정점이 겹치는 삼각형은 넓이가 없으므로 건너뛴다
+    fn push_triangle(&mut self, a: u32, b: u32, c: u32) {
+        let [pa, pb, pc] = [a, b, c].map(|index| self.vertices[index as usize].position);
+        if glm::length(&glm::cross(&(pb - pa), &(pc - pa))) <= f32::EPSILON {
+            return;
+        }
+        self.indices.extend_from_slice(&[a, b, c]);
+    }
+
+    fn finish(mut self) -> Self {
+        mesh::generate_tangents(&mut self.vertices, &self.indices);
+        self
+    }
+}
@@ -0,0 +1,284 @@
+use super::{errors, debug_draw};
+use nalgebra_glm as glm;
+use std::{cell::RefCell, collections::VecDeque, fmt::Write, time::Instant};
+
+const HISTORY_SIZE: usize = 240; // 보관하는 프레임 수
+const MAX_PENDING_FRAMES: usize = 4; // GPU 결과를 이만큼 늦게 읽어서 기다리지 않는다
+
+// 프레임 안의 이름 붙은 구간, 부모 구간 안에 중첩될 수 있다
+#[derive(Clone)]
+pub struct Section {
+    pub name: String,
+    pub depth: usize,
+    pub cpu_start: f64, // 프로파일러를 만든 뒤로 지난 시간(ms)
+    pub cpu_duration: f64, // ms
+    pub gpu_start: Option<f64>, // GPU 시각(ms), 결과를 읽기 전이나 GPU 측정을 끄면 None
+    pub gpu_duration: Option<f64>,
+    queries: Option<(u32, u32)>, // 시작과 끝의 timestamp 쿼리
+}
+
+#[derive(Clone)]
+pub struct Frame {
+    pub index: u64,
+    pub cpu_start: f64,
+    pub cpu_duration: f64,
+    pub sections: Vec<Section>,
+}
+
+struct Profiler {
+    enabled: bool,
+    gpu_enabled: bool,
+    origin: Instant,
+    frame_index: u64,
+    current: Option<Frame>,
+    stack: Vec<usize>, // 열려 있는 구간의 번호
+    pending: VecDeque<Frame>, // GPU 결과를 기다리는 프레임
+    history: VecDeque<Frame>,
+    free_queries: Vec<u32>,
+}
+
+impl Profiler {
+    fn new() -> Self {
+        Self { enabled: true, gpu_enabled: true, origin: Instant::now(), frame_index: 0, current: None, stack: Vec::new(), pending: VecDeque::new(), history: VecDeque::new(), free_queries: Vec::new() }
+    }
+
+    fn now(&self) -> f64 {
+        self.origin.elapsed().as_secs_f64() * 1000.0
+    }
+
+    // GL_TIME_ELAPSED 쿼리는 중첩할 수 없으므로 구간의 시작과 끝에 timestamp를 기록해서 차이를 구한다
+    fn query_timestamp(&mut self) -> u32 {
+        let query = self.free_queries.pop().unwrap_or_else(|| {
+            let mut query = 0;
+            unsafe {
+                gl::GenQueries(1, &mut query);
+            }
+            query
+        });
+        unsafe {
+            gl::QueryCounter(query, gl::TIMESTAMP);
+        }
+        query
+    }
+
+    // 결과가 준비된 프레임만 기록으로 옮긴다, 너무 밀리면 가장 오래된 프레임은 기다려서 읽는다
+    fn collect(&mut self) {
+        while let Some(frame) = self.pending.front() {
+            let ready = frame.sections.iter().filter_map(|section| section.queries).all(|(_, end)| {
+                let mut available = 0;
+                unsafe {
+                    gl::GetQueryObjectiv(end, gl::QUERY_RESULT_AVAILABLE, &mut available);
+                }
+                available != 0
+            });
+            if !ready && self.pending.len() <= MAX_PENDING_FRAMES {
+                break;
+            }
+            let mut frame = self.pending.pop_front().unwrap();
+            for section in &mut frame.sections {
+                let Some((start, end)) = section.queries.take() else {
+                    continue;
+                };
+                let (mut start_time, mut end_time) = (0u64, 0u64);
+                unsafe {
+                    gl::GetQueryObjectui64v(start, gl::QUERY_RESULT, &mut start_time);
+                    gl::GetQueryObjectui64v(end, gl::QUERY_RESULT, &mut end_time);
+                }
+                section.gpu_start = Some(start_time as f64 / 1_000_000.0);
+                section.gpu_duration = Some(end_time.saturating_sub(start_time) as f64 / 1_000_000.0);
+                self.free_queries.extend([start, end]);
+            }
+            self.history.push_back(frame);
+            if HISTORY_SIZE < self.history.len() {
+                self.history.pop_front();
+            }
+        }
+    }
+}
+
+thread_local! {
+    // 어디서든 구간을 열고 닫을 수 있도록 스레드마다 하나를 둔다, GPU 쿼리를 사용하므로 OpenGL 컨텍스트의 스레드에서만 사용한다
+    static PROFILER: RefCell<Profiler> = RefCell::new(Profiler::new());
+}
+
+pub fn set_enabled(enabled: bool) {
+    PROFILER.with_borrow_mut(|profiler| profiler.enabled = enabled);
+}
+
+pub fn set_gpu_enabled(enabled: bool) {
+    PROFILER.with_borrow_mut(|profiler| profiler.gpu_enabled = enabled);
+}
+
+pub fn begin_frame() {
+    PROFILER.with_borrow_mut(|profiler| {
+        if !profiler.enabled {
+            return;
+        }
+        let cpu_start = profiler.now();
+        profiler.frame_index += 1;
+        profiler.current = Some(Frame { index: profiler.frame_index, cpu_start, cpu_duration: 0.0, sections: Vec::new() });
+        profiler.stack.clear();
+    });
+}
+
+// 닫히지 않은 구간은 프레임의 끝에서 닫는다
+pub fn end_frame() {
+    while PROFILER.with_borrow(|profiler| !profiler.stack.is_empty()) {
+        end();
+    }
+    PROFILER.with_borrow_mut(|profiler| {
+        let now = profiler.now();
+        let Some(mut frame) = profiler.current.take() else {
+            return;
+        };
+        frame.cpu_duration = now - frame.cpu_start;
+        profiler.pending.push_back(frame);
+        profiler.collect();
+    });
+}
+
+pub fn begin(name: &str) {
+    PROFILER.with_borrow_mut(|profiler| {
+        if profiler.current.is_none() {
+            return;
+        }
+        let cpu_start = profiler.now();
+        let queries = profiler.gpu_enabled.then(|| (profiler.query_timestamp(), 0));
+        let depth = profiler.stack.len();
+        let frame = profiler.current.as_mut().unwrap();
+        frame.sections.push(Section { name: name.to_owned(), depth, cpu_start, cpu_duration: 0.0, gpu_start: None, gpu_duration: None, queries });
+        let index = frame.sections.len() - 1;
+        profiler.stack.push(index);
+    });
+}
+
+pub fn end() {
+    PROFILER.with_borrow_mut(|profiler| {
+        let Some(index) = profiler.stack.pop() else {
+            return;
+        };
+        let now = profiler.now();
+        let end_query = profiler.current.as_ref().and_then(|frame| frame.sections[index].queries).map(|_| profiler.query_timestamp());
+        let Some(frame) = profiler.current.as_mut() else {
+            return;
+        };
+        let section = &mut frame.sections[index];
+        section.cpu_duration = now - section.cpu_start;
+        if let (Some(queries), Some(end_query)) = (section.queries.as_mut(), end_query) {
+            queries.1 = end_query;
+        }
+    });
+}
+
+// 범위를 벗어날 때 구간을 닫는다
+pub struct Scope;
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        end();
+    }
+}
+
+pub fn scope(name: &str) -> Scope {
+    begin(name);
+    Scope
+}
+
+pub fn get_history() -> Vec<Frame> {
+    PROFILER.with_borrow(|profiler| profiler.history.iter().cloned().collect())
+}
+
+// 기록된 프레임 전체에서 구간 이름별 평균 시간(ms)을 로그로 남긴다
+pub fn log_summary() {
+    let history = get_history();
+    if history.is_empty() {
+        return;
+    }
+    let mut names: Vec<(String, usize)> = Vec::new();
+    for section in history.iter().flat_map(|frame| &frame.sections) {
+        if !names.iter().any(|(name, _)| *name == section.name) {
+            names.push((section.name.clone(), section.depth));
+        }
+    }
+    let frame_average = history.iter().map(|frame| frame.cpu_duration).sum::<f64>() / history.len() as f64;
+    spdlog::info!("Profile of {} frames, frame {:.3} ms", history.len(), frame_average);
+    for (name, depth) in names {
+        let sections: Vec<&Section> = history.iter().flat_map(|frame| &frame.sections).filter(|section| section.name == name).collect();
+        let cpu = sections.iter().map(|section| section.cpu_duration).sum::<f64>() / history.len() as f64;
+        let gpu: Vec<f64> = sections.iter().filter_map(|section| section.gpu_duration).collect();
+        let gpu = if gpu.is_empty() { "-".to_owned() } else { format!("{:.3} ms", gpu.iter().sum::<f64>() / history.len() as f64) };
+        spdlog::info!("{}{}: cpu {:.3} ms, gpu {}", "  ".repeat(depth + 1), name, cpu, gpu);
+    }
+}
+
+// 가장 최근 프레임의 구간을 막대로 그린다, 막대 길이는 한 프레임(16.6ms)이 width 픽셀이다
+pub fn draw_overlay(position: &glm::Vec2, width: f32) {
+    let Some(frame) = PROFILER.with_borrow(|profiler| profiler.history.back().cloned()) else {
+        return;
+    };
+    let scale = width / (1000.0 / 60.0);
+    let row_height = 6.0;
+    let frame_style = debug_draw::Style::new(glm::vec4(1.0, 1.0, 1.0, 0.6));
+    debug_draw::screen_rect(position, &(position + glm::vec2(width, row_height * 2.0 * (1 + frame.sections.iter().map(|section| section.depth + 1).max().unwrap_or(0)) as f32)), &frame_style);
+    for section in &frame.sections {
+        let x = position.x + ((section.cpu_start - frame.cpu_start) * scale as f64) as f32;
+        let y = position.y + row_height * 2.0 * section.depth as f32 + 2.0;
+        let cpu_style = debug_draw::Style::new(glm::vec4(0.3, 0.8, 1.0, 1.0));
+        debug_draw::screen_rect(&glm::vec2(x, y), &glm::vec2(x + (section.cpu_duration * scale as f64) as f32, y + row_height - 1.0), &cpu_style);
+        if let Some(gpu_duration) = section.gpu_duration {
+            let gpu_style = debug_draw::Style::new(glm::vec4(1.0, 0.5, 0.2, 1.0));
+            debug_draw::screen_rect(&glm::vec2(x, y + row_height), &glm::vec2(x + (gpu_duration * scale as f64) as f32, y + 2.0 * row_height - 1.0), &gpu_style);
+        }
+    }
+}
+
+// chrome://tracing이나 Perfetto에서 열 수 있는 trace event 형식, CPU와 GPU 구간을 다른 스레드로 나눠서 보여준다
+pub fn export_chrome_trace(file_path: &str) -> Result<(), errors::Error> {
+    let history = get_history();
+    let mut events = Vec::new();
+    // GPU 시각은 기준점이 다르므로 각 프레임의 첫 구간을 CPU 시각에 맞춘다
+    for frame in &history {
+        let gpu_origin = frame.sections.iter().find_map(|section| section.gpu_start.map(|gpu_start| gpu_start - (section.cpu_start - frame.cpu_start)));
+        events.push(trace_event(&format!("Frame {}", frame.index), "frame", frame.cpu_start, frame.cpu_duration, 0));
+        for section in &frame.sections {
+            events.push(trace_event(&section.name, "cpu", section.cpu_start, section.cpu_duration, 1));
+            if let (Some(gpu_origin), Some(gpu_start), Some(gpu_duration)) = (gpu_origin, section.gpu_start, section.gpu_duration) {
+                events.push(trace_event(&section.name, "gpu", frame.cpu_start + gpu_start - gpu_origin, gpu_duration, 2));
+            }
+        }
+    }
+    let json = format!("{{\"traceEvents\":[\n{}\n]}}\n", events.join(",\n"));
+    std::fs::write(file_path, json)?;
+    spdlog::info!("Saved profile \"{}\" ({} frames)", file_path, history.len());
+    Ok(())
+}
+
+// ts와 dur은 마이크로초 단위
+fn trace_event(name: &str, category: &str, start: f64, duration: f64, thread: u32) -> String {
+    let mut escaped = String::new();
+    for character in name.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            character if character.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", character as u32);
+            }
+            character => escaped.push(character),
+        }
+    }
+    format!("{{\"name\":\"{}\",\"cat\":\"{}\",\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\"pid\":1,\"tid\":{}}}", escaped, category, start * 1000.0, duration * 1000.0, thread)
+}
+
+// OpenGL 컨텍스트가 사라지기 전에 쿼리 객체를 지운다
+pub fn destroy() {
+    PROFILER.with_borrow_mut(|profiler| {
+        let mut queries = std::mem::take(&mut profiler.free_queries);
+        for frame in profiler.pending.drain(..).chain(profiler.current.take()) {
+            queries.extend(frame.sections.iter().filter_map(|section| section.queries).flat_map(|(start, end)| [start, end]).filter(|query| *query != 0));
+        }
+        unsafe {
+            gl::DeleteQueries(queries.len() as i32, queries.as_ptr());
+        }
+        spdlog::info!("Dropped profiler queries({})", queries.len());
+    });
+}
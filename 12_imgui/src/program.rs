@@ -1,4 +1,4 @@
-use super::{errors, common, shader};
+use super::{errors, common, gl_check, gl_debug, gl_state, shader};
 use nalgebra_glm as glm;
 
 pub struct Program {
@@ -31,26 +31,77 @@ impl Program {
         Ok(Program { program })
     }
 
+    // (파일 경로, 소스)로 만든다, 소스는 로딩 스레드에서 미리 읽어 둘 수 있다
+    pub fn load_from_source(vertex_shader: (&str, String), fragment_shader: (&str, String), defines: &[(&str, String)]) -> Result<Program, errors::Error> {
+        let (vertex_shader_path, vertex_shader_source) = vertex_shader;
+        let (fragment_shader_path, fragment_shader_source) = fragment_shader;
+        let vertex_shader = shader::Shader::create_from_source(vertex_shader_path, vertex_shader_source, gl::VERTEX_SHADER, defines)?;
+        let fragment_shader = shader::Shader::create_from_source(fragment_shader_path, fragment_shader_source, gl::FRAGMENT_SHADER, defines)?;
+        let program = Self::create(vec![&vertex_shader, &fragment_shader])?;
+        program.set_label(&format!("{} + {}", vertex_shader_path, fragment_shader_path));
+        spdlog::info!("Created program({})", program.get());
+        Ok(program)
+    }
+
     pub fn get(&self) -> u32 {
         self.program
     }
 
+    // 드라이버 메시지에 번호 대신 표시되는 이름
+    pub fn set_label(&self, label: &str) {
+        gl_debug::set_label(gl::PROGRAM, self.program, label);
+    }
+
     pub fn use_(&self) {
-        unsafe {
-            gl::UseProgram(self.program);
-        }
+        gl_state::use_program(self.program);
     }
 
+    #[cfg_attr(any(debug_assertions, feature = "gl_check"), track_caller)]
     pub fn set_uniform1i<S>(&self, name: S, value: i32) where S: AsRef<str> {
         unsafe {
             gl::Uniform1i(gl::GetUniformLocation(self.program, name.as_ref().as_ptr().cast()), value); // 프로그램의 전역 변수에 값을 할당
         }
+        gl_check::check("Program::set_uniform1i");
+    }
+
+    #[cfg_attr(any(debug_assertions, feature = "gl_check"), track_caller)]
+    pub fn set_uniform1f<S>(&self, name: S, value: f32) where S: AsRef<str> {
+        unsafe {
+            gl::Uniform1f(gl::GetUniformLocation(self.program, name.as_ref().as_ptr().cast()), value);
+        }
+        gl_check::check("Program::set_uniform1f");
+    }
+
+    #[cfg_attr(any(debug_assertions, feature = "gl_check"), track_caller)]
+    pub fn set_uniform3fv<S>(&self, name: S, value: &glm::Vec3) where S: AsRef<str> {
+        unsafe {
+            gl::Uniform3fv(gl::GetUniformLocation(self.program, name.as_ref().as_ptr().cast()), 1, glm::value_ptr(value).as_ptr());
+        }
+        gl_check::check("Program::set_uniform3fv");
+    }
+
+    #[cfg_attr(any(debug_assertions, feature = "gl_check"), track_caller)]
+    pub fn set_uniform4fv<S>(&self, name: S, value: &glm::Vec4) where S: AsRef<str> {
+        unsafe {
+            gl::Uniform4fv(gl::GetUniformLocation(self.program, name.as_ref().as_ptr().cast()), 1, glm::value_ptr(value).as_ptr());
+        }
+        gl_check::check("Program::set_uniform4fv");
+    }
+
+    #[cfg_attr(any(debug_assertions, feature = "gl_check"), track_caller)]
+    pub fn set_uniform_matrix3fv<S>(&self, name: S, value: &glm::TMat3<f32>) where S: AsRef<str> {
+        unsafe {
+            gl::UniformMatrix3fv(gl::GetUniformLocation(self.program, name.as_ref().as_ptr().cast()), 1, gl::FALSE, glm::value_ptr(value).as_ptr());
+        }
+        gl_check::check("Program::set_uniform_matrix3fv");
     }
 
+    #[cfg_attr(any(debug_assertions, feature = "gl_check"), track_caller)]
     pub fn set_uniform_matrix4fv<S>(&self, name: S, value: &glm::TMat4<f32>) where S: AsRef<str> {
         unsafe {
             gl::UniformMatrix4fv(gl::GetUniformLocation(self.program, name.as_ref().as_ptr().cast()), 1, gl::FALSE, glm::value_ptr(value).as_ptr()); // 프로그램의 전역 변수에 4차원 형렬의 주소값을 할당
         }
+        gl_check::check("Program::set_uniform_matrix4fv");
     }
 }
 
@@ -59,6 +110,7 @@ impl Drop for Program {
         unsafe {
             gl::DeleteProgram(self.program);
         }
+        gl_state::on_program_deleted(self.program);
         spdlog::info!("Dropped program({})", self.program);
     }
 }
\ No newline at end of file
@@ -0,0 +1,165 @@
+use super::{material::Material, scene::DrawItem};
+use nalgebra_glm as glm;
+use std::{collections::HashMap, rc::Rc};
+
+const DEPTH_BITS: u32 = 23;
+const MESH_BITS: u32 = 16;
+const MATERIAL_BITS: u32 = 16;
+const PROGRAM_BITS: u32 = 8;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Pass {
+    Opaque, // 앞에서 뒤로, 상태가 같은 항목끼리 모은다
+    Transparent, // 블렌딩이 올바르도록 뒤에서 앞으로
+}
+
+pub struct RenderItem {
+    pub key: u64,
+    pub pass: Pass,
+    pub draw_item: DrawItem,
+    pub material: Rc<Material>,
+    pub program: u32,
+}
+
+// submit이 각 항목을 그리기 전에 바꿔야 하는 상태
+#[derive(Clone, Copy)]
+pub struct StateChanges {
+    pub program: bool,
+    pub material: bool,
+    pub mesh: bool,
+}
+
+// 마지막으로 제출한 프레임의 통계, saved는 항목마다 모든 상태를 바꿨을 때와 비교해서 줄인 횟수
+#[derive(Clone, Copy)]
+pub struct QueueStats {
+    pub item_count: usize,
+    pub program_changes: usize,
+    pub material_changes: usize,
+    pub mesh_changes: usize,
+    pub saved: usize,
+}
+
+impl QueueStats {
+    pub fn new() -> Self {
+        Self { item_count: 0, program_changes: 0, material_changes: 0, mesh_changes: 0, saved: 0 }
+    }
+}
+
+// 정렬 키의 구성(상위 비트부터)
+// 불투명: pass(1) | program(8) | material(16) | mesh(16) | depth(23)
+// 반투명: pass(1) | 뒤집은 depth(23), 상태보다 그리는 순서가 중요하다
+pub struct RenderQueue {
+    items: Vec<RenderItem>,
+    material_ids: HashMap<*const Material, u64>,
+    mesh_ids: HashMap<u32, u64>,
+    stats: QueueStats,
+}
+
+impl RenderQueue {
+    pub fn new() -> Self {
+        Self { items: Vec::new(), material_ids: HashMap::new(), mesh_ids: HashMap::new(), stats: QueueStats::new() }
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.material_ids.clear();
+        self.mesh_ids.clear();
+        self.stats = QueueStats::new();
+    }
+
+    // 카메라로부터의 거리를 far로 나눠서 깊이 비트에 맞춘다
+    pub fn push(&mut self, draw_item: DrawItem, material: Rc<Material>, program: u32, view: &glm::Mat4, far: f32) {
+        let center = draw_item.get_bounding_sphere().center;
+        let distance = -(view * glm::vec4(center.x, center.y, center.z, 1.0)).z;
+        let depth = get_depth(distance, far);
+        let pass = if material.is_transparent() { Pass::Transparent } else { Pass::Opaque };
+
+        // 키에 들어갈 수 있도록 재질과 메시에 이번 프레임 안에서만 쓰는 작은 번호를 붙인다
+        let next_material_id = self.material_ids.len() as u64;
+        let material_id = *self.material_ids.entry(Rc::as_ptr(&material)).or_insert(next_material_id) & ((1 << MATERIAL_BITS) - 1);
+        let next_mesh_id = self.mesh_ids.len() as u64;
+        let mesh_id = *self.mesh_ids.entry(draw_item.mesh.get_vertex_array().get()).or_insert(next_mesh_id) & ((1 << MESH_BITS) - 1);
+        let program_id = program as u64 & ((1 << PROGRAM_BITS) - 1);
+
+        let key = make_key(pass, program_id, material_id, mesh_id, depth);
+        self.items.push(RenderItem { key, pass, draw_item, material, program });
+    }
+
+    pub fn sort(&mut self) {
+        self.items.sort_by_key(|item| item.key);
+    }
+
+    // pass에 속한 항목을 정렬된 순서로 넘기면서 직전 항목과 달라진 상태만 알려준다, 패스가 바뀌면 모든 상태를 다시 설정한다
+    pub fn submit<F>(&mut self, pass: Pass, mut draw: F) where F: FnMut(&RenderItem, StateChanges) {
+        let mut previous: Option<&RenderItem> = None;
+        for item in self.items.iter().filter(|item| item.pass == pass) {
+            let changes = match previous {
+                Some(previous) => StateChanges {
+                    program: previous.program != item.program,
+                    material: !Rc::ptr_eq(&previous.material, &item.material),
+                    mesh: previous.draw_item.mesh.get_vertex_array().get() != item.draw_item.mesh.get_vertex_array().get(),
+                },
+                None => StateChanges { program: true, material: true, mesh: true },
+            };
+            self.stats.item_count += 1;
+            self.stats.program_changes += changes.program as usize;
+            self.stats.material_changes += changes.material as usize;
+            self.stats.mesh_changes += changes.mesh as usize;
+            self.stats.saved += 3 - (changes.program as usize + changes.material as usize + changes.mesh as usize);
+            draw(item, changes);
+            previous = Some(item);
+        }
+    }
+
+    pub fn get_stats(&self) -> QueueStats {
+        self.stats
+    }
+}
+
+fn get_depth(distance: f32, far: f32) -> u64 {
+    ((distance / far).clamp(0.0, 1.0) * ((1 << DEPTH_BITS) - 1) as f32) as u64
+}
+
+fn make_key(pass: Pass, program_id: u64, material_id: u64, mesh_id: u64, depth: u64) -> u64 {
+    match pass {
+        Pass::Opaque => program_id << (MATERIAL_BITS + MESH_BITS + DEPTH_BITS) | material_id << (MESH_BITS + DEPTH_BITS) | mesh_id << DEPTH_BITS | depth,
+        Pass::Transparent => 1 << 63 | ((1 << DEPTH_BITS) - 1 - depth),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transparent_after_opaque() {
+        let opaque = make_key(Pass::Opaque, (1 << PROGRAM_BITS) - 1, (1 << MATERIAL_BITS) - 1, (1 << MESH_BITS) - 1, get_depth(100.0, 10.0));
+        let transparent = make_key(Pass::Transparent, 0, 0, 0, get_depth(100.0, 10.0));
+        assert!(opaque < transparent);
+    }
+
+    #[test]
+    fn opaque_groups_state_before_depth() {
+        let near = get_depth(1.0, 10.0);
+        let far = get_depth(9.0, 10.0);
+        // 상태가 같으면 앞에서 뒤로
+        assert!(make_key(Pass::Opaque, 1, 2, 3, near) < make_key(Pass::Opaque, 1, 2, 3, far));
+        // 깊이보다 프로그램, 재질, 메시 순서가 먼저다
+        assert!(make_key(Pass::Opaque, 1, 2, 3, far) < make_key(Pass::Opaque, 1, 2, 4, near));
+        assert!(make_key(Pass::Opaque, 1, 2, 4, far) < make_key(Pass::Opaque, 1, 3, 0, near));
+        assert!(make_key(Pass::Opaque, 1, 3, 0, far) < make_key(Pass::Opaque, 2, 0, 0, near));
+    }
+
+    #[test]
+    fn transparent_back_to_front() {
+        let near = make_key(Pass::Transparent, 0, 0, 0, get_depth(1.0, 10.0));
+        let far = make_key(Pass::Transparent, 0, 0, 0, get_depth(9.0, 10.0));
+        assert!(far < near);
+    }
+
+    #[test]
+    fn depth_is_clamped() {
+        assert_eq!(get_depth(-1.0, 10.0), 0);
+        assert_eq!(get_depth(20.0, 10.0), (1 << DEPTH_BITS) - 1);
+    }
+}
@@ -0,0 +1,156 @@
+use super::{errors, image::Image, mesh::Mesh, program::Program, shader::Shader, texture::Texture};
+use std::{cell::RefCell, collections::HashMap, rc::{Rc, Weak}};
+
+// 캐시는 약한 참조만 가지고 있으므로 마지막 Rc가 사라지면 각 타입의 Drop이 GPU 자원을 지운다
+struct Entry<T> {
+    handle: Weak<T>,
+    memory: usize, // 바이트, 알 수 없으면 0
+}
+
+struct Cache<T> {
+    entries: HashMap<String, Entry<T>>,
+}
+
+impl<T> Cache<T> {
+    fn new() -> Self {
+        Self { entries: HashMap::new() }
+    }
+
+    fn get(&self, key: &str) -> Option<Rc<T>> {
+        self.entries.get(key).and_then(|entry| entry.handle.upgrade())
+    }
+
+    fn insert(&mut self, key: &str, handle: &Rc<T>, memory: usize) {
+        self.entries.retain(|_, entry| entry.handle.strong_count() != 0);
+        self.entries.insert(key.to_owned(), Entry { handle: Rc::downgrade(handle), memory });
+    }
+
+    fn collect_infos(&self, kind: ResourceKind, infos: &mut Vec<ResourceInfo>) {
+        for (key, entry) in &self.entries {
+            let ref_count = entry.handle.strong_count();
+            if ref_count != 0 {
+                infos.push(ResourceInfo { kind, key: key.clone(), ref_count, memory: entry.memory });
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum ResourceKind {
+    Shader,
+    Program,
+    Image,
+    Texture,
+    Mesh,
+}
+
+pub struct ResourceInfo {
+    pub kind: ResourceKind,
+    pub key: String,
+    pub ref_count: usize, // 자원을 가지고 있는 Rc의 개수
+    pub memory: usize,
+}
+
+struct Resources {
+    shaders: Cache<Shader>,
+    programs: Cache<Program>,
+    images: Cache<Image>,
+    textures: Cache<Texture>,
+    meshes: Cache<Mesh>,
+}
+
+thread_local! {
+    // GL 객체를 담으므로 OpenGL 컨텍스트의 스레드에서만 사용한다
+    static RESOURCES: RefCell<Resources> = RefCell::new(Resources { shaders: Cache::new(), programs: Cache::new(), images: Cache::new(), textures: Cache::new(), meshes: Cache::new() });
+}
+
+// 같은 파일과 define 조합은 여러 프로그램이 하나의 쉐이더를 함께 사용한다
+pub fn load_shader(file_path: &str, type_: u32, defines: &[(&str, String)]) -> Result<Rc<Shader>, errors::Error> {
+    let key = format!("{} ({:#X}){}", file_path, type_, format_defines(defines));
+    if let Some(shader) = RESOURCES.with_borrow(|resources| resources.shaders.get(&key)) {
+        return Ok(shader);
+    }
+    let shader = Rc::new(Shader::create_with_defines(file_path, type_, defines)?);
+    spdlog::info!("Created shader({}) \"{}\"", shader.get(), file_path);
+    RESOURCES.with_borrow_mut(|resources| resources.shaders.insert(&key, &shader, 0));
+    Ok(shader)
+}
+
+// 같은 쉐이더 파일과 define 조합은 하나의 프로그램을 함께 사용한다
+pub fn load_program(vertex_shader_path: &str, fragment_shader_path: &str, defines: &[(&str, String)]) -> Result<Rc<Program>, errors::Error> {
+    let key = format!("{} + {}{}", vertex_shader_path, fragment_shader_path, format_defines(defines));
+    if let Some(program) = RESOURCES.with_borrow(|resources| resources.programs.get(&key)) {
+        return Ok(program);
+    }
+    let vertex_shader = load_shader(vertex_shader_path, gl::VERTEX_SHADER, defines)?;
+    let fragment_shader = load_shader(fragment_shader_path, gl::FRAGMENT_SHADER, defines)?;
+    let program = Program::create(vec![&vertex_shader, &fragment_shader])?;
+    program.set_label(&format!("{} + {}", vertex_shader_path, fragment_shader_path));
+    spdlog::info!("Created program({})", program.get());
+    let program = Rc::new(program);
+    RESOURCES.with_borrow_mut(|resources| resources.programs.insert(&key, &program, 0));
+    Ok(program)
+}
+
+pub fn load_image(file_path: &str) -> Result<Rc<Image>, errors::Error> {
+    if let Some(image) = RESOURCES.with_borrow(|resources| resources.images.get(file_path)) {
+        return Ok(image);
+    }
+    let image = Rc::new(Image::load(file_path)?);
+    spdlog::info!("Loaded image file \"{}\" ({} x {}, {} channels)", file_path, image.get_width(), image.get_height(), image.get_channel_count());
+    RESOURCES.with_borrow_mut(|resources| resources.images.insert(file_path, &image, image.get_data().len()));
+    Ok(image)
+}
+
+// 여러 곳에서 함께 쓰므로 wrap 옵션도 키에 넣어서 옵션이 다르면 따로 만든다
+pub fn load_texture(file_path: &str, wrap: u32) -> Result<Rc<Texture>, errors::Error> {
+    let key = format!("{} ({:#X})", file_path, wrap);
+    if let Some(texture) = RESOURCES.with_borrow(|resources| resources.textures.get(&key)) {
+        return Ok(texture);
+    }
+    let image = load_image(file_path)?;
+    let texture = Texture::create();
+    texture.set_label(file_path).set_wrap(wrap, wrap).set_texture(&image);
+    let texture = Rc::new(texture);
+    // RGBA8로 올리고 mipmap이 원본의 1/3 정도를 더 차지한다
+    let memory = image.get_width() as usize * image.get_height() as usize * 4 * 4 / 3;
+    RESOURCES.with_borrow_mut(|resources| resources.textures.insert(&key, &texture, memory));
+    Ok(texture)
+}
+
+// 파일이 아닌 코드로 만드는 메시는 "primitive/cube"처럼 키를 정해서 처음 한 번만 create로 만든다
+pub fn get_mesh<F>(key: &str, create: F) -> Rc<Mesh> where F: FnOnce() -> Mesh {
+    if let Some(mesh) = RESOURCES.with_borrow(|resources| resources.meshes.get(key)) {
+        return mesh;
+    }
+    let mesh = Rc::new(create());
+    mesh.set_label(key);
+    RESOURCES.with_borrow_mut(|resources| resources.meshes.insert(key, &mesh, mesh.get_memory_size()));
+    mesh
+}
+
+// 아직 살아 있는 자원을 종류와 키 순서로 돌려준다
+pub fn get_infos() -> Vec<ResourceInfo> {
+    let mut infos = Vec::new();
+    RESOURCES.with_borrow(|resources| {
+        resources.shaders.collect_infos(ResourceKind::Shader, &mut infos);
+        resources.programs.collect_infos(ResourceKind::Program, &mut infos);
+        resources.images.collect_infos(ResourceKind::Image, &mut infos);
+        resources.textures.collect_infos(ResourceKind::Texture, &mut infos);
+        resources.meshes.collect_infos(ResourceKind::Mesh, &mut infos);
+    });
+    infos.sort_by(|a, b| (a.kind, &a.key).cmp(&(b.kind, &b.key)));
+    infos
+}
+
+pub fn log_usage() {
+    let infos = get_infos();
+    spdlog::info!("Loaded resources({}), {:.1} KiB", infos.len(), infos.iter().map(|info| info.memory).sum::<usize>() as f32 / 1024.0);
+    for info in &infos {
+        spdlog::info!("  {:?} \"{}\": refs {}, {:.1} KiB", info.kind, info.key, info.ref_count, info.memory as f32 / 1024.0);
+    }
+}
+
+fn format_defines(defines: &[(&str, String)]) -> String {
+    defines.iter().map(|(name, value)| format!(" {}={}", name, value)).collect()
+}
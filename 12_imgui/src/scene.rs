@@ -0,0 +1,230 @@
+use super::{light::Light, material::Material, mesh::{BoundingBox, BoundingSphere, Mesh}};
+use nalgebra_glm as glm;
+use std::{cell::RefCell, rc::{Rc, Weak}};
+
+pub struct Node {
+    // property
+    pub name: String,
+    translation: glm::Vec3,
+    rotation: glm::Quat,
+    scale: glm::Vec3,
+
+    // hierarchy
+    parent: Weak<RefCell<Self>>,
+    pub children: Vec<Rc<RefCell<Self>>>,
+    world_matrix: glm::Mat4,
+    dirty: bool,
+
+    // component
+    pub mesh: Option<Rc<Mesh>>,
+    pub material: Option<Rc<Material>>,
+    pub light: Option<Light>,
+}
+
+impl Node {
+    pub fn create(name: &str) -> Rc<RefCell<Self>> {
+        // property
+        let name = name.to_owned();
+        let translation = glm::vec3(0.0, 0.0, 0.0);
+        let rotation = glm::quat_identity();
+        let scale = glm::vec3(1.0, 1.0, 1.0);
+
+        // hierarchy
+        let parent = Weak::new();
+        let children = Vec::new();
+        let world_matrix = glm::Mat4::identity();
+        let dirty = true;
+
+        Rc::new(RefCell::new(Self { name, translation, rotation, scale, parent, children, world_matrix, dirty, mesh: None, material: None, light: None }))
+    }
+
+    // 부모 노드의 Rc가 필요하므로 연관 함수로 만든다
+    pub fn add_child(parent: &Rc<RefCell<Self>>, child: Rc<RefCell<Self>>) -> Rc<RefCell<Self>> {
+        Self::remove_from_parent(&child);
+        child.borrow_mut().parent = Rc::downgrade(parent);
+        child.borrow_mut().mark_dirty();
+        parent.borrow_mut().children.push(child.clone());
+        child
+    }
+
+    pub fn remove_from_parent(node: &Rc<RefCell<Self>>) {
+        let parent = node.borrow().parent.upgrade();
+        if let Some(parent) = parent {
+            parent.borrow_mut().children.retain(|child| !Rc::ptr_eq(child, node));
+            node.borrow_mut().parent = Weak::new();
+            node.borrow_mut().mark_dirty();
+        }
+    }
+
+    pub fn get_parent(&self) -> Option<Rc<RefCell<Self>>> {
+        self.parent.upgrade()
+    }
+
+    pub fn get_translation(&self) -> glm::Vec3 {
+        self.translation
+    }
+
+    pub fn get_rotation(&self) -> glm::Quat {
+        self.rotation
+    }
+
+    pub fn get_scale(&self) -> glm::Vec3 {
+        self.scale
+    }
+
+    pub fn set_translation(&mut self, translation: glm::Vec3) -> &mut Self {
+        self.translation = translation;
+        self.mark_dirty();
+        self
+    }
+
+    pub fn set_rotation(&mut self, rotation: glm::Quat) -> &mut Self {
+        self.rotation = rotation;
+        self.mark_dirty();
+        self
+    }
+
+    pub fn set_scale(&mut self, scale: glm::Vec3) -> &mut Self {
+        self.scale = scale;
+        self.mark_dirty();
+        self
+    }
+
+    pub fn set_mesh(&mut self, mesh: Rc<Mesh>) -> &mut Self {
+        self.mesh = Some(mesh);
+        self
+    }
+
+    pub fn set_material(&mut self, material: Rc<Material>) -> &mut Self {
+        self.material = Some(material);
+        self
+    }
+
+    pub fn set_light(&mut self, light: Light) -> &mut Self {
+        self.light = Some(light);
+        self
+    }
+
+    // 확대, 회전, 평행 이동 순으로 적용하는 행렬
+    pub fn get_local_matrix(&self) -> glm::Mat4 {
+        glm::translation(&self.translation) * glm::quat_to_mat4(&self.rotation) * glm::scaling(&self.scale)
+    }
+
+    // 변경된 노드와 그 자식들의 world 행렬을 다시 계산하도록 표시한다
+    pub fn mark_dirty(&mut self) {
+        if self.dirty {
+            return;
+        }
+        self.dirty = true;
+        for child in &self.children {
+            child.borrow_mut().mark_dirty();
+        }
+    }
+
+    pub fn get_world_matrix(&mut self) -> glm::Mat4 {
+        if self.dirty {
+            let parent_matrix = match self.parent.upgrade() {
+                Some(parent) => parent.borrow_mut().get_world_matrix(),
+                None => glm::Mat4::identity(),
+            };
+            self.world_matrix = parent_matrix * self.get_local_matrix();
+            self.dirty = false;
+        }
+        self.world_matrix
+    }
+
+    pub fn find(node: &Rc<RefCell<Self>>, name: &str) -> Option<Rc<RefCell<Self>>> {
+        if node.borrow().name == name {
+            return Some(node.clone());
+        }
+        node.borrow().children.iter().find_map(|child| Self::find(child, name))
+    }
+
+    // 부모의 world 행렬을 내려보내며 캐시를 갱신하고, 그리기 목록과 광원 목록을 모은다
+    fn traverse(node: &Rc<RefCell<Self>>, parent_matrix: &glm::Mat4, parent_updated: bool, draw_items: &mut Vec<DrawItem>, lights: &mut Vec<LightItem>) {
+        let mut current = node.borrow_mut();
+        let updated = parent_updated || current.dirty;
+        if updated {
+            current.world_matrix = parent_matrix * current.get_local_matrix();
+            current.dirty = false;
+        }
+        let world_matrix = current.world_matrix;
+        if let Some(mesh) = &current.mesh {
+            draw_items.push(DrawItem { node: node.clone(), mesh: mesh.clone(), material: current.material.clone(), world_matrix });
+        }
+        if let Some(light) = current.light {
+            lights.push(LightItem { light, world_matrix });
+        }
+        let children = current.children.clone();
+        drop(current);
+        for child in &children {
+            Self::traverse(child, &world_matrix, updated, draw_items, lights);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DrawItem {
+    pub node: Rc<RefCell<Node>>,
+    pub mesh: Rc<Mesh>,
+    pub material: Option<Rc<Material>>,
+    pub world_matrix: glm::Mat4,
+}
+
+impl DrawItem {
+    // normal을 world 공간으로 옮기는 행렬, 비균등 확대가 있어도 면에 수직이 되도록 역행렬의 전치 행렬을 사용한다
+    pub fn get_normal_matrix(&self) -> glm::Mat3 {
+        glm::mat4_to_mat3(&glm::transpose(&glm::inverse(&self.world_matrix)))
+    }
+
+    pub fn get_bounding_box(&self) -> BoundingBox {
+        self.mesh.get_bounding_box().transform(&self.world_matrix)
+    }
+
+    pub fn get_bounding_sphere(&self) -> BoundingSphere {
+        self.mesh.get_bounding_sphere().transform(&self.world_matrix)
+    }
+}
+
+pub struct LightItem {
+    pub light: Light,
+    pub world_matrix: glm::Mat4,
+}
+
+impl LightItem {
+    pub fn get_position(&self) -> glm::Vec3 {
+        self.world_matrix.column(3).xyz()
+    }
+
+    // 빛은 노드의 -z 방향으로 나아간다
+    pub fn get_direction(&self) -> glm::Vec3 {
+        glm::normalize(&(self.world_matrix * glm::vec4(0.0, 0.0, -1.0, 0.0)).xyz())
+    }
+}
+
+pub struct Scene {
+    pub root: Rc<RefCell<Node>>,
+}
+
+impl Scene {
+    pub fn create() -> Self {
+        let root = Node::create("root");
+        Self { root }
+    }
+
+    pub fn add_node(&self, name: &str) -> Rc<RefCell<Node>> {
+        Node::add_child(&self.root, Node::create(name))
+    }
+
+    pub fn find(&self, name: &str) -> Option<Rc<RefCell<Node>>> {
+        Node::find(&self.root, name)
+    }
+
+    // 장면을 순회하며 world 행렬을 갱신하고 그려야 할 메시 목록을 만든다
+    pub fn collect(&self) -> (Vec<DrawItem>, Vec<LightItem>) {
+        let mut draw_items = Vec::new();
+        let mut lights = Vec::new();
+        Node::traverse(&self.root, &glm::Mat4::identity(), false, &mut draw_items, &mut lights);
+        (draw_items, lights)
+    }
+}
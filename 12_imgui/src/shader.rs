@@ -1,15 +1,28 @@
-use super::{errors, common};
+use super::{errors, asset, common, gl_debug};
 
 pub struct Shader {
     shader: u32,
 }
 
 impl Shader {
-    pub fn create<S>(file_path: S, type_: u32) -> Result<Shader, errors::Error> where S: AsRef<str> {
+    // `#version` 바로 다음 줄에 `#define 이름 값`을 넣어서 컴파일한다
+    pub fn create_with_defines<S>(file_path: S, type_: u32, defines: &[(&str, String)]) -> Result<Shader, errors::Error> where S: AsRef<str> {
+        // Load shader file
+        let text = Self::load_source(file_path.as_ref())?;
+        Self::create_from_source(file_path.as_ref(), text, type_, defines)
+    }
+
+    // load_source로 미리 읽어 둔 소스를 컴파일한다, file_path는 디버그 이름으로만 사용한다
+    pub fn create_from_source(file_path: &str, mut text: String, type_: u32, defines: &[(&str, String)]) -> Result<Shader, errors::Error> {
         let shader;
 
-        // Load shader file
-        let text = std::fs::read(file_path.as_ref())?;
+        if !defines.is_empty() {
+            let position = if text.starts_with("#version") { text.find('\n').map(|index| index + 1).unwrap_or(text.len()) } else { 0 };
+            let mut header: String = defines.iter().map(|(name, value)| format!("#define {} {}\n", name, value)).collect();
+            // 컴파일 오류의 줄 번호가 원본 파일과 같도록 되돌린다
+            header += &format!("#line {}\n", if position == 0 { 1 } else { 2 });
+            text.insert_str(position, &header);
+        }
         let text_len = text.len() as i32;
 
         // Create and compile shader
@@ -31,9 +44,37 @@ impl Shader {
             }
         }
 
+        gl_debug::set_label(gl::SHADER, shader, file_path);
         Ok(Shader { shader })
     }
 
+    // `#include "파일"` 줄을 같은 폴더에 있는 파일의 내용으로 바꾼다
+    // OpenGL을 사용하지 않으므로 로딩 스레드에서 호출할 수 있다
+    pub fn load_source(file_path: &str) -> Result<String, errors::Error> {
+        let text = asset::read_to_string(file_path)?;
+        if !text.contains("#include") {
+            return Ok(text);
+        }
+        let directory = std::path::Path::new(file_path).parent().unwrap_or(std::path::Path::new(""));
+        let mut source = String::new();
+        for (number, line) in text.lines().enumerate() {
+            match line.trim().strip_prefix("#include") {
+                Some(name) => {
+                    let include_path = directory.join(name.trim().trim_matches('"'));
+                    source += "#line 1\n";
+                    source += &Self::load_source(&include_path.to_string_lossy())?;
+                    // 포함한 파일 다음 줄부터 원본 파일의 줄 번호로 되돌린다
+                    source += &format!("\n#line {}\n", number + 2);
+                }
+                None => {
+                    source += line;
+                    source += "\n";
+                }
+            }
+        }
+        Ok(source)
+    }
+
     pub fn get(&self) -> u32 {
         self.shader
     }
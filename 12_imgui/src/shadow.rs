@@ -0,0 +1,233 @@
+use super::{errors, resource, gl_state, camera::Camera, framebuffer::Framebuffer, light::LightType, program::Program, scene::{DrawItem, LightItem}, texture::Texture, vertex_array::VertexArray};
+use nalgebra_glm as glm;
+use std::rc::Rc;
+
+// 셰이더의 shadow_matrices 배열 크기, 프로그램을 만들 때 MAX_SHADOW_COUNT로 주입한다
+pub const MAX_SHADOW_COUNT: usize = 8;
+pub const MAX_CASCADE_COUNT: usize = 4;
+
+#[derive(Clone, Copy)]
+pub struct ShadowSettings {
+    pub resolution: u32, // 그림자 맵 하나의 해상도
+    pub bias: f32, // 깊이 비교에 더하는 값, 표면이 스스로 그림자를 만드는 현상(shadow acne)을 막는다
+    pub normal_bias: f32, // 표면을 normal 방향으로 밀어낸 뒤에 비교한다
+    pub pcf_radius: i32, // (2r + 1)^2개의 텍셀을 비교해서 평균 낸다, 0이면 경계가 딱딱하다
+    pub cascade_count: usize, // directional 광원이 카메라 절두체를 나눠 덮는 그림자 맵 개수
+    pub cascade_lambda: f32, // 0이면 균등 분할, 1이면 로그 분할
+    pub max_distance: f32, // directional 광원의 그림자가 보이는 카메라로부터의 거리
+    pub caster_distance: f32, // 절두체 밖에서도 그림자를 드리울 수 있도록 광원 쪽으로 늘리는 거리
+    pub max_spot_shadow_count: usize,
+}
+
+impl ShadowSettings {
+    pub fn new() -> Self {
+        Self { resolution: 1024, bias: 0.002, normal_bias: 0.02, pcf_radius: 1, cascade_count: 1, cascade_lambda: 0.75, max_distance: 20.0, caster_distance: 10.0, max_spot_shadow_count: 4 }
+    }
+}
+
+// 모든 그림자 맵을 하나의 깊이 텍스쳐에 타일로 나눠 담는다
+pub struct ShadowMap {
+    pub settings: ShadowSettings,
+    framebuffer: Framebuffer,
+    atlas: Texture,
+    columns: usize,
+    rows: usize,
+    program: Rc<Program>,
+    debug_program: Rc<Program>,
+    debug_vertex_array: VertexArray,
+    shadow_matrices: Vec<glm::Mat4>, // world 공간에서 atlas의 (u, v, depth)로 옮기는 행렬
+    cascade_splits: Vec<f32>,
+}
+
+impl ShadowMap {
+    pub fn create(settings: ShadowSettings) -> Result<ShadowMap, errors::Error> {
+        let mut settings = settings;
+        settings.cascade_count = settings.cascade_count.clamp(1, MAX_CASCADE_COUNT);
+        settings.max_spot_shadow_count = settings.max_spot_shadow_count.min(MAX_SHADOW_COUNT - settings.cascade_count);
+
+        let tile_count = settings.cascade_count + settings.max_spot_shadow_count;
+        let columns = (tile_count as f32).sqrt().ceil() as usize;
+        let rows = tile_count.div_ceil(columns);
+
+        let atlas = Texture::create();
+        atlas.set_label("shadow atlas");
+        atlas.set_storage(settings.resolution * columns as u32, settings.resolution * rows as u32, gl::DEPTH_COMPONENT24, gl::DEPTH_COMPONENT, gl::FLOAT);
+        atlas.set_filter(gl::NEAREST, gl::NEAREST).set_wrap(gl::CLAMP_TO_EDGE, gl::CLAMP_TO_EDGE); // 깊이 값끼리 보간하면 비교 결과가 틀어진다
+        let framebuffer = Framebuffer::create();
+        framebuffer.attach_texture(gl::DEPTH_ATTACHMENT, gl::TEXTURE_2D, atlas.get(), 0).set_no_color_buffer().check()?;
+        Framebuffer::unbind();
+
+        let program = resource::load_program("shader/shadow.vert", "shader/shadow.frag", &[])?;
+        let debug_program = resource::load_program("shader/fullscreen.vert", "shader/shadow_debug.frag", &[])?;
+        let debug_vertex_array = VertexArray::create();
+        spdlog::info!("Created shadow map ({} x {} tiles of {} x {})", columns, rows, settings.resolution, settings.resolution);
+
+        Ok(ShadowMap { settings, framebuffer, atlas, columns, rows, program, debug_program, debug_vertex_array, shadow_matrices: Vec::new(), cascade_splits: Vec::new() })
+    }
+
+    // 그림자를 만드는 광원마다 깊이만 그리고, lights와 같은 순서로 각 광원의 첫 번째 그림자 맵 번호를 돌려준다
+    pub fn render(&mut self, draw_items: &[DrawItem], lights: &[LightItem], camera: &Camera, aspect: f32) -> Vec<i32> {
+        self.shadow_matrices.clear();
+        self.cascade_splits.clear();
+        let mut light_views = Vec::new(); // 각 타일의 광원 시점 projection * view
+        let mut shadow_indices = vec![-1; lights.len()];
+        let mut has_directional = false;
+        let mut spot_count = 0;
+
+        for (index, item) in lights.iter().enumerate() {
+            if !item.light.enabled || !item.light.cast_shadow {
+                continue;
+            }
+            match item.light.type_ {
+                // 첫 번째 directional 광원만 캐스케이드를 사용한다
+                LightType::Directional if !has_directional => {
+                    has_directional = true;
+                    shadow_indices[index] = light_views.len() as i32;
+                    let far = self.settings.max_distance.min(camera.get_far());
+                    self.cascade_splits = compute_cascade_splits(camera.get_near(), far, self.settings.cascade_count, self.settings.cascade_lambda);
+                    let mut near = camera.get_near();
+                    for split in self.cascade_splits.clone() {
+                        light_views.push(self.fit_directional(&item.get_direction(), &camera.get_frustum_corners(aspect, near, split)));
+                        near = split;
+                    }
+                }
+                LightType::Spot if spot_count < self.settings.max_spot_shadow_count => {
+                    spot_count += 1;
+                    shadow_indices[index] = light_views.len() as i32;
+                    let position = item.get_position();
+                    let direction = item.get_direction();
+                    let up = if direction.y.abs() < 0.999 { glm::vec3(0.0, 1.0, 0.0) } else { glm::vec3(1.0, 0.0, 0.0) };
+                    let view = glm::look_at(&position, &(position + direction), &up);
+                    let far = item.light.get_range().min(100.0);
+                    let projection = glm::perspective(1.0, (2.0 * item.light.outer_angle).to_radians().min(179.0f32.to_radians()), 0.05, far);
+                    light_views.push(projection * view);
+                }
+                _ => {}
+            }
+        }
+
+        let viewport = gl_state::get_viewport();
+        self.framebuffer.bind();
+        gl_state::set_enabled(gl::DEPTH_TEST, true);
+        unsafe {
+            gl::Clear(gl::DEPTH_BUFFER_BIT);
+        }
+        self.program.use_();
+        let resolution = self.settings.resolution.cast_signed();
+        for (tile, light_view) in light_views.iter().enumerate() {
+            let (column, row) = (tile % self.columns, tile / self.columns);
+            gl_state::set_viewport(column as i32 * resolution, row as i32 * resolution, resolution, resolution);
+            for draw_item in draw_items {
+                self.program.set_uniform_matrix4fv("transform\0", &(light_view * draw_item.world_matrix));
+                draw_item.mesh.draw();
+            }
+            // NDC(-1 ~ 1)를 타일 영역의 텍스쳐 좌표와 깊이(0 ~ 1)로 옮긴다
+            let tile_scale = glm::vec3(0.5 / self.columns as f32, 0.5 / self.rows as f32, 0.5);
+            let tile_offset = glm::vec3((column as f32 + 0.5) / self.columns as f32, (row as f32 + 0.5) / self.rows as f32, 0.5);
+            self.shadow_matrices.push(glm::translation(&tile_offset) * glm::scaling(&tile_scale) * light_view);
+        }
+        Framebuffer::unbind();
+        gl_state::set_viewport(viewport[0], viewport[1], viewport[2], viewport[3]);
+
+        shadow_indices
+    }
+
+    // 절두체 조각을 감싸는 구에 맞춰 직교 투영을 만든다, 구를 쓰면 카메라가 회전해도 크기가 변하지 않아 그림자가 떨리지 않는다
+    fn fit_directional(&self, direction: &glm::Vec3, corners: &[glm::Vec3; 8]) -> glm::Mat4 {
+        let center = corners.iter().fold(glm::vec3(0.0, 0.0, 0.0), |sum, corner| sum + corner) / 8.0;
+        let radius = corners.iter().map(|corner| glm::distance(corner, &center)).fold(0.0f32, f32::max);
+        let up = if direction.y.abs() < 0.999 { glm::vec3(0.0, 1.0, 0.0) } else { glm::vec3(1.0, 0.0, 0.0) };
+        let light_view = glm::look_at(&glm::vec3(0.0, 0.0, 0.0), direction, &up);
+
+        // 중심을 텍셀 단위로 맞춰서 카메라가 움직일 때 그림자 경계가 반짝이지 않도록 한다
+        let texel_size = 2.0 * radius / self.settings.resolution as f32;
+        let mut center_light = (light_view * glm::vec4(center.x, center.y, center.z, 1.0)).xyz();
+        center_light.x = (center_light.x / texel_size).floor() * texel_size;
+        center_light.y = (center_light.y / texel_size).floor() * texel_size;
+        let near = -center_light.z - radius - self.settings.caster_distance;
+        let far = -center_light.z + radius;
+        let projection = glm::ortho(center_light.x - radius, center_light.x + radius, center_light.y - radius, center_light.y + radius, near, far);
+        projection * light_view
+    }
+
+    // 그림자 맵을 unit번에 바인딩하고 셰이더의 그림자 전역 변수를 채운다
+    pub fn bind(&self, program: &Program, unit: u32, view: &glm::Mat4) {
+        program.set_uniform1i("shadow_map\0", unit.cast_signed());
+        program.set_uniform1f("shadow_bias\0", self.settings.bias);
+        program.set_uniform1f("shadow_normal_bias\0", self.settings.normal_bias);
+        program.set_uniform1i("shadow_pcf_radius\0", self.settings.pcf_radius);
+        program.set_uniform_matrix4fv("view_matrix\0", view);
+        program.set_uniform1i("cascade_count\0", self.cascade_splits.len() as i32);
+        for (index, split) in self.cascade_splits.iter().enumerate() {
+            program.set_uniform1f(format!("cascade_splits[{}]\0", index), *split);
+        }
+        for (tile, shadow_matrix) in self.shadow_matrices.iter().enumerate() {
+            let (column, row) = ((tile % self.columns) as f32, (tile / self.columns) as f32);
+            let bounds = glm::vec4(column / self.columns as f32, row / self.rows as f32, (column + 1.0) / self.columns as f32, (row + 1.0) / self.rows as f32);
+            program.set_uniform_matrix4fv(format!("shadow_matrices[{}]\0", tile), shadow_matrix);
+            program.set_uniform4fv(format!("shadow_tiles[{}]\0", tile), &bounds);
+        }
+        gl_state::active_texture(unit);
+        self.atlas.bind();
+    }
+
+    // 화면 왼쪽 아래에 그림자 맵 전체를 흑백으로 보여준다
+    pub fn draw_debug(&self, width: u32, height: u32) {
+        let size = (width.min(height) / 3).cast_signed();
+        let aspect = self.columns as f32 / self.rows as f32;
+        gl_state::set_viewport(0, 0, (size as f32 * aspect) as i32, size);
+        gl_state::set_enabled(gl::DEPTH_TEST, false);
+        gl_state::active_texture(0);
+        self.atlas.bind();
+        self.debug_program.use_();
+        self.debug_program.set_uniform1i("shadow_map\0", 0);
+        self.debug_vertex_array.bind();
+        unsafe {
+            gl::DrawArrays(gl::TRIANGLES, 0, 3);
+        }
+        gl_state::set_enabled(gl::DEPTH_TEST, true);
+        gl_state::set_viewport(0, 0, width.cast_signed(), height.cast_signed());
+    }
+}
+
+// 균등 분할과 로그 분할을 lambda로 섞어서 각 캐스케이드의 먼 쪽 거리를 구한다
+fn compute_cascade_splits(near: f32, far: f32, count: usize, lambda: f32) -> Vec<f32> {
+    (1..=count).map(|index| {
+        let ratio = index as f32 / count as f32;
+        let logarithmic = near * (far / near).powf(ratio);
+        let uniform = near + (far - near) * ratio;
+        lambda * logarithmic + (1.0 - lambda) * uniform
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cascade_splits_end_at_far() {
+        for lambda in [0.0, 0.5, 1.0] {
+            let splits = compute_cascade_splits(0.1, 50.0, 4, lambda);
+            assert_eq!(splits.len(), 4);
+            assert!((splits[3] - 50.0).abs() < 1e-3);
+            assert!(splits.windows(2).all(|pair| pair[0] < pair[1]));
+            assert!(0.1 < splits[0]);
+        }
+    }
+
+    #[test]
+    fn cascade_splits_blend_uniform_and_logarithmic() {
+        let uniform = compute_cascade_splits(1.0, 16.0, 4, 0.0);
+        let logarithmic = compute_cascade_splits(1.0, 16.0, 4, 1.0);
+        let blended = compute_cascade_splits(1.0, 16.0, 4, 0.5);
+        for (index, expected) in [4.75, 8.5, 12.25, 16.0].iter().enumerate() {
+            assert!((uniform[index] - expected).abs() < 1e-4);
+        }
+        for (index, expected) in [2.0, 4.0, 8.0, 16.0].iter().enumerate() {
+            assert!((logarithmic[index] - expected).abs() < 1e-4);
+        }
+        for index in 0..4 {
+            assert!((blended[index] - (uniform[index] + logarithmic[index]) / 2.0).abs() < 1e-4);
+        }
+    }
+}
@@ -1,10 +1,11 @@
-use super::image;
+use super::{gl_check, gl_debug, gl_state, image};
 
 pub struct Texture {
     texture: u32,
 }
 
 impl Texture {
+    #[cfg_attr(any(debug_assertions, feature = "gl_check"), track_caller)]
     pub fn create() -> Texture {
         let mut texture = 0;
 
@@ -12,12 +13,13 @@ impl Texture {
             gl::GenTextures(1, &mut texture);
             spdlog::info!("Created texture({})", texture);
             // bind and set default filter and wrap option
-            gl::BindTexture(gl::TEXTURE_2D, texture);
+            gl_state::bind_texture(gl::TEXTURE_2D, texture);
             gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE.cast_signed());
             gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE.cast_signed());
             gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR_MIPMAP_LINEAR.cast_signed());
             gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR.cast_signed());
         }
+        gl_check::check("Texture::create");
 
         Texture { texture }
     }
@@ -27,30 +29,38 @@ impl Texture {
     }
 
     pub fn bind(&self) -> &Self {
-        unsafe {
-            gl::BindTexture(gl::TEXTURE_2D, self.texture);
-        }
+        gl_state::bind_texture(gl::TEXTURE_2D, self.texture);
+        self
+    }
+
+    pub fn set_label(&self, label: &str) -> &Self {
+        gl_debug::set_label(gl::TEXTURE, self.texture, label);
         self
     }
 
+    #[cfg_attr(any(debug_assertions, feature = "gl_check"), track_caller)]
     pub fn set_filter(&self, min_filter: u32, mag_filter: u32) -> &Self {
         self.bind();
         unsafe {
             gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, min_filter.cast_signed());
             gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, mag_filter.cast_signed());
         }
+        gl_check::check("Texture::set_filter");
         self
     }
 
+    #[cfg_attr(any(debug_assertions, feature = "gl_check"), track_caller)]
     pub fn set_wrap(&self, wrap_s: u32, wrap_t: u32) -> &Self {
         self.bind();
         unsafe {
             gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, wrap_s.cast_signed());
             gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, wrap_t.cast_signed());
         }
+        gl_check::check("Texture::set_wrap");
         self
     }
 
+    #[cfg_attr(any(debug_assertions, feature = "gl_check"), track_caller)]
     pub fn set_texture(&self, image: &image::Image) -> &Self {
         self.bind();
         unsafe {
@@ -65,8 +75,31 @@ impl Texture {
             );
             gl::GenerateMipmap(gl::TEXTURE_2D);
         }
+        gl_check::check("Texture::set_texture");
         self
     }
+
+    // 픽셀 데이터 없이 공간만 할당한다, 프레임 버퍼에 그려서 채우는 텍스쳐에 사용한다
+    #[cfg_attr(any(debug_assertions, feature = "gl_check"), track_caller)]
+    pub fn set_storage(&self, width: u32, height: u32, internal_format: u32, format: u32, type_: u32) -> &Self {
+        self.bind();
+        unsafe {
+            gl::TexImage2D(gl::TEXTURE_2D, 0, internal_format.cast_signed(), width.cast_signed(), height.cast_signed(), 0, format, type_, std::ptr::null());
+        }
+        gl_check::check("Texture::set_storage");
+        self.set_filter(gl::LINEAR, gl::LINEAR)
+    }
+
+    // 채널당 32비트 부동 소수점인 RGB 데이터(HDR 이미지)를 업로드한다
+    #[cfg_attr(any(debug_assertions, feature = "gl_check"), track_caller)]
+    pub fn set_hdr_texture(&self, width: u32, height: u32, data: &[f32]) -> &Self {
+        self.bind();
+        unsafe {
+            gl::TexImage2D(gl::TEXTURE_2D, 0, gl::RGB16F.cast_signed(), width.cast_signed(), height.cast_signed(), 0, gl::RGB, gl::FLOAT, data.as_ptr().cast());
+        }
+        gl_check::check("Texture::set_hdr_texture");
+        self.set_filter(gl::LINEAR, gl::LINEAR)
+    }
 }
 
 impl Drop for Texture {
@@ -74,6 +107,7 @@ impl Drop for Texture {
         unsafe { 
             gl::DeleteTextures(1, &mut self.texture);
         }
+        gl_state::on_texture_deleted(self.texture);
         spdlog::info!("Dropped texture({})", self.texture);
     }
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
 pub mod window;
 pub mod object;
 
-use crate::{errors, image::Image};
+use crate::{errors, gl_state, image::Image, resource};
 use crate::ui::{window::Window};
 
 use nalgebra_glm as glm;
@@ -21,20 +21,17 @@ pub struct Manager {
     prev_cursor_pos: glm::Vec2,
 
     // resource
-    close_image: Image,
-    maximize_image: Image,
-    minimize_image: Image,
+    close_image: Rc<Image>,
+    maximize_image: Rc<Image>,
+    minimize_image: Rc<Image>,
 }
 
 impl Manager {
     pub fn create(frame_buffer_size_x: f32, frame_buffer_size_y: f32) -> Result<Self, errors::Error> {
         // resource
-        let close_image = Image::load("resources/images/close.png")?;
-        spdlog::info!("Loaded image file \"resources/images/close.png\" ({} x {}, {} channels)", close_image.get_width(), close_image.get_height(), close_image.get_channel_count());
-        let maximize_image = Image::load("resources/images/maximize.png")?;
-        spdlog::info!("Loaded image file \"resources/images/maximize.png\" ({} x {}, {} channels)", maximize_image.get_width(), maximize_image.get_height(), maximize_image.get_channel_count());
-        let minimize_image = Image::load("resources/images/minimize.png")?;
-        spdlog::info!("Loaded image file \"resources/images/minimize.png\" ({} x {}, {} channels)", minimize_image.get_width(), minimize_image.get_height(), minimize_image.get_channel_count());
+        let close_image = resource::load_image("resources/images/close.png")?;
+        let maximize_image = resource::load_image("resources/images/maximize.png")?;
+        let minimize_image = resource::load_image("resources/images/minimize.png")?;
 
         // window
         let windows = Vec::new();
@@ -77,9 +74,7 @@ impl Manager {
             self.on_cursor_pos_event(self.cursor_pos.x, self.cursor_pos.y);
         }
         // 깊이 테스트 없이, 뒤에 있는 오브젝트부터 렌더링 한다
-        unsafe {
-            gl::Disable(gl::DEPTH_TEST);
-        }
+        gl_state::set_enabled(gl::DEPTH_TEST, false);
         for window in self.windows.iter().map(|w|{ w.borrow_mut() }) {
             window.render();
         }
@@ -137,6 +132,11 @@ impl Manager {
         }
     }
 
+    // 커서가 창 위에 있으면 클릭을 장면으로 넘기지 않는다
+    pub fn is_cursor_on_window(&self) -> bool {
+        self.on_cursor_window.is_some()
+    }
+
     pub fn on_frame_buffer_size_event(&mut self, frame_buffer_size_x: f32, frame_buffer_size_y: f32) {
         self.ratio = glm::vec2(2.0 / frame_buffer_size_x, 2.0 / frame_buffer_size_y);
         for mut window in self.windows.iter().map(|w|{ w.borrow_mut() }) {
@@ -1,4 +1,4 @@
-use crate::{buffer::Buffer, errors, image::Image, program::Program, shader::Shader, texture::Texture, vertex_array::VertexArray};
+use crate::{buffer::Buffer, errors, gl_state, image::Image, program::Program, resource, texture::Texture, vertex_array::VertexArray};
 
 use nalgebra_glm as glm;
 use std::{cell::RefCell, rc::Rc};
@@ -28,7 +28,7 @@ pub struct Object {
     pub vertices_content: [f32; 36],
     pub indices_border: [u32; 24],
     pub indices_content: [u32; 6],
-    pub program: Program,
+    pub program: Rc<Program>, // 모든 UI 객체가 함께 사용한다
     pub vao_border: VertexArray,
     pub vao_content: VertexArray,
     pub vbo_border: Buffer,
@@ -145,12 +145,7 @@ impl Object {
             1, 2, 3,
         ];
 
-        let vertex_shader = Shader::create("shader/ui.vert", gl::VERTEX_SHADER)?;
-        let fragment_shader = Shader::create("shader/ui.frag", gl::FRAGMENT_SHADER)?;
-        spdlog::info!("Created vertex shader({})", vertex_shader.get());
-        spdlog::info!("Created fragment shader({})", fragment_shader.get());
-        let program = Program::create(vec![&vertex_shader, &fragment_shader])?;
-        spdlog::info!("Created program({})", program.get());
+        let program = resource::load_program("shader/ui.vert", "shader/ui.frag", &[])?;
         program.use_();
 
         let vao_border = VertexArray::create();
@@ -166,6 +161,13 @@ impl Object {
         let ebo_content = Buffer::create(gl::ELEMENT_ARRAY_BUFFER, size_of_val(&indices_content).cast_signed(), indices_content.as_ptr().cast(), gl::STATIC_DRAW);
         vao_content.set(0, 3, gl::FLOAT, gl::FALSE, (size_of::<f32>() * 9) as i32, (size_of::<f32>() * 0) as *const _);
         vao_content.set(1, 4, gl::FLOAT, gl::FALSE, (size_of::<f32>() * 9) as i32, (size_of::<f32>() * 3) as *const _);
+        // 드라이버 메시지에서 어떤 UI 객체인지 알 수 있도록 이름을 단다
+        vao_border.set_label(&format!("{} border vao", name));
+        vbo_border.set_label(&format!("{} border vbo", name));
+        ebo_border.set_label(&format!("{} border ebo", name));
+        vao_content.set_label(&format!("{} content vao", name));
+        vbo_content.set_label(&format!("{} content vbo", name));
+        ebo_content.set_label(&format!("{} content ebo", name));
 
         let shader_type = ShaderType::Color;
         let tbo = None;
@@ -455,7 +457,7 @@ impl Object {
             if self.shader_type == ShaderType::Color {
                 self.program.set_uniform1i("shader_type\0", 0);
             } else {
-                gl::ActiveTexture(gl::TEXTURE0);
+                gl_state::active_texture(0);
                 self.tbo.as_ref().unwrap().bind();
                 self.program.set_uniform1i("texture0\0", 0);
                 if self.shader_type == ShaderType::Texture {
@@ -570,7 +572,9 @@ impl Object {
         if self.tbo.is_some() {
             return self;
         }
-        self.tbo = Some(Texture::create());
+        let tbo = Texture::create();
+        tbo.set_label(&format!("{} texture", self.name));
+        self.tbo = Some(tbo);
         self.vao_content.set(2, 2, gl::FLOAT, gl::FALSE, (size_of::<f32>() * 9) as i32, (size_of::<f32>() * 7) as *const _);
         self
     }
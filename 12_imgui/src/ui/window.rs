@@ -1,4 +1,4 @@
-use crate::{errors, image::Image};
+use crate::{errors, gl_state, image::Image};
 use crate::ui::object::{Object, Color, ShaderType};
 
 use nalgebra_glm as glm;
@@ -176,9 +176,7 @@ impl Window {
     }
 
     pub fn render(&self) {
-        unsafe {
-            gl::Disable(gl::DEPTH_TEST);
-        }
+        gl_state::set_enabled(gl::DEPTH_TEST, false);
         for mut element in self.elements.iter().map(|w|{ w.borrow_mut() }) {
             element.render();
         }
@@ -1,3 +1,5 @@
+use super::{gl_check, gl_debug, gl_state};
+
 pub struct VertexArray {
     vertex_array: u32,
 }
@@ -14,12 +16,14 @@ impl VertexArray {
         VertexArray { vertex_array }
     }
 
+    #[cfg_attr(any(debug_assertions, feature = "gl_check"), track_caller)]
     pub fn set(&self, index: u32, size: i32, type_: u32, normalized: u8, stride: i32, offset: *const std::ffi::c_void) {
         unsafe {
-            gl::BindVertexArray(self.vertex_array);
+            gl_state::bind_vertex_array(self.vertex_array);
             gl::EnableVertexAttribArray(index);
             gl::VertexAttribPointer(index, size, type_, normalized, stride, offset);
         }
+        gl_check::check("VertexArray::set");
     }
 
     pub fn get(&self) -> u32 {
@@ -27,9 +31,13 @@ impl VertexArray {
     }
 
     pub fn bind(&self) {
-        unsafe {
-            gl::BindVertexArray(self.vertex_array);
-        }
+        gl_state::bind_vertex_array(self.vertex_array);
+    }
+
+    // GenVertexArrays로 받은 이름은 처음 바인딩할 때 객체가 만들어지므로 먼저 바인딩한다
+    pub fn set_label(&self, label: &str) {
+        self.bind();
+        gl_debug::set_label(gl::VERTEX_ARRAY, self.vertex_array, label);
     }
 }
 
@@ -38,6 +46,7 @@ impl Drop for VertexArray {
         unsafe {
             gl::DeleteVertexArrays(1, &mut self.vertex_array);
         }
+        gl_state::on_vertex_array_deleted(self.vertex_array);
         spdlog::info!("Dropped vertex array({})", self.vertex_array);
     }
 }
\ No newline at end of file
@@ -0,0 +1,30 @@
+use super::{errors, runner::Window};
+
+// Runner가 한 프레임에서 호출하는 순서
+// on_resize, on_event (받은 이벤트마다) -> fixed_update (0번 이상) -> update -> render -> ui -> 버퍼 교체
+pub trait App: Sized {
+    // 각 장의 오류 타입, 창을 만들다 생긴 오류도 이 타입으로 바꿔서 돌려준다
+    type Error: From<errors::Error>;
+
+    // OpenGL 함수를 불러온 뒤에 한 번 호출한다
+    fn init(window: &mut Window) -> Result<Self, Self::Error>;
+
+    // 고정된 시간 간격(Runner::set_fixed_time_step)마다 호출한다, 프레임이 느리면 한 프레임에 여러 번 호출된다
+    fn fixed_update(&mut self, _window: &mut Window, _time_step: f32) {}
+
+    fn update(&mut self, _window: &mut Window, _time: f32, _delta_time: f32) {}
+
+    fn render(&mut self, _window: &mut Window) {}
+
+    // render가 그린 장면 위에 UI를 그린다
+    fn ui(&mut self, _window: &mut Window) {}
+
+    fn on_event(&mut self, _window: &mut Window, _event: &glfw::WindowEvent) {}
+
+    // 프레임 버퍼 크기가 바뀌면 on_event보다 먼저 호출한다, 뷰포트는 Runner가 이미 gl::Viewport로 바꿔 둔다
+    // GL 상태를 따로 기억해 두는 App은 Runner가 바꾼 뷰포트를 모르므로 여기서 자신의 기록도 갱신해야 한다
+    fn on_resize(&mut self, _window: &mut Window, _width: i32, _height: i32) {}
+
+    // 창을 닫은 뒤, OpenGL 컨텍스트가 사라지기 전에 호출한다
+    fn shutdown(&mut self, _window: &mut Window) {}
+}
@@ -0,0 +1,32 @@
+use std::path::{Path, PathBuf};
+
+// 어느 디렉토리에서 실행해도 shader/와 resources/를 찾을 수 있도록 한다
+// 상대 경로는 get_directories의 순서대로 찾아서 처음 찾은 파일을 사용하고, 절대 경로는 그대로 사용한다
+
+const ASSET_PATH_VARIABLE: &str = "ASSET_PATH"; // 운영체제의 경로 구분자로 여러 디렉토리를 적을 수 있다
+
+// 우선순위: ASSET_PATH 환경 변수 > 현재 디렉토리 > 실행 파일의 디렉토리 > 워크스페이스 루트
+pub fn get_directories() -> Vec<PathBuf> {
+    let mut directories = Vec::new();
+    if let Some(paths) = std::env::var_os(ASSET_PATH_VARIABLE) {
+        directories.extend(std::env::split_paths(&paths).filter(|path| !path.as_os_str().is_empty()));
+    }
+    directories.push(PathBuf::from("."));
+    if let Some(directory) = std::env::current_exe().ok().and_then(|path| path.parent().map(Path::to_path_buf)) {
+        directories.push(directory);
+    }
+    // shader/와 resources/는 각 장의 디렉토리가 아닌 워크스페이스 루트에 있다
+    if let Some(root) = Path::new(env!("CARGO_MANIFEST_DIR")).parent() {
+        directories.push(root.to_path_buf());
+    }
+    directories
+}
+
+// 찾지 못하면 받은 경로를 그대로 돌려주므로 파일을 여는 쪽에서 NotFound 오류가 난다
+pub fn find<P>(path: P) -> PathBuf where P: AsRef<Path> {
+    let path = path.as_ref();
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+    get_directories().into_iter().map(|directory| directory.join(path)).find(|path| path.is_file()).unwrap_or_else(|| path.to_path_buf())
+}
@@ -0,0 +1,212 @@
+use nalgebra_glm as glm;
+
+#[derive(Clone, Copy)]
+pub enum Projection {
+    // 세로 화각(도)
+    Perspective { fov: f32 },
+    // 화면에 보이는 세로 길이, 가로 길이는 종횡비로 정해진다
+    Orthographic { height: f32 },
+}
+
+pub struct Camera {
+    position: glm::Vec3,
+    pitch: f32, // 끄덕끄덕
+    yaw: f32, // 도리도리
+    projection: Projection,
+    near: f32,
+    far: f32,
+}
+
+impl Camera {
+    pub fn new(position: glm::Vec3) -> Self {
+        Self { position, pitch: 0.0, yaw: 0.0, projection: Projection::Perspective { fov: 45.0 }, near: 0.01, far: 20.0 }
+    }
+
+    pub fn get_position(&self) -> glm::Vec3 {
+        self.position
+    }
+
+    pub fn get_pitch(&self) -> f32 {
+        self.pitch
+    }
+
+    pub fn get_yaw(&self) -> f32 {
+        self.yaw
+    }
+
+    pub fn get_projection(&self) -> Projection {
+        self.projection
+    }
+
+    pub fn get_near(&self) -> f32 {
+        self.near
+    }
+
+    pub fn get_far(&self) -> f32 {
+        self.far
+    }
+
+    pub fn set_position(&mut self, position: glm::Vec3) -> &mut Self {
+        self.position = position;
+        self
+    }
+
+    // pitch는 위아래가 뒤집히지 않도록 -89 ~ 89도로 제한한다
+    pub fn set_rotation(&mut self, pitch: f32, yaw: f32) -> &mut Self {
+        self.pitch = pitch.clamp(-89.0, 89.0);
+        self.yaw = yaw.rem_euclid(360.0);
+        self
+    }
+
+    pub fn set_perspective(&mut self, fov: f32) -> &mut Self {
+        self.projection = Projection::Perspective { fov };
+        self
+    }
+
+    pub fn set_orthographic(&mut self, height: f32) -> &mut Self {
+        self.projection = Projection::Orthographic { height };
+        self
+    }
+
+    pub fn set_clip_planes(&mut self, near: f32, far: f32) -> &mut Self {
+        self.near = near;
+        self.far = far;
+        self
+    }
+
+    // 카메라가 target을 바라보도록 pitch, yaw를 계산한다
+    pub fn look_at(&mut self, target: &glm::Vec3) -> &mut Self {
+        let direction = target - self.position;
+        if glm::length(&direction) == 0.0 {
+            return self;
+        }
+        let direction = glm::normalize(&direction);
+        let pitch = direction.y.asin().to_degrees();
+        let yaw = (-direction.x).atan2(-direction.z).to_degrees();
+        self.set_rotation(pitch, yaw)
+    }
+
+    // 원근 투영은 화각을, 직교 투영은 보이는 영역의 크기를 줄인다
+    pub fn zoom(&mut self, amount: f32) -> &mut Self {
+        self.projection = match self.projection {
+            Projection::Perspective { fov } => Projection::Perspective { fov: (fov - amount).clamp(1.0, 120.0) },
+            Projection::Orthographic { height } => Projection::Orthographic { height: (height * 0.9f32.powf(amount)).max(0.01) },
+        };
+        self
+    }
+
+    // 카메라가 바라보는 방향
+    pub fn get_front(&self) -> glm::Vec3 {
+        (glm::rotate(&glm::Mat4::identity(), self.yaw.to_radians(), &glm::vec3(0.0, 1.0, 0.0)) * glm::rotate(&glm::Mat4::identity(), self.pitch.to_radians(), &glm::vec3(1.0, 0.0, 0.0)) * glm::vec4(0.0, 0.0, -1.0, 0.0)).xyz()
+    }
+
+    pub fn get_right(&self) -> glm::Vec3 {
+        glm::normalize(&glm::cross(&glm::vec3(0.0, 1.0, 0.0), &-self.get_front()))
+    }
+
+    pub fn get_up(&self) -> glm::Vec3 {
+        glm::cross(&self.get_right(), &self.get_front())
+    }
+
+    pub fn get_view_matrix(&self) -> glm::Mat4 {
+        glm::look_at(&self.position, &(self.position + self.get_front()), &glm::vec3(0.0, 1.0, 0.0))
+    }
+
+    pub fn get_projection_matrix(&self, aspect: f32) -> glm::Mat4 {
+        self.get_projection_matrix_range(aspect, self.near, self.far)
+    }
+
+    fn get_projection_matrix_range(&self, aspect: f32, near: f32, far: f32) -> glm::Mat4 {
+        match self.projection {
+            Projection::Perspective { fov } => glm::perspective(aspect, fov.to_radians(), near, far),
+            Projection::Orthographic { height } => {
+                let half_height = height / 2.0;
+                let half_width = half_height * aspect;
+                glm::ortho(-half_width, half_width, -half_height, half_height, near, far)
+            }
+        }
+    }
+
+    // 카메라에서 near ~ far 거리 구간의 절두체 꼭짓점 8개(world 공간)
+    pub fn get_frustum_corners(&self, aspect: f32, near: f32, far: f32) -> [glm::Vec3; 8] {
+        let inverse = glm::inverse(&(self.get_projection_matrix_range(aspect, near, far) * self.get_view_matrix()));
+        let mut corners = [glm::vec3(0.0, 0.0, 0.0); 8];
+        for (index, corner) in corners.iter_mut().enumerate() {
+            let ndc = glm::vec4(if index & 1 == 0 { -1.0 } else { 1.0 }, if index & 2 == 0 { -1.0 } else { 1.0 }, if index & 4 == 0 { -1.0 } else { 1.0 }, 1.0);
+            let position = inverse * ndc;
+            *corner = position.xyz() / position.w;
+        }
+        corners
+    }
+}
+
+// 한 프레임 동안 모인 입력의 변화량
+pub struct Input {
+    pub movement: glm::Vec3, // x: 오른쪽, y: 위, z: 앞, 각 성분은 -1 ~ 1
+    pub rotation: glm::Vec2, // 마우스 이동량(픽셀)
+    pub turn: glm::Vec2, // 초당 회전 각도(도), 게임패드 스틱처럼 누르고 있는 동안 계속 도는 입력, 방향은 rotation과 같다
+    pub zoom: f32, // 스크롤 이동량
+}
+
+impl Input {
+    pub fn new() -> Self {
+        Self { movement: glm::vec3(0.0, 0.0, 0.0), rotation: glm::vec2(0.0, 0.0), turn: glm::vec2(0.0, 0.0), zoom: 0.0 }
+    }
+}
+
+impl Default for Input {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ControllerMode {
+    // 바라보는 방향과 상관없이 수평면 위를 걷는다
+    FirstPerson,
+    // 바라보는 방향으로 자유롭게 날아다닌다
+    FreeFly,
+    // target을 중심으로 공전하며, 이동 입력은 target을 옮긴다
+    Orbit { target: glm::Vec3, distance: f32 },
+}
+
+pub struct Controller {
+    pub mode: ControllerMode,
+    pub speed: f32,
+    pub sensitivity: f32,
+}
+
+impl Controller {
+    pub fn new(mode: ControllerMode) -> Self {
+        Self { mode, speed: 2.5, sensitivity: 0.15 }
+    }
+
+    pub fn update(&mut self, camera: &mut Camera, input: &Input, delta_time: f32) {
+        let distance = delta_time * self.speed;
+        let pitch = camera.get_pitch() - input.rotation.y * self.sensitivity - input.turn.y * delta_time;
+        let yaw = camera.get_yaw() - input.rotation.x * self.sensitivity - input.turn.x * delta_time;
+        camera.set_rotation(pitch, yaw);
+
+        match &mut self.mode {
+            ControllerMode::FirstPerson => {
+                let front = camera.get_front();
+                let forward = glm::normalize(&glm::vec3(front.x, 0.0, front.z));
+                let position = camera.get_position() + distance * (input.movement.x * camera.get_right() + input.movement.y * glm::vec3(0.0, 1.0, 0.0) + input.movement.z * forward);
+                camera.set_position(position).zoom(input.zoom);
+            }
+            ControllerMode::FreeFly => {
+                let position = camera.get_position() + distance * (input.movement.x * camera.get_right() + input.movement.y * glm::vec3(0.0, 1.0, 0.0) + input.movement.z * camera.get_front());
+                camera.set_position(position).zoom(input.zoom);
+            }
+            ControllerMode::Orbit { target, distance: orbit_distance } => {
+                *target += distance * *orbit_distance * (input.movement.x * camera.get_right() + input.movement.y * camera.get_up());
+                *orbit_distance = (*orbit_distance * 0.9f32.powf(input.zoom + input.movement.z * delta_time * self.speed)).max(0.01);
+                if let Projection::Orthographic { .. } = camera.get_projection() {
+                    camera.zoom(input.zoom);
+                }
+                let position = *target - camera.get_front() * *orbit_distance;
+                camera.set_position(position);
+            }
+        }
+    }
+}
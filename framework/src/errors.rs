@@ -0,0 +1,39 @@
+pub enum Error {
+    InitError(glfw::InitError),
+    CreateWindowError,
+    LoadGlError,
+    UnsupportedVersion((u32, u32), (u32, u32)), // 요청한 버전, 만들어진 컨텍스트의 버전
+}
+
+impl std::error::Error for Error {}
+
+impl std::fmt::Debug for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InitError(description) => {
+                write!(f, "Failed to initialize glfw\n{}", description)
+            }
+            Error::CreateWindowError => {
+                write!(f, "Failed to create GLFW window")
+            }
+            Error::LoadGlError => {
+                write!(f, "Failed to initialize glad")
+            }
+            Error::UnsupportedVersion(requested, created) => {
+                write!(f, "OpenGL {}.{} is required, but created {}.{}", requested.0, requested.1, created.0, created.1)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl From<glfw::InitError> for Error {
+    fn from(description: glfw::InitError) -> Self {
+        Error::InitError(description)
+    }
+}
@@ -0,0 +1,12 @@
+// 모든 장이 함께 사용하는 창과 메인 루프, 카메라
+// 각 장은 App 트레이트를 구현하고 Runner로 실행한다
+mod common;
+mod errors;
+mod app;
+mod runner;
+pub mod camera;
+pub mod asset;
+
+pub use app::App;
+pub use errors::Error;
+pub use runner::{Runner, Window};
@@ -0,0 +1,233 @@
+use super::{common, errors, app::App};
+use glfw::Context;
+
+const MAX_FIXED_STEPS: u32 = 8; // 프레임이 아주 느려도 fixed_update를 이 이상 몰아서 호출하지 않는다
+
+// App이 사용하는 glfw와 창
+pub struct Window {
+    glfw: glfw::Glfw,
+    window: glfw::PWindow,
+}
+
+impl Window {
+    pub fn get_glfw(&self) -> &glfw::Glfw {
+        &self.glfw
+    }
+
+    pub fn get_glfw_mut(&mut self) -> &mut glfw::Glfw {
+        &mut self.glfw
+    }
+
+    pub fn get_window(&self) -> &glfw::PWindow {
+        &self.window
+    }
+
+    pub fn get_window_mut(&mut self) -> &mut glfw::PWindow {
+        &mut self.window
+    }
+
+    // glfw를 초기화한 뒤로 지난 시간(초)
+    pub fn get_time(&self) -> f32 {
+        self.glfw.get_time() as f32
+    }
+
+    pub fn get_frame_buffer_size(&self) -> (i32, i32) {
+        self.window.get_framebuffer_size()
+    }
+
+    pub fn close(&mut self) {
+        self.window.set_should_close(true);
+    }
+}
+
+// 창을 만들고 메인 루프를 돌리면서 App의 함수를 호출한다
+pub struct Runner {
+    title: String,
+    width: u32,
+    height: u32,
+    gl_version: (u32, u32),
+    vsync: bool,
+    samples: u32,
+    debug_context: bool,
+    fixed_time_step: f32,
+    close_on_escape: bool,
+    log_input: bool,
+}
+
+impl Runner {
+    pub fn new(title: &str) -> Self {
+        Self { title: title.to_owned(), width: 640, height: 480, gl_version: (3, 3), vsync: true, samples: 0, debug_context: false, fixed_time_step: 1.0 / 60.0, close_on_escape: true, log_input: true }
+    }
+
+    pub fn set_size(&mut self, width: u32, height: u32) -> &mut Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    // core 프로파일로 만든다
+    pub fn set_gl_version(&mut self, major: u32, minor: u32) -> &mut Self {
+        self.gl_version = (major, minor);
+        self
+    }
+
+    pub fn set_vsync(&mut self, vsync: bool) -> &mut Self {
+        self.vsync = vsync;
+        self
+    }
+
+    // MSAA 샘플 수, 0이면 끈다
+    pub fn set_samples(&mut self, samples: u32) -> &mut Self {
+        self.samples = samples;
+        self
+    }
+
+    // 드라이버가 KHR_debug 메시지를 자세히 보내도록 디버그 컨텍스트를 요청한다
+    pub fn set_debug_context(&mut self, debug_context: bool) -> &mut Self {
+        self.debug_context = debug_context;
+        self
+    }
+
+    pub fn set_fixed_time_step(&mut self, fixed_time_step: f32) -> &mut Self {
+        self.fixed_time_step = fixed_time_step;
+        self
+    }
+
+    pub fn set_close_on_escape(&mut self, close_on_escape: bool) -> &mut Self {
+        self.close_on_escape = close_on_escape;
+        self
+    }
+
+    // 키보드와 마우스 버튼 이벤트를 로그로 남긴다
+    pub fn set_log_input(&mut self, log_input: bool) -> &mut Self {
+        self.log_input = log_input;
+        self
+    }
+
+    pub fn run<A>(&self) -> Result<(), A::Error> where A: App {
+        // Initialize glfw
+        spdlog::info!("Initialize glfw");
+        let mut glfw = glfw::init_no_callbacks().map_err(errors::Error::from)?;
+
+        glfw.window_hint(glfw::WindowHint::ContextVersion(self.gl_version.0, self.gl_version.1));
+        glfw.window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Core));
+        glfw.window_hint(glfw::WindowHint::Samples((self.samples > 0).then_some(self.samples)));
+        glfw.window_hint(glfw::WindowHint::OpenGlDebugContext(self.debug_context));
+
+        // Create glfw window
+        spdlog::info!("Create glfw window");
+        let (mut window, events) = glfw.create_window(self.width, self.height, &self.title, glfw::WindowMode::Windowed).ok_or(errors::Error::CreateWindowError)?;
+        window.set_all_polling(true);
+        window.make_current();
+        glfw.set_swap_interval(if self.vsync { glfw::SwapInterval::Sync(1) } else { glfw::SwapInterval::None });
+
+        // Initialize glad
+        spdlog::info!("Initialize glad");
+        gl::load_with(|s| window.get_proc_address(s) as *const _);
+        let gl_version = unsafe { common::c_str_to_string(gl::GetString(gl::VERSION).cast()) };
+        let Some(gl_version) = gl_version else {
+            return Err(errors::Error::LoadGlError.into());
+        };
+        spdlog::info!("Loaded OpenGL {}", gl_version);
+        // 드라이버가 요청보다 낮은 버전의 컨텍스트를 돌려줄 수 있으므로 확인한다
+        let (mut major, mut minor) = (0, 0);
+        unsafe {
+            gl::GetIntegerv(gl::MAJOR_VERSION, &mut major);
+            gl::GetIntegerv(gl::MINOR_VERSION, &mut minor);
+        }
+        let created = (major.max(0) as u32, minor.max(0) as u32);
+        if created < self.gl_version {
+            return Err(errors::Error::UnsupportedVersion(self.gl_version, created).into());
+        }
+        let (width, height) = window.get_framebuffer_size();
+        unsafe {
+            gl::Viewport(0, 0, width, height); // State-setting function
+            if self.samples > 0 {
+                gl::Enable(gl::MULTISAMPLE);
+            }
+        }
+
+        let mut window = Window { glfw, window };
+        let mut app = A::init(&mut window)?;
+
+        // Start main loop
+        spdlog::info!("Start main loop");
+        let mut previous_time = window.get_time();
+        let mut accumulated_time = 0.0;
+        while !window.window.should_close() {
+            let time = window.get_time();
+            let delta_time = time - previous_time;
+            previous_time = time;
+
+            window.glfw.poll_events();
+            // 이벤트를 처리하는 동안 App이 창을 빌려 쓸 수 있도록 먼저 모두 꺼낸다
+            let frame_events: Vec<glfw::WindowEvent> = glfw::flush_messages(&events).map(|(_, event)| event).collect();
+            for event in &frame_events {
+                self.handle_event(&mut window, event);
+                if let glfw::WindowEvent::FramebufferSize(width, height) = *event {
+                    app.on_resize(&mut window, width, height);
+                }
+                app.on_event(&mut window, event);
+            }
+
+            accumulated_time += delta_time;
+            let mut step_count = 0;
+            while accumulated_time >= self.fixed_time_step && step_count < MAX_FIXED_STEPS {
+                app.fixed_update(&mut window, self.fixed_time_step);
+                accumulated_time -= self.fixed_time_step;
+                step_count += 1;
+            }
+            if step_count == MAX_FIXED_STEPS {
+                accumulated_time = 0.0;
+            }
+
+            app.update(&mut window, time, delta_time);
+            app.render(&mut window);
+            app.ui(&mut window);
+            window.window.swap_buffers();
+        }
+        app.shutdown(&mut window);
+
+        Ok(())
+    }
+
+    // 모든 장이 같은 방식으로 처리하는 이벤트
+    fn handle_event(&self, window: &mut Window, event: &glfw::WindowEvent) {
+        match *event {
+            glfw::WindowEvent::FramebufferSize(width, height) => {
+                spdlog::info!("FramebufferSize changed: {} x {}", width, height);
+                unsafe {
+                    gl::Viewport(0, 0, width, height);
+                }
+            }
+            glfw::WindowEvent::Key(key, scancode, action, modifiers) => {
+                if self.log_input {
+                    spdlog::info!("key: {}, scancode: {}, action: {}, mods: {}", key as usize, scancode, get_action_name(action), get_modifiers_name(modifiers));
+                }
+                if self.close_on_escape && key == glfw::Key::Escape && action == glfw::Action::Press {
+                    window.close();
+                }
+            }
+            glfw::WindowEvent::MouseButton(mouse_button, action, modifiers) if self.log_input => {
+                spdlog::info!("mouse: {}, action: {}, mods: {}", mouse_button as usize, get_action_name(action), get_modifiers_name(modifiers));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn get_action_name(action: glfw::Action) -> &'static str {
+    match action {
+        glfw::Action::Press => "Press",
+        glfw::Action::Release => "Release",
+        glfw::Action::Repeat => "Repeat",
+    }
+}
+
+fn get_modifiers_name(modifiers: glfw::Modifiers) -> String {
+    format!("{}{}{}",
+        if modifiers.contains(glfw::Modifiers::Control) { "C" } else { "-" },
+        if modifiers.contains(glfw::Modifiers::Shift) { "S" } else { "-" },
+        if modifiers.contains(glfw::Modifiers::Alt) { "A" } else { "-" },
+    )
+}
@@ -0,0 +1,63 @@
+#version 330 core
+
+in vec2 vertex_texture_coord; // x: dot(n, v), y: roughness
+
+out vec2 fragment_color;
+
+const float PI = 3.14159265359;
+const uint SAMPLE_COUNT = 1024u;
+
+vec2 hammersley(uint index, uint count) {
+    uint bits = index;
+    bits = (bits << 16u) | (bits >> 16u);
+    bits = ((bits & 0x55555555u) << 1u) | ((bits & 0xAAAAAAAAu) >> 1u);
+    bits = ((bits & 0x33333333u) << 2u) | ((bits & 0xCCCCCCCCu) >> 2u);
+    bits = ((bits & 0x0F0F0F0Fu) << 4u) | ((bits & 0xF0F0F0F0u) >> 4u);
+    bits = ((bits & 0x00FF00FFu) << 8u) | ((bits & 0xFF00FF00u) >> 8u);
+    return vec2(float(index) / float(count), float(bits) * 2.3283064365386963e-10);
+}
+
+vec3 importance_sample_ggx(vec2 xi, vec3 normal, float roughness) {
+    float a = roughness * roughness;
+    float phi = 2.0 * PI * xi.x;
+    float cos_theta = sqrt((1.0 - xi.y) / (1.0 + (a * a - 1.0) * xi.y));
+    float sin_theta = sqrt(1.0 - cos_theta * cos_theta);
+    vec3 h = vec3(cos(phi) * sin_theta, sin(phi) * sin_theta, cos_theta);
+
+    vec3 up = abs(normal.z) < 0.999 ? vec3(0.0, 0.0, 1.0) : vec3(1.0, 0.0, 0.0);
+    vec3 tangent = normalize(cross(up, normal));
+    vec3 bitangent = cross(normal, tangent);
+    return normalize(tangent * h.x + bitangent * h.y + normal * h.z);
+}
+
+// IBL에서는 k = a^2 / 2를 사용한다
+float geometry_schlick_ggx(float n_dot_v, float roughness) {
+    float k = (roughness * roughness) / 2.0;
+    return n_dot_v / (n_dot_v * (1.0 - k) + k);
+}
+
+// Fresnel 항을 F0에 대한 배율(x)과 편향(y)으로 나눠서 적분한다 (split sum 근사)
+void main() {
+    float n_dot_v = max(vertex_texture_coord.x, 0.0001);
+    float roughness = vertex_texture_coord.y;
+    vec3 view = vec3(sqrt(1.0 - n_dot_v * n_dot_v), 0.0, n_dot_v);
+    vec3 normal = vec3(0.0, 0.0, 1.0);
+
+    float scale = 0.0;
+    float bias = 0.0;
+    for (uint index = 0u; index < SAMPLE_COUNT; index++) {
+        vec3 h = importance_sample_ggx(hammersley(index, SAMPLE_COUNT), normal, roughness);
+        vec3 light = normalize(2.0 * dot(view, h) * h - view);
+        float n_dot_l = max(light.z, 0.0);
+        float n_dot_h = max(h.z, 0.0);
+        float v_dot_h = max(dot(view, h), 0.0);
+        if (n_dot_l > 0.0) {
+            float geometry = geometry_schlick_ggx(n_dot_v, roughness) * geometry_schlick_ggx(n_dot_l, roughness);
+            float visibility = (geometry * v_dot_h) / (n_dot_h * n_dot_v);
+            float fresnel = pow(1.0 - v_dot_h, 5.0);
+            scale += (1.0 - fresnel) * visibility;
+            bias += fresnel * visibility;
+        }
+    }
+    fragment_color = vec2(scale, bias) / float(SAMPLE_COUNT);
+}
@@ -0,0 +1,13 @@
+#version 330 core
+
+layout (location = 0) in vec3 position;
+
+uniform mat4 projection;
+uniform mat4 view;
+
+out vec3 local_position; // 큐브의 중심에서 바라본 방향으로 큐브맵을 샘플링한다
+
+void main() {
+    local_position = position;
+    gl_Position = projection * view * vec4(position, 1.0);
+}
@@ -0,0 +1,13 @@
+#version 330 core
+
+layout (location = 0) in vec3 position; // world 공간의 위치, 화면 공간의 선은 NDC
+layout (location = 1) in vec4 color;
+
+uniform mat4 transform; // world 공간의 선은 projection * view, 화면 공간의 선은 단위 행렬
+
+out vec4 vertex_color;
+
+void main() {
+    gl_Position = transform * vec4(position, 1.0);
+    vertex_color = color;
+}
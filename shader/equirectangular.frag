@@ -0,0 +1,27 @@
+#version 330 core
+
+uniform sampler2D equirectangular_map;
+uniform bool has_equirectangular_map;
+
+in vec3 local_position;
+
+out vec4 fragment_color;
+
+const vec2 inverse_atan = vec2(0.1591, 0.3183); // (1 / 2pi, 1 / pi)
+
+void main() {
+    vec3 direction = normalize(local_position);
+    vec3 color;
+    if (has_equirectangular_map) {
+        // 방향을 경도, 위도로 바꿔서 equirectangular 이미지의 좌표로 사용한다
+        vec2 uv = vec2(atan(direction.z, direction.x), asin(direction.y)) * inverse_atan + 0.5;
+        color = texture(equirectangular_map, uv).rgb;
+    } else {
+        // 환경 맵이 없으면 하늘, 지평선, 땅으로 이루어진 단순한 하늘을 만든다
+        vec3 sky = vec3(0.25, 0.45, 0.85);
+        vec3 horizon = vec3(0.9, 0.9, 0.95);
+        vec3 ground = vec3(0.2, 0.18, 0.16);
+        color = direction.y > 0.0 ? mix(horizon, sky, pow(direction.y, 0.5)) : mix(horizon, ground, pow(-direction.y, 0.3));
+    }
+    fragment_color = vec4(color, 1.0);
+}
@@ -0,0 +1,10 @@
+#version 330 core
+
+out vec2 vertex_texture_coord;
+
+// 정점 버퍼 없이 화면을 덮는 삼각형 하나를 그린다
+void main() {
+    vec2 position = vec2(float((gl_VertexID << 1) & 2), float(gl_VertexID & 2));
+    vertex_texture_coord = position;
+    gl_Position = vec4(position * 2.0 - 1.0, 0.0, 1.0);
+}
@@ -0,0 +1,13 @@
+#version 330 core
+
+layout (location = 0) in vec3 position; // world 공간의 위치
+layout (location = 1) in vec3 color;
+
+uniform mat4 transform; // projection * view
+
+out vec3 vertex_color;
+
+void main() {
+    gl_Position = transform * vec4(position, 1.0);
+    vertex_color = color;
+}
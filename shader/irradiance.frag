@@ -0,0 +1,30 @@
+#version 330 core
+
+uniform samplerCube environment_map;
+
+in vec3 local_position;
+
+out vec4 fragment_color;
+
+const float PI = 3.14159265359;
+
+// normal 방향의 반구에서 들어오는 빛을 코사인 가중치로 적분한다 (diffuse IBL)
+void main() {
+    vec3 normal = normalize(local_position);
+    vec3 up = abs(normal.y) < 0.999 ? vec3(0.0, 1.0, 0.0) : vec3(0.0, 0.0, 1.0);
+    vec3 right = normalize(cross(up, normal));
+    up = normalize(cross(normal, right));
+
+    vec3 irradiance = vec3(0.0);
+    float sample_delta = 0.025;
+    float sample_count = 0.0;
+    for (float phi = 0.0; phi < 2.0 * PI; phi += sample_delta) {
+        for (float theta = 0.0; theta < 0.5 * PI; theta += sample_delta) {
+            vec3 tangent_sample = vec3(sin(theta) * cos(phi), sin(theta) * sin(phi), cos(theta));
+            vec3 sample_direction = tangent_sample.x * right + tangent_sample.y * up + tangent_sample.z * normal;
+            irradiance += texture(environment_map, sample_direction).rgb * cos(theta) * sin(theta);
+            sample_count++;
+        }
+    }
+    fragment_color = vec4(PI * irradiance / sample_count, 1.0);
+}
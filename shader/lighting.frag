@@ -0,0 +1,84 @@
+#version 330 core
+
+// MAX_LIGHT_COUNT는 프로그램을 만들 때 #define으로 주입된다
+#ifndef MAX_LIGHT_COUNT
+#define MAX_LIGHT_COUNT 8
+#endif
+
+#define LIGHT_DIRECTIONAL 0
+#define LIGHT_POINT 1
+#define LIGHT_SPOT 2
+
+struct Light {
+    int type;
+    vec3 position;
+    vec3 direction; // 빛이 나아가는 방향
+    vec3 color; // color * intensity
+    float constant; // 감쇠 계수: 1 / (constant + linear * d + quadratic * d^2)
+    float linear;
+    float quadratic;
+    float inner_cutoff; // cos(안쪽 원뿔 각도)
+    float outer_cutoff; // cos(바깥쪽 원뿔 각도)
+    int shadow_index; // 그림자 맵 번호, -1이면 그림자를 만들지 않는다
+};
+
+struct Material {
+    vec3 ambient;
+    vec3 diffuse;
+    vec3 specular;
+    float shininess;
+    float opacity;
+    bool has_diffuse_texture;
+    sampler2D diffuse_texture;
+};
+
+uniform Light lights[MAX_LIGHT_COUNT];
+uniform int light_count;
+uniform Material material;
+uniform vec3 view_position;
+
+#include "shadow.glsl"
+
+in vec3 vertex_position;
+in vec3 vertex_normal;
+in vec2 vertex_texture_coord;
+
+out vec4 fragment_color; // 최종 출력 색상
+
+void main() {
+    vec4 albedo = vec4(1.0);
+    if (material.has_diffuse_texture) {
+        albedo = texture(material.diffuse_texture, vertex_texture_coord);
+    }
+    vec3 normal = normalize(vertex_normal);
+    vec3 view_direction = normalize(view_position - vertex_position);
+
+    vec3 color = material.ambient * albedo.rgb;
+    for (int index = 0; index < min(light_count, MAX_LIGHT_COUNT); index++) {
+        Light light = lights[index];
+        vec3 light_direction; // 표면에서 광원을 향하는 방향
+        float attenuation = 1.0;
+        if (light.type == LIGHT_DIRECTIONAL) {
+            light_direction = normalize(-light.direction);
+        } else {
+            vec3 offset = light.position - vertex_position;
+            float light_distance = length(offset);
+            light_direction = offset / light_distance;
+            attenuation = 1.0 / (light.constant + light.linear * light_distance + light.quadratic * light_distance * light_distance);
+            if (light.type == LIGHT_SPOT) {
+                // 안쪽 원뿔에서 바깥쪽 원뿔까지 부드럽게 어두워진다
+                float theta = dot(light_direction, normalize(-light.direction));
+                attenuation *= clamp((theta - light.outer_cutoff) / max(light.inner_cutoff - light.outer_cutoff, 0.0001), 0.0, 1.0);
+            }
+        }
+
+        float diffuse = max(dot(normal, light_direction), 0.0);
+        // Blinn-Phong: 반사 벡터 대신 half vector와 normal의 사잇각을 사용한다
+        vec3 half_direction = normalize(light_direction + view_direction);
+        float specular = diffuse > 0.0 ? pow(max(dot(normal, half_direction), 0.0), material.shininess) : 0.0;
+
+        float shadow = compute_shadow(light.shadow_index, light.type == LIGHT_DIRECTIONAL, vertex_position, normal, light_direction);
+        color += shadow * attenuation * light.color * (diffuse * material.diffuse * albedo.rgb + specular * material.specular);
+    }
+    fragment_color = vec4(color, albedo.a * material.opacity);
+}
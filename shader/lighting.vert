@@ -0,0 +1,20 @@
+#version 330 core
+
+layout (location = 0) in vec3 position; // vao의 0번 속성으로 자동 할당
+layout (location = 1) in vec3 normal; // vao의 1번 속성으로 자동 할당
+layout (location = 2) in vec2 texture_coord; // vao의 2번 속성으로 자동 할당
+
+uniform mat4 transform; // projection * view * model
+uniform mat4 model;
+uniform mat3 normal_matrix; // model 행렬의 역행렬의 전치 행렬, 비균등 확대에도 normal이 면에 수직으로 유지된다
+
+out vec3 vertex_position; // world 공간의 위치
+out vec3 vertex_normal; // world 공간의 normal
+out vec2 vertex_texture_coord;
+
+void main() {
+    gl_Position = transform * vec4(position, 1.0);
+    vertex_position = (model * vec4(position, 1.0)).xyz;
+    vertex_normal = normal_matrix * normal;
+    vertex_texture_coord = texture_coord;
+}
@@ -0,0 +1,9 @@
+#version 330 core
+
+uniform vec4 outline_color;
+
+out vec4 fragment_color;
+
+void main() {
+    fragment_color = outline_color;
+}
@@ -0,0 +1,11 @@
+#version 330 core
+
+layout (location = 0) in vec3 position;
+layout (location = 1) in vec3 normal;
+
+uniform mat4 transform; // projection * view * model
+uniform float outline_width; // 로컬 좌표계 기준으로 normal 방향으로 밀어내는 거리
+
+void main() {
+    gl_Position = transform * vec4(position + normal * outline_width, 1.0);
+}
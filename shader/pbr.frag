@@ -0,0 +1,184 @@
+#version 330 core
+
+// MAX_LIGHT_COUNT는 프로그램을 만들 때 #define으로 주입된다
+#ifndef MAX_LIGHT_COUNT
+#define MAX_LIGHT_COUNT 8
+#endif
+
+#define LIGHT_DIRECTIONAL 0
+#define LIGHT_POINT 1
+#define LIGHT_SPOT 2
+
+struct Light {
+    int type;
+    vec3 position;
+    vec3 direction; // 빛이 나아가는 방향
+    vec3 color; // color * intensity
+    float constant; // 감쇠 계수: 1 / (constant + linear * d + quadratic * d^2)
+    float linear;
+    float quadratic;
+    float inner_cutoff; // cos(안쪽 원뿔 각도)
+    float outer_cutoff; // cos(바깥쪽 원뿔 각도)
+    int shadow_index; // 그림자 맵 번호, -1이면 그림자를 만들지 않는다
+};
+
+// 각 텍스쳐는 glTF의 metallic-roughness 규칙을 따른다
+struct Material {
+    vec4 base_color;
+    float metallic;
+    float roughness;
+    vec3 emissive;
+    float normal_scale;
+    float occlusion_strength;
+    bool has_base_color_texture;
+    bool has_metallic_roughness_texture; // g: roughness, b: metallic
+    bool has_normal_texture;
+    bool has_occlusion_texture; // r: occlusion
+    bool has_emissive_texture;
+    sampler2D base_color_texture;
+    sampler2D metallic_roughness_texture;
+    sampler2D normal_texture;
+    sampler2D occlusion_texture;
+    sampler2D emissive_texture;
+};
+
+uniform Light lights[MAX_LIGHT_COUNT];
+uniform int light_count;
+uniform Material material;
+uniform vec3 view_position;
+
+// image-based lighting
+uniform bool has_environment;
+uniform samplerCube irradiance_map;
+uniform samplerCube prefilter_map;
+uniform sampler2D brdf_lut;
+uniform float prefilter_max_lod;
+uniform float environment_intensity;
+
+#include "shadow.glsl"
+
+in vec3 vertex_position;
+in vec2 vertex_texture_coord;
+in mat3 vertex_tbn;
+
+out vec4 fragment_color; // 최종 출력 색상
+
+const float PI = 3.14159265359;
+
+// sRGB로 저장된 색상 텍스쳐를 선형 공간으로 옮긴다
+vec3 srgb_to_linear(vec3 color) {
+    return pow(color, vec3(2.2));
+}
+
+// 미세면 분포 함수: 미세면이 half vector를 향하는 비율
+float distribution_ggx(float n_dot_h, float roughness) {
+    float a = roughness * roughness;
+    float a2 = a * a;
+    float denominator = n_dot_h * n_dot_h * (a2 - 1.0) + 1.0;
+    return a2 / (PI * denominator * denominator);
+}
+
+// 기하 함수: 미세면끼리 가려지거나 그림자가 지는 비율, 직접광에서는 k = (r + 1)^2 / 8
+float geometry_smith(float n_dot_v, float n_dot_l, float roughness) {
+    float r = roughness + 1.0;
+    float k = (r * r) / 8.0;
+    float ggx_v = n_dot_v / (n_dot_v * (1.0 - k) + k);
+    float ggx_l = n_dot_l / (n_dot_l * (1.0 - k) + k);
+    return ggx_v * ggx_l;
+}
+
+float pow5(float x) {
+    float x2 = x * x;
+    return x2 * x2 * x;
+}
+
+vec3 fresnel_schlick(float cos_theta, vec3 f0) {
+    return f0 + (1.0 - f0) * pow5(clamp(1.0 - cos_theta, 0.0, 1.0));
+}
+
+// 거친 표면은 가장자리의 반사가 약하다
+vec3 fresnel_schlick_roughness(float cos_theta, vec3 f0, float roughness) {
+    return f0 + (max(vec3(1.0 - roughness), f0) - f0) * pow5(clamp(1.0 - cos_theta, 0.0, 1.0));
+}
+
+void main() {
+    vec4 base_color = material.base_color;
+    if (material.has_base_color_texture) {
+        vec4 texel = texture(material.base_color_texture, vertex_texture_coord);
+        base_color *= vec4(srgb_to_linear(texel.rgb), texel.a);
+    }
+    float metallic = material.metallic;
+    float roughness = material.roughness;
+    if (material.has_metallic_roughness_texture) {
+        vec4 texel = texture(material.metallic_roughness_texture, vertex_texture_coord);
+        roughness *= texel.g;
+        metallic *= texel.b;
+    }
+    roughness = clamp(roughness, 0.04, 1.0);
+    float occlusion = 1.0;
+    if (material.has_occlusion_texture) {
+        occlusion = mix(1.0, texture(material.occlusion_texture, vertex_texture_coord).r, material.occlusion_strength);
+    }
+    vec3 emissive = material.emissive;
+    if (material.has_emissive_texture) {
+        emissive *= srgb_to_linear(texture(material.emissive_texture, vertex_texture_coord).rgb);
+    }
+
+    vec3 normal = normalize(vertex_tbn[2]);
+    if (material.has_normal_texture) {
+        vec3 tangent_normal = texture(material.normal_texture, vertex_texture_coord).xyz * 2.0 - 1.0;
+        tangent_normal.xy *= material.normal_scale;
+        normal = normalize(vertex_tbn * tangent_normal);
+    }
+    vec3 view_direction = normalize(view_position - vertex_position);
+    float n_dot_v = max(dot(normal, view_direction), 0.0001);
+
+    // 유전체는 4%를 반사하고, 금속은 base color로 반사한다
+    vec3 f0 = mix(vec3(0.04), base_color.rgb, metallic);
+
+    vec3 color = vec3(0.0);
+    for (int index = 0; index < min(light_count, MAX_LIGHT_COUNT); index++) {
+        Light light = lights[index];
+        vec3 light_direction; // 표면에서 광원을 향하는 방향
+        float attenuation = 1.0;
+        if (light.type == LIGHT_DIRECTIONAL) {
+            light_direction = normalize(-light.direction);
+        } else {
+            vec3 offset = light.position - vertex_position;
+            float light_distance = length(offset);
+            light_direction = offset / light_distance;
+            attenuation = 1.0 / (light.constant + light.linear * light_distance + light.quadratic * light_distance * light_distance);
+            if (light.type == LIGHT_SPOT) {
+                float theta = dot(light_direction, normalize(-light.direction));
+                attenuation *= clamp((theta - light.outer_cutoff) / max(light.inner_cutoff - light.outer_cutoff, 0.0001), 0.0, 1.0);
+            }
+        }
+        vec3 half_direction = normalize(light_direction + view_direction);
+        float n_dot_l = max(dot(normal, light_direction), 0.0);
+        float n_dot_h = max(dot(normal, half_direction), 0.0);
+
+        // Cook-Torrance BRDF
+        vec3 fresnel = fresnel_schlick(max(dot(half_direction, view_direction), 0.0), f0);
+        vec3 specular = distribution_ggx(n_dot_h, roughness) * geometry_smith(n_dot_v, n_dot_l, roughness) * fresnel / (4.0 * n_dot_v * n_dot_l + 0.0001);
+        vec3 diffuse = (1.0 - fresnel) * (1.0 - metallic) * base_color.rgb / PI;
+
+        float shadow = compute_shadow(light.shadow_index, light.type == LIGHT_DIRECTIONAL, vertex_position, normal, light_direction);
+        color += (diffuse + specular) * light.color * attenuation * n_dot_l * shadow;
+    }
+
+    if (has_environment) {
+        vec3 fresnel = fresnel_schlick_roughness(n_dot_v, f0, roughness);
+        vec3 diffuse = (1.0 - fresnel) * (1.0 - metallic) * texture(irradiance_map, normal).rgb * base_color.rgb;
+        vec3 reflection = reflect(-view_direction, normal);
+        vec3 prefiltered = textureLod(prefilter_map, reflection, roughness * prefilter_max_lod).rgb;
+        vec2 brdf = texture(brdf_lut, vec2(n_dot_v, roughness)).rg;
+        vec3 specular = prefiltered * (fresnel * brdf.x + brdf.y);
+        color += (diffuse + specular) * occlusion * environment_intensity;
+    } else {
+        color += vec3(0.03) * base_color.rgb * occlusion;
+    }
+    color += emissive;
+
+    color = color / (color + vec3(1.0)); // Reinhard 톤 매핑
+    fragment_color = vec4(pow(color, vec3(1.0 / 2.2)), base_color.a); // 감마 보정
+}
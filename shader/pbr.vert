@@ -0,0 +1,26 @@
+#version 330 core
+
+layout (location = 0) in vec3 position; // vao의 0번 속성으로 자동 할당
+layout (location = 1) in vec3 normal; // vao의 1번 속성으로 자동 할당
+layout (location = 2) in vec2 texture_coord; // vao의 2번 속성으로 자동 할당
+layout (location = 3) in vec4 tangent; // vao의 3번 속성으로 자동 할당, w는 bitangent의 방향
+
+uniform mat4 transform; // projection * view * model
+uniform mat4 model;
+uniform mat3 normal_matrix;
+
+out vec3 vertex_position; // world 공간의 위치
+out vec2 vertex_texture_coord;
+out mat3 vertex_tbn; // tangent 공간에서 world 공간으로 옮기는 행렬
+
+void main() {
+    gl_Position = transform * vec4(position, 1.0);
+    vertex_position = (model * vec4(position, 1.0)).xyz;
+    vertex_texture_coord = texture_coord;
+
+    vec3 n = normalize(normal_matrix * normal);
+    vec3 t = normalize(mat3(model) * tangent.xyz);
+    t = normalize(t - dot(t, n) * n); // 그람-슈미트 직교화
+    vec3 b = cross(n, t) * tangent.w;
+    vertex_tbn = mat3(t, b, n);
+}
@@ -0,0 +1,70 @@
+#version 330 core
+
+uniform samplerCube environment_map;
+uniform float roughness;
+uniform float environment_size; // 환경 맵 한 면의 해상도
+
+in vec3 local_position;
+
+out vec4 fragment_color;
+
+const float PI = 3.14159265359;
+const uint SAMPLE_COUNT = 1024u;
+
+float distribution_ggx(float n_dot_h, float roughness) {
+    float a = roughness * roughness;
+    float a2 = a * a;
+    float denominator = n_dot_h * n_dot_h * (a2 - 1.0) + 1.0;
+    return a2 / (PI * denominator * denominator);
+}
+
+// 저불일치 수열, 무작위 샘플보다 고르게 분포한다
+vec2 hammersley(uint index, uint count) {
+    uint bits = index;
+    bits = (bits << 16u) | (bits >> 16u);
+    bits = ((bits & 0x55555555u) << 1u) | ((bits & 0xAAAAAAAAu) >> 1u);
+    bits = ((bits & 0x33333333u) << 2u) | ((bits & 0xCCCCCCCCu) >> 2u);
+    bits = ((bits & 0x0F0F0F0Fu) << 4u) | ((bits & 0xF0F0F0F0u) >> 4u);
+    bits = ((bits & 0x00FF00FFu) << 8u) | ((bits & 0xFF00FF00u) >> 8u);
+    return vec2(float(index) / float(count), float(bits) * 2.3283064365386963e-10);
+}
+
+// GGX 분포를 따르는 half vector를 샘플링한다
+vec3 importance_sample_ggx(vec2 xi, vec3 normal, float roughness) {
+    float a = roughness * roughness;
+    float phi = 2.0 * PI * xi.x;
+    float cos_theta = sqrt((1.0 - xi.y) / (1.0 + (a * a - 1.0) * xi.y));
+    float sin_theta = sqrt(1.0 - cos_theta * cos_theta);
+    vec3 h = vec3(cos(phi) * sin_theta, sin(phi) * sin_theta, cos_theta);
+
+    vec3 up = abs(normal.z) < 0.999 ? vec3(0.0, 0.0, 1.0) : vec3(1.0, 0.0, 0.0);
+    vec3 tangent = normalize(cross(up, normal));
+    vec3 bitangent = cross(normal, tangent);
+    return normalize(tangent * h.x + bitangent * h.y + normal * h.z);
+}
+
+// 거칠기별로 환경 맵을 흐리게 만들어 mip level에 담는다 (specular IBL), 시선 방향은 normal과 같다고 가정한다
+void main() {
+    vec3 normal = normalize(local_position);
+    vec3 view = normal;
+
+    vec3 color = vec3(0.0);
+    float total_weight = 0.0;
+    for (uint index = 0u; index < SAMPLE_COUNT; index++) {
+        vec3 h = importance_sample_ggx(hammersley(index, SAMPLE_COUNT), normal, roughness);
+        vec3 light = normalize(2.0 * dot(view, h) * h - view);
+        float n_dot_l = max(dot(normal, light), 0.0);
+        if (n_dot_l > 0.0) {
+            // 확률 밀도가 낮은 샘플은 더 넓은 영역을 대표하므로 높은 mip level에서 읽는다
+            float n_dot_h = max(dot(normal, h), 0.0);
+            float pdf = distribution_ggx(n_dot_h, roughness) * 0.25 + 0.0001;
+            float texel_solid_angle = 4.0 * PI / (6.0 * environment_size * environment_size);
+            float sample_solid_angle = 1.0 / (float(SAMPLE_COUNT) * pdf + 0.0001);
+            float mip_level = roughness == 0.0 ? 0.0 : 0.5 * log2(sample_solid_angle / texel_solid_angle);
+
+            color += textureLod(environment_map, light, mip_level).rgb * n_dot_l;
+            total_weight += n_dot_l;
+        }
+    }
+    fragment_color = vec4(color / total_weight, 1.0);
+}
@@ -0,0 +1,5 @@
+#version 330 core
+
+// 깊이만 기록하므로 출력할 색상이 없다
+void main() {
+}
@@ -0,0 +1,62 @@
+// 그림자 맵 atlas를 읽는 함수, lighting.frag와 pbr.frag에서 #include 한다
+
+// MAX_SHADOW_COUNT는 프로그램을 만들 때 #define으로 주입된다
+#ifndef MAX_SHADOW_COUNT
+#define MAX_SHADOW_COUNT 8
+#endif
+#define MAX_CASCADE_COUNT 4
+
+uniform sampler2D shadow_map;
+uniform mat4 shadow_matrices[MAX_SHADOW_COUNT]; // world 공간에서 atlas의 (u, v, depth)로 옮기는 행렬
+uniform vec4 shadow_tiles[MAX_SHADOW_COUNT]; // atlas에서 각 그림자 맵이 차지하는 영역 (min.xy, max.xy)
+uniform float shadow_bias;
+uniform float shadow_normal_bias;
+uniform int shadow_pcf_radius;
+uniform int cascade_count;
+uniform float cascade_splits[MAX_CASCADE_COUNT]; // 각 캐스케이드의 먼 쪽 거리
+uniform mat4 view_matrix;
+
+// 0이면 완전히 그림자 안, 1이면 완전히 빛을 받는다
+float compute_shadow(int shadow_index, bool directional, vec3 position, vec3 normal, vec3 light_direction) {
+    if (shadow_index < 0) {
+        return 1.0;
+    }
+    int index = shadow_index;
+    if (directional) {
+        // 카메라에서 멀어질수록 더 넓은 영역을 덮는 캐스케이드를 사용한다
+        float depth = -(view_matrix * vec4(position, 1.0)).z;
+        if (depth > cascade_splits[cascade_count - 1]) {
+            return 1.0;
+        }
+        for (int cascade = 0; cascade < cascade_count; cascade++) {
+            if (depth <= cascade_splits[cascade]) {
+                index += cascade;
+                break;
+            }
+        }
+    }
+
+    float n_dot_l = clamp(dot(normal, light_direction), 0.0, 1.0);
+    vec4 shadow_position = shadow_matrices[index] * vec4(position + normal * shadow_normal_bias * (1.0 - n_dot_l), 1.0);
+    vec3 coord = shadow_position.xyz / shadow_position.w;
+    vec4 tile = shadow_tiles[index];
+    if (coord.z > 1.0 || any(lessThan(coord.xy, tile.xy)) || any(greaterThan(coord.xy, tile.zw))) {
+        return 1.0;
+    }
+    // 빛과 비스듬한 표면일수록 bias를 키운다
+    float bias = max(shadow_bias * (1.0 - n_dot_l), shadow_bias * 0.1);
+
+    // percentage-closer filtering: 주변 텍셀과 비교한 결과를 평균 낸다, 이웃한 타일을 읽지 않도록 영역 안으로 제한한다
+    vec2 texel_size = 1.0 / vec2(textureSize(shadow_map, 0));
+    float lit = 0.0;
+    float count = 0.0;
+    for (int x = -shadow_pcf_radius; x <= shadow_pcf_radius; x++) {
+        for (int y = -shadow_pcf_radius; y <= shadow_pcf_radius; y++) {
+            vec2 uv = clamp(coord.xy + vec2(x, y) * texel_size, tile.xy + 0.5 * texel_size, tile.zw - 0.5 * texel_size);
+            float closest_depth = texture(shadow_map, uv).r;
+            lit += coord.z - bias > closest_depth ? 0.0 : 1.0;
+            count += 1.0;
+        }
+    }
+    return lit / count;
+}
@@ -0,0 +1,9 @@
+#version 330 core
+
+layout (location = 0) in vec3 position;
+
+uniform mat4 transform; // 광원 시점의 projection * view * model
+
+void main() {
+    gl_Position = transform * vec4(position, 1.0);
+}
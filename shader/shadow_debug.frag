@@ -0,0 +1,12 @@
+#version 330 core
+
+uniform sampler2D shadow_map;
+
+in vec2 vertex_texture_coord;
+
+out vec4 fragment_color;
+
+void main() {
+    float depth = texture(shadow_map, vertex_texture_coord).r;
+    fragment_color = vec4(vec3(depth), 1.0);
+}
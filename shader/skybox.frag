@@ -0,0 +1,13 @@
+#version 330 core
+
+uniform samplerCube environment_map;
+
+in vec3 local_position;
+
+out vec4 fragment_color;
+
+void main() {
+    vec3 color = texture(environment_map, local_position).rgb;
+    color = color / (color + vec3(1.0)); // Reinhard 톤 매핑
+    fragment_color = vec4(pow(color, vec3(1.0 / 2.2)), 1.0); // 감마 보정
+}
@@ -0,0 +1,14 @@
+#version 330 core
+
+layout (location = 0) in vec3 position;
+
+uniform mat4 projection;
+uniform mat4 view; // 평행 이동을 뺀 view 행렬
+
+out vec3 local_position;
+
+void main() {
+    local_position = position;
+    vec4 clip_position = projection * view * vec4(position, 1.0);
+    gl_Position = clip_position.xyww; // 깊이를 항상 1.0(가장 먼 곳)으로 만든다
+}